@@ -1,70 +1,129 @@
-use criterion::{Criterion, black_box, criterion_group, criterion_main};
-use netmap_rs::prelude::*;
+//! Round-trip latency benchmarks.
+//!
+//! With the `sys` feature the packets cross a netmap pipe, which needs the netmap
+//! kernel module but neither root nor a NIC. Without `sys` the same benchmarks run
+//! over the in-process fallback channel, measuring the userspace ring logic alone.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use std::time::Duration;
 
-fn single_packet_latency(c: &mut Criterion) {
-    let nm = NetmapBuilder::new("netmap:eth0")
-        .num_tx_rings(1)
-        .num_rx_rings(1)
-        .open()
-        .expect("Failed to Open Netmap interface");
+const PAYLOAD_LEN: usize = 64;
+const BATCH_SIZE: usize = 32;
+
+#[cfg(feature = "sys")]
+mod pipe {
+    use super::*;
+    use netmap_rs::prelude::*;
+
+    const PIPE_NAME: &str = "netmap:pipe{bench_latency}";
+
+    /// Opens both endpoints of the benchmark pipe.
+    fn open_pipe() -> (Netmap, Netmap) {
+        let open = || {
+            NetmapBuilder::new(PIPE_NAME)
+                .num_tx_rings(1)
+                .num_rx_rings(1)
+                .build()
+                .expect("Failed to open netmap pipe (is the netmap module loaded?)")
+        };
+        let tx_end = open();
+        let rx_end = open();
+        (tx_end, rx_end)
+    }
 
-    let mut tx_ring = nm.tx_ring(0).expect("Failed to get TX ring");
-    let mut rx_ring = nm.rx_ring(0).expect("Failed to get RX ring");
-    let payload = vec![0u8; 64]; // 64 byte packet
+    pub fn single_packet_latency(c: &mut Criterion) {
+        let (tx_end, rx_end) = open_pipe();
+        let mut tx_ring = tx_end.tx_ring(0).expect("Failed to get TX ring");
+        let mut rx_ring = rx_end.rx_ring(0).expect("Failed to get RX ring");
+        let payload = vec![0u8; PAYLOAD_LEN];
 
-    c.bench_function(
-        "single_packet_round_trip" | b | {
+        c.bench_function("pipe_single_packet_round_trip", |b| {
             b.iter(|| {
                 tx_ring.send(black_box(&payload)).expect("Send failed");
                 tx_ring.sync();
 
                 while rx_ring.recv().is_none() {
-                    // spin until the packet is received
+                    rx_ring.sync();
+                }
+            });
+        });
+    }
+
+    pub fn batch_latency(c: &mut Criterion) {
+        let (tx_end, rx_end) = open_pipe();
+        let mut tx_ring = tx_end.tx_ring(0).expect("Failed to get TX ring");
+        let mut rx_ring = rx_end.rx_ring(0).expect("Failed to get RX ring");
+        let payload = vec![0u8; PAYLOAD_LEN];
+
+        c.bench_function(&format!("pipe_batch_{}_packets", BATCH_SIZE), |b| {
+            b.iter(|| {
+                let mut reservation = tx_ring
+                    .reserve_batch(black_box(BATCH_SIZE))
+                    .expect("Reservation failed");
+
+                for i in 0..BATCH_SIZE {
+                    let pkt = reservation
+                        .packet(i, payload.len())
+                        .expect("Packet access failed");
+                    pkt.copy_from_slice(&payload);
+                }
+
+                reservation.commit();
+                tx_ring.sync();
+
+                let mut received = 0;
+                while received < BATCH_SIZE {
+                    if rx_ring.recv().is_some() {
+                        received += 1;
+                    } else {
+                        rx_ring.sync();
+                    }
                 }
             });
-        },
-    );
+        });
+    }
 }
 
-fn batch_latency(c: &mut Criterion) {
-    let nm = NetmapBuilder::new("netmap:eth0")
-        .num_tx_rings(1)
-        .num_rx_rings(1)
-        .open()
-        .expect("Failed to open Netmap interface");
-
-    let mut tx_ring = nm.tx_ring(0).expect("Failed to get TX ring");
-    let mut rx_ring = nm.rx_ring(0).expect("Failed to get RX ring");
-    let payload = vec![0u8; 64];
-    let batch_size = 32;
-
-    c.bench_function(&format!("batch_{}_packets", batch_size), |b| {
-        b.iter(|| {
-            let mut reservation = tx_ring
-                .reserve_batch(black_box(batch_size))
-                .expect("Reservation failed");
-
-            for i in 0..batch_size {
-                let pkt = reservation
-                    .packet(i, payload.len())
-                    .expect("Packet access failed");
-                pkt.copy_from_slice(&payload);
-            }
-
-            reservation.commit();
-            tx_ring.sync();
-
-            let mut received = 0;
-            while received < batch_size {
-                if rx_ring.recv().is_none() {
-                    received += 1;
+#[cfg(not(feature = "sys"))]
+mod fallback {
+    use super::*;
+    use netmap_rs::fallback::create_fallback_channel;
+
+    pub fn single_packet_latency(c: &mut Criterion) {
+        let (tx, rx) = create_fallback_channel(BATCH_SIZE);
+        let payload = vec![0u8; PAYLOAD_LEN];
+
+        c.bench_function("fallback_single_packet_round_trip", |b| {
+            b.iter(|| {
+                tx.send(black_box(&payload)).expect("Send failed");
+                black_box(rx.recv().expect("Packet missing"));
+            });
+        });
+    }
+
+    pub fn batch_latency(c: &mut Criterion) {
+        let (tx, rx) = create_fallback_channel(BATCH_SIZE);
+        let payload = vec![0u8; PAYLOAD_LEN];
+
+        c.bench_function(&format!("fallback_batch_{}_packets", BATCH_SIZE), |b| {
+            b.iter(|| {
+                for _ in 0..BATCH_SIZE {
+                    tx.send(black_box(&payload)).expect("Send failed");
+                }
+                for _ in 0..BATCH_SIZE {
+                    black_box(rx.recv().expect("Packet missing"));
                 }
-            }
+            });
         });
-    });
+    }
 }
 
+#[cfg(feature = "sys")]
+use pipe::{batch_latency, single_packet_latency};
+
+#[cfg(not(feature = "sys"))]
+use fallback::{batch_latency, single_packet_latency};
+
 criterion_group! {
     name = benches;
     config = Criterion::default()
@@ -0,0 +1,28 @@
+//! Minimal benchmark that only touches pure-Rust code, so `cargo bench` can confirm the
+//! criterion harness builds and runs on any machine, with or without the `sys` feature.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use netmap_rs::packet::flow_hash;
+use netmap_rs::prelude::*;
+use std::time::Duration;
+
+fn frame_and_hash(c: &mut Criterion) {
+    let packet = vec![0u8; 64];
+
+    c.bench_function("smoke_frame_flow_hash", |b| {
+        b.iter(|| {
+            let frame = Frame::new(black_box(&packet));
+            flow_hash(frame.payload())
+        });
+    });
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default()
+        .warm_up_time(Duration::from_millis(100))
+        .measurement_time(Duration::from_millis(500));
+    targets = frame_and_hash
+}
+
+criterion_main!(benches);
@@ -1,59 +1,180 @@
-use criterion::{Criterion, Throughput, black_box, criterion_group, criterion_main};
-use netmap_rs::prelude::*;
+//! Throughput benchmarks.
+//!
+//! With the `sys` feature the packets cross a netmap pipe, which needs the netmap
+//! kernel module but neither root nor a NIC. Without `sys` the same packet sizes are
+//! pushed through the in-process fallback channel, measuring the userspace ring logic
+//! alone.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
 use std::time::Duration;
 
-fn throughput(c: &mut Criterion) {
-    let nm = NetmapBuilder::new("netmap:eth0")
-        .num_tx_rings(1)
-        .num_rx_rings(1)
-        .open()
-        .expect("Failed to open Netmap interface");
-
-    let mut tx_ring = nm.tx_ring(0).expect("Failed to get TX ring");
-    let mut rx_ring = nm.rx_ring(0).expect("Failed to get RX ring");
-
-    let mut group = c.benchmark_group("throughput");
-    group.measurement_time(Duration::from_secs(5));
-
-    for size in [64, 128, 256, 512, 1024, 1500].iter() {
-        group.throughput(Throughput::Bytes(*size as u64));
-
-        let payload = vec![0u8: *size];
-        let batch_size = 64;
-
-        group.benchmark_group(&format!("{}_bytes", size), |b| {
-            b.iter(|| {
-                // send batch
-                let mut reservation = tx_ring
-                    .reserve_batch(black_box(batch_size))
-                    .expect("Reservation failed");
-
-                for i in 0..batch_size {
-                    let pkt = reservation
-                        .packet(i, payload.len())
-                        .expect("Packet access failed");
-                    pkt.copy_from_slice(&payload);
-                }
-
-                reservation.commit();
-                tx_ring.sync();
-
-                // receive batch
-                let mut frames = vec![Frame::default(); batch_size];
-                let mut received = 0;
-                while received < batch_size {
-                    received += rx_ring.recv_batch(&mut frames[received..]);
-                }
+const PACKET_SIZES: [usize; 6] = [64, 128, 256, 512, 1024, 1500];
+const BATCH_SIZE: usize = 64;
+
+#[cfg(feature = "sys")]
+mod pipe {
+    use super::*;
+    use netmap_rs::prelude::*;
+
+    const PIPE_NAME: &str = "netmap:pipe{bench_throughput}";
+
+    /// Opens both endpoints of the benchmark pipe.
+    fn open_pipe() -> (Netmap, Netmap) {
+        let open = || {
+            NetmapBuilder::new(PIPE_NAME)
+                .num_tx_rings(1)
+                .num_rx_rings(1)
+                .build()
+                .expect("Failed to open netmap pipe (is the netmap module loaded?)")
+        };
+        let tx_end = open();
+        let rx_end = open();
+        (tx_end, rx_end)
+    }
+
+    /// Receives `count` packets so the pipe never fills up between iterations.
+    fn drain(rx_ring: &mut RxRing<'_>, count: usize) {
+        let mut received = 0;
+        while received < count {
+            if rx_ring.recv().is_some() {
+                received += 1;
+            } else {
+                rx_ring.sync();
+            }
+        }
+    }
+
+    pub fn throughput(c: &mut Criterion) {
+        let (tx_end, rx_end) = open_pipe();
+        let mut tx_ring = tx_end.tx_ring(0).expect("Failed to get TX ring");
+        let mut rx_ring = rx_end.rx_ring(0).expect("Failed to get RX ring");
+
+        let mut group = c.benchmark_group("pipe_throughput");
+        group.measurement_time(Duration::from_secs(5));
+
+        for size in PACKET_SIZES.iter() {
+            group.throughput(Throughput::Bytes((*size * BATCH_SIZE) as u64));
+
+            let payload = vec![0u8; *size];
+
+            group.bench_function(format!("{}_bytes", size), |b| {
+                b.iter(|| {
+                    // send batch
+                    let mut reservation = tx_ring
+                        .reserve_batch(black_box(BATCH_SIZE))
+                        .expect("Reservation failed");
+
+                    for i in 0..BATCH_SIZE {
+                        let pkt = reservation
+                            .packet(i, payload.len())
+                            .expect("Packet access failed");
+                        pkt.copy_from_slice(&payload);
+                    }
+
+                    reservation.commit();
+                    tx_ring.sync();
+
+                    // receive batch
+                    drain(&mut rx_ring, BATCH_SIZE);
+                });
             });
-        });
+        }
+
+        group.finish();
     }
 
-    group.finish();
+    /// Per-packet `send` (which publishes `head`/`cur` for every packet) against one
+    /// `reserve_batch` + `commit_lens` (which publishes them once, behind a single fence).
+    pub fn commit_strategies(c: &mut Criterion) {
+        let (tx_end, rx_end) = open_pipe();
+        let mut tx_ring = tx_end.tx_ring(0).expect("Failed to get TX ring");
+        let mut rx_ring = rx_end.rx_ring(0).expect("Failed to get RX ring");
+        let payload = vec![0u8; 64];
+
+        let mut group = c.benchmark_group("tx_commit");
+        for batch_size in [32usize, 256].iter() {
+            group.throughput(Throughput::Elements(*batch_size as u64));
+
+            group.bench_function(format!("per_packet_{}", batch_size), |b| {
+                b.iter(|| {
+                    for _ in 0..*batch_size {
+                        tx_ring.send(black_box(&payload)).expect("Send failed");
+                    }
+                    tx_ring.sync();
+                    drain(&mut rx_ring, *batch_size);
+                });
+            });
+
+            let lens = vec![payload.len(); *batch_size];
+            group.bench_function(format!("batch_fence_{}", batch_size), |b| {
+                b.iter(|| {
+                    let mut reservation = tx_ring
+                        .reserve_batch(black_box(*batch_size))
+                        .expect("Reservation failed");
+                    for i in 0..*batch_size {
+                        reservation.buffer(i).expect("Buffer access failed")[..payload.len()]
+                            .copy_from_slice(&payload);
+                    }
+                    reservation.commit_lens(&lens).expect("Commit failed");
+                    tx_ring.sync();
+                    drain(&mut rx_ring, *batch_size);
+                });
+            });
+        }
+
+        group.finish();
+    }
+}
+
+#[cfg(not(feature = "sys"))]
+mod fallback {
+    use super::*;
+    use netmap_rs::fallback::create_fallback_channel;
+
+    pub fn throughput(c: &mut Criterion) {
+        let (tx, rx) = create_fallback_channel(BATCH_SIZE);
+
+        let mut group = c.benchmark_group("fallback_throughput");
+        group.measurement_time(Duration::from_secs(5));
+
+        for size in PACKET_SIZES.iter() {
+            group.throughput(Throughput::Bytes((*size * BATCH_SIZE) as u64));
+
+            let payload = vec![0u8; *size];
+
+            group.bench_function(format!("{}_bytes", size), |b| {
+                b.iter(|| {
+                    for _ in 0..BATCH_SIZE {
+                        tx.send(black_box(&payload)).expect("Send failed");
+                    }
+                    for _ in 0..BATCH_SIZE {
+                        black_box(rx.recv().expect("Packet missing").len());
+                    }
+                });
+            });
+        }
+
+        group.finish();
+    }
+}
+
+#[cfg(feature = "sys")]
+use pipe::{commit_strategies, throughput};
+
+#[cfg(not(feature = "sys"))]
+use fallback::throughput;
+
+#[cfg(feature = "sys")]
+criterion_group! {
+    name = benches;
+    config = Criterion::default().warm_up_time(Duration::from_secs(1));
+    targets = throughput, commit_strategies
 }
 
+#[cfg(not(feature = "sys"))]
 criterion_group! {
     name = benches;
-    config = Criterion::default().warm_up_time(Duration::from_secs(!));
+    config = Criterion::default().warm_up_time(Duration::from_secs(1));
     targets = throughput
 }
 
@@ -89,8 +89,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                     }
                     
                     // Test batch receive
-                    let mut recv_batch = vec![Frame::new_owned(vec![0u8; 1500]); 10];
-                    let received = rx_ring.recv_batch(&mut recv_batch);
+                    // Frames borrow the ring buffers directly; no copies are made
+                    let received = rx_ring.recv_batch_borrowed(10).count();
                     if received > 0 {
                         println!("✓ Received {} packets in batch", received);
                     } else {
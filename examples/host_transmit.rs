@@ -95,8 +95,8 @@ fn build_udp_packet(
     packet.extend_from_slice(&dst_port.to_be_bytes()); // Destination Port
     let udp_len = (UDP_HDR_LEN + payload.len()) as u16;
     packet.extend_from_slice(&udp_len.to_be_bytes()); // Length
-    packet.extend_from_slice(&[0x00, 0x00]); // Checksum (optional for IPv4, 0 means no checksum)
-                                            // Calculating UDP checksum requires pseudo-header, skipping for simplicity.
+    packet.extend_from_slice(&[0x00, 0x00]); // Checksum (0 = none); `send_to_host` fills it in,
+                                            // since some host stacks drop UDP without one.
 
     // Payload
     packet.extend_from_slice(payload);
@@ -161,12 +161,16 @@ fn main() -> Result<(), Box<dyn Error>> {
 
     println!("Sending packet ({} bytes) to host stack: {:02X?}", packet.len(), &packet[..std::cmp::min(packet.len(), 48)]);
 
-    match tx_ring.send(&packet) {
+    match tx_ring.send_to_host(&packet) {
         Ok(_) => {
             tx_ring.sync(); // Ensure packet is processed
             println!("Packet sent successfully to host stack via Netmap.");
             println!("Try listening with: sudo tcpdump -i <base_if_name> -n udp port {} and host {}", dst_port, dst_ip);
         }
+        Err(e @ netmap_rs::Error::ExceedsHostMtu(_)) => {
+            eprintln!("Packet does not fit through {}: {}", if_name, e);
+            return Err(Box::new(e));
+        }
         Err(e) => {
             eprintln!("Failed to send packet: {:?}", e);
             return Err(Box::new(e));
@@ -177,7 +181,7 @@ fn main() -> Result<(), Box<dyn Error>> {
     for i in 0..3 {
         let dynamic_payload = format!("Hello #{} from netmap-rs to host stack!", i);
         let packet = build_udp_packet(src_mac, dst_mac, src_ip, dst_ip, src_port, dst_port, dynamic_payload.as_bytes());
-        if tx_ring.send(&packet).is_ok() {
+        if tx_ring.send_to_host(&packet).is_ok() {
             println!("Sent dynamic packet #{}", i);
         } else {
             eprintln!("Failed to send dynamic packet #{}", i);
@@ -1,40 +1,77 @@
+//! Round trips over a netmap pipe.
+//!
+//! Both endpoints of `pipe{ping_pong}` are opened in this process, so the example needs
+//! the netmap kernel module but no physical NIC. One endpoint sends `ping <n>`, the other
+//! answers with `pong <n>`, and the round-trip time of each exchange is printed.
 #![cfg(feature = "sys")]
 
 use netmap_rs::prelude::*;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+
+const PIPE_NAME: &str = "netmap:pipe{ping_pong}";
+const ROUNDS: usize = 5;
+const TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Waits up to `TIMEOUT` for the next packet on `rx_ring` and returns a copy of it.
+fn recv_timeout(rx_ring: &mut RxRing) -> Result<Vec<u8>, Error> {
+    let deadline = Instant::now() + TIMEOUT;
+    loop {
+        if let Some(frame) = rx_ring.recv() {
+            return Ok(frame.payload().to_vec());
+        }
+        if Instant::now() >= deadline {
+            return Err(Error::Timeout);
+        }
+        rx_ring.sync();
+        std::thread::sleep(Duration::from_micros(50));
+    }
+}
 
 fn main() -> Result<(), Error> {
-    let nm = NetmapBuilder::new("netmap:eth0")
-        .nm_tx_rings(1)
+    let pinger = NetmapBuilder::new(PIPE_NAME)
+        .num_tx_rings(1)
         .num_rx_rings(1)
         .build()?;
+    let ponger = NetmapBuilder::new(PIPE_NAME)
+        .num_tx_rings(1)
+        .num_rx_rings(1)
+        .build()?;
+
+    let mut ping_tx = pinger.tx_ring(0)?;
+    let mut ping_rx = pinger.rx_ring(0)?;
+    let mut pong_tx = ponger.tx_ring(0)?;
+    let mut pong_rx = ponger.rx_ring(0)?;
+
+    for round in 0..ROUNDS {
+        let ping = format!("ping {}", round);
+        let start = Instant::now();
 
-    let mut tx_ring = nm.tx_ring(0)?;
-    let mut rx_ring = nm.rx_ring(0)?;
-
-    // send a packet
-    tx_ring.send(b"hello world")?;
-    tx_ring.sync();
-
-    // receive packets
-    let mut received = false;
-    for _ in 0..10 {
-        // try a few times
-        while let Some(frame) = rx_ring.recv() {
-            println!("Received packet: {:?}", frame.payload());
-            assert_eq!(frame.payload(), b"hello world");
-            received = true;
-            break;
+        ping_tx.send(ping.as_bytes())?;
+        ping_tx.sync();
+
+        // the other endpoint answers every ping with the matching pong
+        let request = recv_timeout(&mut pong_rx)?;
+        if request != ping.as_bytes() {
+            return Err(Error::MalformedPacket(format!(
+                "expected {:?}, got {:?}",
+                ping,
+                String::from_utf8_lossy(&request)
+            )));
         }
-        if received {
-            break;
+        let pong = format!("pong {}", round);
+        pong_tx.send(pong.as_bytes())?;
+        pong_tx.sync();
+
+        let reply = recv_timeout(&mut ping_rx)?;
+        if reply != pong.as_bytes() {
+            return Err(Error::MalformedPacket(format!(
+                "expected {:?}, got {:?}",
+                pong,
+                String::from_utf8_lossy(&reply)
+            )));
         }
-        std::thread::sleep(Duration::from_millis(100)); // wait for packets
-        rx_ring.sync(); // tell kernel we are done with previous packets
-    }
 
-    if !received {
-        return Err(Error::WouldBlock); // or some other error
+        println!("{} -> {} in {:?}", ping, pong, start.elapsed());
     }
 
     Ok(())
@@ -11,6 +11,9 @@
 //! cargo run --example pipe_receiver_process --features sys
 //!
 //! (Then run `pipe_sender_process` in another terminal)
+//!
+//! The receiver also stops as soon as the sender exits, which it learns from
+//! `RxRing::peer_closed` once every packet sent before the exit has been read.
 
 use std::error::Error;
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -71,6 +74,10 @@ fn main() -> Result<(), Box<dyn Error>> {
         }
         if !running.load(Ordering::Relaxed) { break; }
 
+        if received_in_batch == 0 && rx_ring.peer_closed() {
+            println!("[Receiver Process] Sender closed its end of the pipe.");
+            break;
+        }
         if received_in_batch == 0 {
             thread::sleep(Duration::from_millis(100)); // Wait if no packets
         }
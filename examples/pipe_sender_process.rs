@@ -9,7 +9,8 @@
 //! Usage:
 //! cargo run --example pipe_sender_process --features sys
 //!
-//! (Ensure `pipe_receiver_process` is running or started shortly after)
+//! If the receiver is not up yet, the sender waits up to `PEER_WAIT` for it
+//! to appear before giving up.
 
 use std::error::Error;
 use std::thread;
@@ -18,6 +19,7 @@ use std::time::Duration;
 use netmap_rs::prelude::*;
 
 const PIPE_NAME: &str = "netmap:pipe{interproc_example_789}"; // Must match receiver
+const PEER_WAIT: Duration = Duration::from_secs(10);
 const NUM_PACKETS_IPC: usize = 3;
 const PACKET_BASE_PAYLOAD_IPC: &[u8] = b"IPC Hello from Process A, msg=";
 
@@ -28,6 +30,7 @@ fn main() -> Result<(), Box<dyn Error>> {
     let pipe_ep = NetmapBuilder::new(PIPE_NAME)
         .num_tx_rings(1) // Pipes default to 1 TX, 1 RX.
         .num_rx_rings(1)
+        .pipe_wait_peer(PEER_WAIT)
         .build()
         .map_err(|e| format!("[Sender Process] Failed to open pipe endpoint: {:?}. Is receiver running?", e))?;
 
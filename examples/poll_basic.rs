@@ -3,6 +3,9 @@
 //! This example demonstrates how to use `poll()` (via the `polling` crate)
 //! with Netmap file descriptors to wait for I/O readiness without busy-looping.
 //!
+//! For a plain blocking wait without an extra dependency, see `poll_builtin`, which
+//! uses `Netmap::poll`; this example is for plugging Netmap into an existing reactor.
+//!
 //! It sets up two Netmap pipe endpoints for intra-process communication:
 //! - `pipe_a`: Acts as the sender.
 //! - `pipe_b`: Acts as the receiver.
@@ -17,11 +20,11 @@
 //! cargo run --example poll_basic --features sys
 
 use std::error::Error;
-use std::os::unix::io::AsRawFd;
+use std::os::unix::io::{AsRawFd, BorrowedFd};
 use std::time::Duration;
 
 use netmap_rs::prelude::*;
-use polling::{Event, Poller};
+use polling::{Event, Events, Poller};
 
 // Use a unique pipe name for this example
 const PIPE_NAME_POLL: &str = "netmap:pipe{poll_example_456}";
@@ -32,13 +35,13 @@ fn main() -> Result<(), Box<dyn Error>> {
 
     // --- Setup Pipe Endpoints ---
     // Endpoint A will send, Endpoint B will receive.
-    let mut pipe_a = NetmapBuilder::new(PIPE_NAME_POLL)
+    let pipe_a = NetmapBuilder::new(PIPE_NAME_POLL)
         .num_tx_rings(1)
         .num_rx_rings(1) // Though not used for RX in A for this simple example
         .build()
         .expect("Failed to open pipe endpoint A");
 
-    let mut pipe_b = NetmapBuilder::new(PIPE_NAME_POLL)
+    let pipe_b = NetmapBuilder::new(PIPE_NAME_POLL)
         .num_tx_rings(1) // Though not used for TX in B for this simple example
         .num_rx_rings(1)
         .build()
@@ -50,20 +53,26 @@ fn main() -> Result<(), Box<dyn Error>> {
     // --- Polling Setup ---
     // Get the raw file descriptor for pipe_b (receiver)
     let fd_b = pipe_b.as_raw_fd();
+    let fd_a = pipe_a.as_raw_fd();
+    // Safety: both descriptors stay open until `pipe_a`/`pipe_b` drop at the end of `main`.
+    let (borrowed_a, borrowed_b) = unsafe { (BorrowedFd::borrow_raw(fd_a), BorrowedFd::borrow_raw(fd_b)) };
 
     // Create a Poller
     let poller = Poller::new().expect("Failed to create Poller");
 
     // Register fd_b for readability (POLLIN). Key 0 is arbitrary for this example.
     // We use level-triggered polling by default with the `polling` crate.
-    poller.add(fd_b, Event::readable(0)).expect("Failed to register fd_b with Poller");
+    // Safety: the descriptors are deleted from the poller before they are closed.
+    unsafe { poller.add(fd_b, Event::readable(0)) }.expect("Failed to register fd_b with Poller");
+    // fd_a starts with no interest; it is armed for writability only while a send is pending.
+    unsafe { poller.add(fd_a, Event::none(1)) }.expect("Failed to register fd_a with Poller");
 
     let mut packets_sent = 0;
     let mut packets_received = 0;
     let mut main_loop_iterations = 0;
 
     // Buffer for poll events
-    let mut events = Vec::new();
+    let mut events = Events::new();
 
     println!("Starting event loop. Will send {} packets.", NUM_PACKETS_TO_SEND);
     println!("Monitoring pipe_b's fd ({}) for readable events (packets from pipe_a).", fd_b);
@@ -79,23 +88,25 @@ fn main() -> Result<(), Box<dyn Error>> {
             // For simplicity here, we try to send directly if space is likely.
             // Let's add a simple POLLOUT check for demonstration:
 
-            let fd_a = pipe_a.as_raw_fd();
-            // Temporarily add fd_a for writability check
-            poller.add(fd_a, Event::writable(1)).expect("Failed to add fd_a for write polling");
+            // Temporarily arm fd_a for a writability check
+            poller.modify(borrowed_a, Event::writable(1)).expect("Failed to arm fd_a for write polling");
 
             // Wait for a short time to see if fd_a becomes writable
             match poller.wait(&mut events, Some(Duration::from_millis(0))) { // Non-blocking check
                 Ok(_) => {
                     let mut can_write_to_a = false;
-                    for ev in &events {
+                    for ev in events.iter() {
                         if ev.key == 1 && ev.writable { // Event for fd_a and it's writable
                             can_write_to_a = true;
                             break;
                         }
                     }
-                    if can_write_to_a || tx_a.num_slots() - (tx_a.head() - tx_a.tail() + tx_a.num_slots() as u32) % tx_a.num_slots() as u32 > 1 { // Heuristic: check space if poll didn't signal
+                    // Free TX slots are those from head up to (not including) tail.
+                    let num_slots = tx_a.num_slots() as u32;
+                    let free_slots = (tx_a.tail() + num_slots - tx_a.head()) % num_slots;
+                    if can_write_to_a || free_slots > 0 { // Heuristic: check space if poll didn't signal
                         let mut payload = format!("Packet #{}", packets_sent).into_bytes();
-                        payload.resize(60, 0); // Pad to typical minimum packet size
+                        netmap_rs::packet::pad_to_min(&mut payload); // Pad to minimum Ethernet frame size
 
                         match tx_a.send(&payload) {
                             Ok(_) => {
@@ -103,7 +114,7 @@ fn main() -> Result<(), Box<dyn Error>> {
                                 println!("[Sender A] Sent packet #{} ({} bytes)", packets_sent, payload.len());
                                 packets_sent += 1;
                             }
-                            Err(Error::InsufficientSpace) => {
+                            Err(netmap_rs::Error::InsufficientSpace) => {
                                 println!("[Sender A] TX ring full, will try later.");
                                 // tx_a.sync(); // Sync to update tail pointer if needed
                             }
@@ -130,7 +141,8 @@ fn main() -> Result<(), Box<dyn Error>> {
             // If fd_a was added with key 1, and fd_b with key 0, they are distinct.
             // Let's ensure fd_b is still monitored for reads.
             // If add is called again for an existing fd, it acts like modify.
-            poller.modify(fd_a, Event::none(1)).expect("Failed to remove interest from fd_a"); // Stop polling fd_a for now
+            poller.modify(borrowed_a, Event::none(1)).expect("Failed to remove interest from fd_a"); // Stop polling fd_a for now
+            events.clear();
         }
 
         // --- Receiver Logic (pipe_b) ---
@@ -138,7 +150,7 @@ fn main() -> Result<(), Box<dyn Error>> {
         // Timeout of 100ms for this example loop
         match poller.wait(&mut events, Some(Duration::from_millis(100))) {
             Ok(_) => {
-                for ev in &events {
+                for ev in events.iter() {
                     if ev.key == 0 { // Event for fd_b (receiver)
                         if ev.readable {
                             // println!("[Receiver B] fd {} readable event received.", fd_b);
@@ -199,8 +211,8 @@ fn main() -> Result<(), Box<dyn Error>> {
     }
 
     // Clean up poller by removing descriptors (optional, as Poller::drop will do it)
-    poller.delete(fd_b).ok(); // Ignore error if already removed or never added properly
-    // poller.delete(fd_a).ok(); // fd_a was transient
+    poller.delete(borrowed_b).ok(); // Ignore error if already removed or never added properly
+    poller.delete(borrowed_a).ok();
 
     println!("Example finished. Total iterations: {}", main_loop_iterations);
     Ok(())
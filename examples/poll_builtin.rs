@@ -0,0 +1,68 @@
+//! Waiting for packets with `Netmap::poll`.
+//!
+//! The same exchange as `poll_basic`, without the `polling` crate: one endpoint of
+//! `pipe{poll_builtin}` sends a few packets, waiting for TX space with
+//! `Interest::WRITABLE` whenever its ring is full, and the other blocks on
+//! `Interest::READABLE` until they arrive. `poll` syncs the rings itself, so no
+//! `sync()` is needed between a readable result and `recv()`.
+//!
+//! Usage:
+//! cargo run --example poll_builtin --features sys
+#![cfg(feature = "sys")]
+
+use netmap_rs::prelude::*;
+use std::time::Duration;
+
+const PIPE_NAME: &str = "netmap:pipe{poll_builtin}";
+const NUM_PACKETS: usize = 5;
+const TIMEOUT: Duration = Duration::from_secs(1);
+
+fn main() -> Result<(), Error> {
+    let sender = NetmapBuilder::new(PIPE_NAME)
+        .num_tx_rings(1)
+        .num_rx_rings(1)
+        .build()?;
+    let receiver = NetmapBuilder::new(PIPE_NAME)
+        .num_tx_rings(1)
+        .num_rx_rings(1)
+        .build()?;
+
+    let mut tx_ring = sender.tx_ring(0)?;
+    let mut rx_ring = receiver.rx_ring(0)?;
+
+    let mut received = 0;
+    for i in 0..NUM_PACKETS {
+        let mut payload = format!("Packet #{}", i).into_bytes();
+        netmap_rs::packet::pad_to_min(&mut payload);
+        loop {
+            match tx_ring.send(&payload) {
+                Ok(()) => break,
+                Err(Error::InsufficientSpace) => {
+                    // Ring full: sleep until the kernel has drained some of it.
+                    if sender.poll(Interest::WRITABLE, Some(TIMEOUT))?.is_timeout() {
+                        return Err(Error::Timeout);
+                    }
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        tx_ring.sync();
+        println!("[sender] sent packet #{}", i);
+    }
+
+    while received < NUM_PACKETS {
+        if receiver.poll(Interest::READABLE, Some(TIMEOUT))?.is_timeout() {
+            eprintln!("[receiver] timed out after {} of {} packets", received, NUM_PACKETS);
+            return Err(Error::Timeout);
+        }
+        while let Some(frame) = rx_ring.recv() {
+            let payload = frame.payload();
+            let text_len = payload.iter().position(|&b| b == 0).unwrap_or(payload.len());
+            println!("[receiver] got {} bytes: {}", frame.len(), String::from_utf8_lossy(&payload[..text_len]));
+            received += 1;
+        }
+    }
+
+    println!("All {} packets received.", NUM_PACKETS);
+    Ok(())
+}
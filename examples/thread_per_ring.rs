@@ -1,27 +1,23 @@
 //! Thread-per-ring with core pinning example
 
-use core_affinity::CoreId;
 use netmap_rs::prelude::*;
-#[cfg(feature = "sys")]
-use std::sync::Arc;
 use std::thread;
 use std::time::Duration;
 
 fn main() -> Result<(), Error> {
     #[cfg(feature = "sys")]
-    let nm_sys = {
-        let nm = NetmapBuilder::new("netmap:eth0")
-            .num_tx_rings(4)
-            .num_rx_rings(4)
-            .open()?;
-        Arc::new(nm)
-    };
+    let nm_sys = NetmapBuilder::new("netmap:eth0")
+        .num_tx_rings(4)
+        .num_rx_rings(4)
+        .build()?
+        .into_shared();
 
     let core_ids = core_affinity::get_core_ids().unwrap_or_else(|| {
         eprintln!("Warning: Could not get core IDs. Thread pinning will not occur.");
         Vec::new()
     });
 
+    #[cfg(not(feature = "sys"))]
     let num_sim_threads = 4; // For fallback mode, simulate this many threads
 
     #[cfg(feature = "sys")]
@@ -32,7 +28,7 @@ fn main() -> Result<(), Error> {
     // Spawn one thread per RX ring (or simulated)
     for i in 0..num_rx_rings_to_spawn {
         #[cfg(feature = "sys")]
-        let nm_clone_sys = nm_sys.clone();
+        let mut rx_ring = nm_sys.rx_ring(i)?;
 
         let core_id_to_pin = if !core_ids.is_empty() {
             Some(core_ids[i % core_ids.len()])
@@ -53,7 +49,6 @@ fn main() -> Result<(), Error> {
 
             #[cfg(feature = "sys")]
             {
-                let mut rx_ring = nm_clone_sys.rx_ring(i).unwrap();
                 println!("RX thread {} (sys) started on core {:?}", i, core_id_to_pin.map(|c| c.id));
 
                 let mut counter = 0;
@@ -89,7 +84,7 @@ fn main() -> Result<(), Error> {
     // Spawn one thread per TX ring (or simulated)
     for i in 0..num_tx_rings_to_spawn {
         #[cfg(feature = "sys")]
-        let nm_clone_sys = nm_sys.clone();
+        let mut tx_ring = nm_sys.tx_ring(i)?;
 
         let core_id_to_pin = if !core_ids.is_empty() {
             Some(core_ids[i % core_ids.len()])
@@ -110,7 +105,6 @@ fn main() -> Result<(), Error> {
 
             #[cfg(feature = "sys")]
             {
-                let mut tx_ring = nm_clone_sys.tx_ring(i).unwrap();
                 println!("TX thread {} (sys) started on core {:?}", i, core_id_to_pin.map(|c| c.id));
 
                 let payload = vec![0u8; 64];
@@ -118,7 +112,18 @@ fn main() -> Result<(), Error> {
                 let start = std::time::Instant::now();
 
                 loop {
-                    tx_ring.send(&payload).unwrap();
+                    match tx_ring.try_send(&payload) {
+                        Ok(()) => {}
+                        Err(Error::WouldBlock) => {
+                            // Ring full: hand the queued packets to the NIC and try again.
+                            tx_ring.sync();
+                            continue;
+                        }
+                        Err(e) => {
+                            eprintln!("TX {} (sys): send failed: {}", i, e);
+                            break;
+                        }
+                    }
                     tx_ring.sync();
                     counter += 1;
 
@@ -140,6 +145,15 @@ fn main() -> Result<(), Error> {
         });
     }
 
+    // Every ring is now owned by its thread; asking for one again is refused until
+    // that thread drops its handle.
+    #[cfg(feature = "sys")]
+    match nm_sys.rx_ring(0) {
+        Err(Error::RingBusy(idx)) => println!("RX ring {} is owned by its worker thread", idx),
+        Err(e) => return Err(e),
+        Ok(_) => unreachable!("RX ring 0 was moved into a worker thread"),
+    }
+
     // Keep main thread alive
     println!("Main thread running. System threads (if any) are processing packets.");
     println!("Fallback threads (if any) are simulating activity.");
@@ -0,0 +1,84 @@
+//! Tokio Frame Stream Example
+//!
+//! This example consumes a Netmap pipe as a `Stream` of frames using
+//! `AsyncNetmapRxRing::frames`, instead of reading into a byte buffer through `AsyncRead`.
+//!
+//! It sets up two Netmap pipe endpoints:
+//! - Endpoint A: Acts as the sender and is closed once all packets are sent.
+//! - Endpoint B: Acts as the receiver, iterating over the frame stream.
+//!
+//! Each item of the stream is one packet, so no buffer has to be sized up front and packet
+//! boundaries are kept. Closing endpoint A ends the stream on endpoint B.
+//!
+//! Usage:
+//! cargo run --example tokio_frame_stream --features "tokio-async sys"
+
+#![cfg(feature = "tokio-async")]
+
+use std::error::Error;
+use futures_util::StreamExt;
+use tokio::io::AsyncWriteExt;
+
+use netmap_rs::NetmapBuilder;
+use netmap_rs::tokio_async::{TokioNetmap, AsyncNetmapTxRing, FrameStream};
+
+// Use a unique pipe name for this example
+const STREAM_PIPE_NAME: &str = "netmap:pipe{tokio_frame_stream_example}";
+const STREAM_NUM_PACKETS: usize = 5;
+
+async fn sender_task(tokio_netmap: TokioNetmap, mut tx_ring: AsyncNetmapTxRing) -> Result<(), Box<dyn Error + Send + Sync>> {
+    for i in 0..STREAM_NUM_PACKETS {
+        let mut payload = format!("StreamPacket #{}", i).into_bytes();
+        netmap_rs::packet::pad_to_min(&mut payload);
+        tx_ring.write_all(&payload).await?;
+        tx_ring.flush().await?;
+        println!("[Sender] Sent packet #{} ({} bytes)", i, payload.len());
+    }
+    // Dropping the last handle to endpoint A closes it, which ends the receiver's stream.
+    drop(tx_ring);
+    drop(tokio_netmap);
+    println!("[Sender] All packets sent, endpoint closed.");
+    Ok(())
+}
+
+async fn receiver_task(mut frames: FrameStream) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let mut packets_received = 0;
+    while let Some(frame) = frames.next().await {
+        let frame = frame?;
+        println!(
+            "[Receiver] Frame #{}: {} bytes: '{}'",
+            packets_received,
+            frame.len(),
+            String::from_utf8_lossy(&frame.payload()[..frame.len().min(20)]) // Print first 20 chars
+        );
+        packets_received += 1;
+    }
+    println!("[Receiver] Stream ended after {} of {} packets.", packets_received, STREAM_NUM_PACKETS);
+    Ok(())
+}
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> Result<(), Box<dyn Error + Send + Sync>> {
+    println!("Tokio Frame Stream Example using '{}'", STREAM_PIPE_NAME);
+
+    let tokio_netmap_a = TokioNetmap::new(
+        NetmapBuilder::new(STREAM_PIPE_NAME)
+            .build()
+            .map_err(|e| format!("Failed to open pipe endpoint A: {:?}", e))?,
+    )?;
+    let tokio_netmap_b = TokioNetmap::new(
+        NetmapBuilder::new(STREAM_PIPE_NAME)
+            .build()
+            .map_err(|e| format!("Failed to open pipe endpoint B: {:?}", e))?,
+    )?;
+
+    let async_tx_a = tokio_netmap_a.tx_ring(0)?;
+    let frames_b = tokio_netmap_b.rx_ring(0)?.frames();
+
+    let receiver_handle = tokio::spawn(receiver_task(frames_b));
+    sender_task(tokio_netmap_a, async_tx_a).await?;
+    receiver_handle.await??;
+
+    println!("[Main] Example finished.");
+    Ok(())
+}
@@ -31,13 +31,13 @@ use netmap_rs::tokio_async::{TokioNetmap, AsyncNetmapRxRing, AsyncNetmapTxRing};
 // Use a unique pipe name for this example
 const ASYNC_PIPE_NAME: &str = "netmap:pipe{tokio_async_example_789}";
 const ASYNC_NUM_PACKETS: usize = 5;
-const ASYNC_PACKET_SIZE: usize = 60; // Minimum Ethernet frame size
+const ASYNC_PACKET_SIZE: usize = netmap_rs::packet::MIN_FRAME_LEN; // Minimum Ethernet frame size
 
 async fn sender_task(mut tx_ring: AsyncNetmapTxRing) -> Result<(), Box<dyn Error + Send + Sync>> {
     println!("[Async Sender] Task started.");
     for i in 0..ASYNC_NUM_PACKETS {
         let mut payload = format!("AsyncPacket #{}", i).into_bytes();
-        payload.resize(ASYNC_PACKET_SIZE, 0); // Pad to ensure fixed size
+        netmap_rs::packet::pad_to_min(&mut payload); // Pad to ensure fixed size
 
         print!("[Async Sender] Sending packet #{} ({} bytes)...", i, payload.len());
 
@@ -0,0 +1,65 @@
+//! Ring traits shared by the netmap and fallback implementations.
+//!
+//! The sys `TxRing`/`RxRing` and the [`FallbackTxRing`]/[`FallbackRxRing`] all implement
+//! these, so code generic over them builds unchanged with or without the `sys` feature. The concrete types keep their
+//! inherent methods (which these traits forward to) for direct use.
+//!
+//! ```
+//! use netmap_rs::fallback::create_fallback_channel;
+//! use netmap_rs::prelude::*;
+//!
+//! /// Sends `payload` on `tx` and returns whatever arrives on `rx`.
+//! fn echo(tx: &mut impl TxRingApi, rx: &mut impl RxRingApi, payload: &[u8]) -> Result<Option<Vec<u8>>, Error> {
+//!     tx.send(payload)?;
+//!     tx.sync();
+//!     rx.sync();
+//!     Ok(rx.recv().map(|frame| frame.payload().to_vec()))
+//! }
+//!
+//! # fn main() -> Result<(), Error> {
+//! let (mut tx, mut rx) = create_fallback_channel(16);
+//! assert_eq!(echo(&mut tx, &mut rx, b"hello")?.as_deref(), Some(&b"hello"[..]));
+//!
+//! // The same function drives real netmap rings, here the two ends of a pipe.
+//! #[cfg(feature = "sys")]
+//! if let (Ok(a), Ok(b)) = (
+//!     NetmapBuilder::new("netmap:pipe{api_doc}").build(),
+//!     NetmapBuilder::new("netmap:pipe{api_doc}").build(),
+//! ) {
+//!     let received = echo(&mut a.tx_ring(0)?, &mut b.rx_ring(0)?, b"hello")?;
+//!     assert_eq!(received.as_deref(), Some(&b"hello"[..]));
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`FallbackTxRing`]: crate::fallback::FallbackTxRing
+//! [`FallbackRxRing`]: crate::fallback::FallbackRxRing
+
+use crate::error::Error;
+use crate::frame::Frame;
+
+/// Operations common to every TX ring.
+pub trait TxRingApi {
+    /// Queue a single packet.
+    ///
+    /// # Errors
+    /// Fails when the ring has no room for the packet; a netmap ring reports
+    /// `Error::InsufficientSpace`, the fallback `Error::WouldBlock`.
+    fn send(&mut self, buf: &[u8]) -> Result<(), Error>;
+
+    /// Largest packet that fits in a single slot.
+    fn max_payload_size(&self) -> usize;
+
+    /// Make queued packets visible to the receiver. A no-op for the fallback.
+    fn sync(&mut self);
+}
+
+/// Operations common to every RX ring.
+pub trait RxRingApi {
+    /// Take the next received packet, if any.
+    fn recv(&mut self) -> Option<Frame<'_>>;
+
+    /// Pick up packets that arrived since the last sync. A no-op for the fallback.
+    fn sync(&mut self);
+}
@@ -0,0 +1,165 @@
+#![cfg(feature = "sys")]
+
+//! Link aggregation over several netmap interfaces.
+
+use std::cell::Cell;
+use std::io;
+use std::os::unix::io::AsRawFd;
+use std::time::{Duration, Instant};
+
+use crate::error::Error;
+use crate::frame::Frame;
+use crate::netmap::Netmap;
+use crate::packet;
+
+/// Several [`Netmap`] interfaces used as one logical port.
+///
+/// [`send`](Self::send) spreads traffic over the members whose link is up, keeping each
+/// flow on one member (chosen by [`packet::flow_hash`]) so its packets stay in order.
+/// [`recv_any`](Self::recv_any) drains every member, whatever its link state.
+///
+/// Link state is only re-read by [`refresh_links`](Self::refresh_links); call it
+/// periodically (or after a send error) to take failed members out of rotation and to
+/// bring recovered ones back.
+///
+/// # Examples
+///
+/// ```no_run
+/// use netmap_rs::bond::Bond;
+/// use netmap_rs::NetmapBuilder;
+///
+/// let bond = Bond::new(vec![
+///     NetmapBuilder::new("netmap:eth0").build()?,
+///     NetmapBuilder::new("netmap:eth1").build()?,
+/// ])?;
+/// # let frame = [0u8; 60];
+/// bond.send(&frame)?;
+/// # Ok::<(), netmap_rs::Error>(())
+/// ```
+pub struct Bond {
+    members: Vec<Netmap>,
+    active: Vec<usize>,      // Indices into `members` whose link was up at the last refresh
+    next_member: Cell<usize>, // Where `recv_any` starts its next scan
+}
+
+impl Bond {
+    /// Bonds `members`, checking each member's link to build the initial balancing set.
+    ///
+    /// # Errors
+    /// Returns `Error::BindFail` if `members` is empty, or `Error::Io` if a link check fails.
+    pub fn new(members: Vec<Netmap>) -> Result<Self, Error> {
+        if members.is_empty() {
+            return Err(Error::BindFail("A bond needs at least one member".to_string()));
+        }
+        let mut bond = Self {
+            members,
+            active: Vec::new(),
+            next_member: Cell::new(0),
+        };
+        bond.refresh_links()?;
+        Ok(bond)
+    }
+
+    /// Re-checks every member's link and rebuilds the set [`send`](Self::send) balances over.
+    ///
+    /// Returns the number of members with their link up.
+    pub fn refresh_links(&mut self) -> Result<usize, Error> {
+        let mut active = Vec::with_capacity(self.members.len());
+        for (i, member) in self.members.iter().enumerate() {
+            match member.wait_for_link(Duration::ZERO) {
+                Ok(()) => active.push(i),
+                Err(Error::Timeout) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        self.active = active;
+        Ok(self.active.len())
+    }
+
+    /// Returns the members in the order they were bonded.
+    pub fn members(&self) -> &[Netmap] {
+        &self.members
+    }
+
+    /// Returns the indices of the members [`send`](Self::send) currently balances over.
+    pub fn active_members(&self) -> &[usize] {
+        &self.active
+    }
+
+    /// Sends one frame on the member (and TX ring) its flow hashes to, and syncs that ring.
+    ///
+    /// Returns the index of the member used.
+    ///
+    /// # Errors
+    /// Returns an `Error::Io` of kind `NotConnected` if every member's link is down, or
+    /// any error from sending on the chosen ring.
+    pub fn send(&self, frame: &[u8]) -> Result<usize, Error> {
+        if self.active.is_empty() {
+            return Err(Error::Io(io::Error::new(
+                io::ErrorKind::NotConnected,
+                "every bond member's link is down",
+            )));
+        }
+        let hash = packet::flow_hash(frame) as usize;
+        let member_idx = self.active[hash % self.active.len()];
+        let member = &self.members[member_idx];
+
+        let num_rings = member.num_tx_rings();
+        if num_rings == 0 {
+            return Err(Error::InvalidRingIndex(0));
+        }
+        let mut tx_ring = member.tx_ring(hash / self.active.len() % num_rings)?;
+        tx_ring.sync_on_drop().send(frame)?;
+        Ok(member_idx)
+    }
+
+    /// Blocks until a frame arrives on any member, or `timeout` elapses.
+    ///
+    /// Returns the index of the member the frame came from along with the frame, or
+    /// `Ok(None)` on timeout. Members are scanned round-robin, like the rings in
+    /// [`Netmap::recv_any`].
+    ///
+    /// # Errors
+    /// Returns `Error::Io` if polling the members' file descriptors fails.
+    pub fn recv_any(&self, timeout: Duration) -> Result<Option<(usize, Frame<'_>)>, Error> {
+        let num_members = self.members.len();
+        let deadline = Instant::now() + timeout;
+        loop {
+            let start = self.next_member.get();
+            for i in (start..num_members).chain(0..start) {
+                if let Some((_, frame)) = self.members[i].recv_any(Duration::ZERO)? {
+                    self.next_member.set((i + 1) % num_members);
+                    return Ok(Some((i, frame)));
+                }
+            }
+
+            let now = Instant::now();
+            if now >= deadline {
+                return Ok(None);
+            }
+            self.wait_readable(deadline - now)?;
+        }
+    }
+
+    /// `poll(2)`s every member's descriptor for readability for at most `timeout`,
+    /// which also syncs their RX rings.
+    fn wait_readable(&self, timeout: Duration) -> Result<(), Error> {
+        let mut pfds: Vec<libc::pollfd> = self
+            .members
+            .iter()
+            .map(|member| libc::pollfd {
+                fd: member.as_raw_fd(),
+                events: libc::POLLIN,
+                revents: 0,
+            })
+            .collect();
+        let timeout_ms = timeout.as_nanos().div_ceil(1_000_000).min(libc::c_int::MAX as u128) as libc::c_int;
+        if unsafe { libc::poll(pfds.as_mut_ptr(), pfds.len() as libc::nfds_t, timeout_ms) } < 0 {
+            let err = io::Error::last_os_error();
+            if err.kind() != io::ErrorKind::Interrupted {
+                return Err(Error::Io(err));
+            }
+        }
+        Ok(())
+    }
+}
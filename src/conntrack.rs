@@ -0,0 +1,162 @@
+//! Lightweight connection tracking keyed by the IPv4 5-tuple.
+
+use std::collections::HashMap;
+use std::net::{Ipv4Addr, SocketAddrV4};
+use std::time::{Duration, Instant};
+
+use crate::frame::Frame;
+use crate::packet::{self, ETH_HEADER_LEN, IPPROTO_TCP, IPPROTO_UDP};
+
+/// The addresses, ports and protocol identifying one direction of a TCP or UDP flow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FiveTuple {
+    /// Source address and port.
+    pub src: SocketAddrV4,
+    /// Destination address and port.
+    pub dst: SocketAddrV4,
+    /// IP protocol number (6 for TCP, 17 for UDP).
+    pub protocol: u8,
+}
+
+impl FiveTuple {
+    /// Parse the 5-tuple of an Ethernet/IPv4 TCP or UDP frame.
+    ///
+    /// Returns `None` for other protocols and for frames too short to hold the ports.
+    pub fn from_frame(frame: &[u8]) -> Option<Self> {
+        if !packet::is_ipv4(frame) {
+            return None;
+        }
+        let (ihl, total_len) = packet::ipv4_lengths(frame).ok()?;
+        let ip = &frame[ETH_HEADER_LEN..];
+        let protocol = ip[9];
+        if (protocol != IPPROTO_TCP && protocol != IPPROTO_UDP) || total_len < ihl + 4 {
+            return None;
+        }
+
+        let addr = |at: usize| Ipv4Addr::new(ip[at], ip[at + 1], ip[at + 2], ip[at + 3]);
+        let port = |at: usize| u16::from_be_bytes([ip[at], ip[at + 1]]);
+        Some(Self {
+            src: SocketAddrV4::new(addr(12), port(ihl)),
+            dst: SocketAddrV4::new(addr(16), port(ihl + 2)),
+            protocol,
+        })
+    }
+
+    /// The same flow seen from the other direction.
+    pub fn reversed(&self) -> Self {
+        Self {
+            src: self.dst,
+            dst: self.src,
+            protocol: self.protocol,
+        }
+    }
+}
+
+/// What [`ConnTrack::track`] knows about the flow a frame belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlowState {
+    /// Only the direction that opened the flow has been seen so far.
+    New,
+    /// Packets have been seen in both directions.
+    Established,
+    /// The frame is not IPv4 TCP or UDP, so it is not tracked.
+    Untracked,
+}
+
+#[derive(Debug)]
+struct Flow {
+    origin: FiveTuple, // Direction of the first packet seen
+    replied: bool,
+    last_seen: Instant,
+}
+
+/// A table of TCP/UDP flows, each expiring once idle for longer than a timeout.
+///
+/// Flows are bucketed by [`packet::flow_hash`], which is symmetric, so both
+/// directions of a flow land in the same bucket; the 5-tuple tells flows in one
+/// bucket apart. Stale flows are dropped lazily: a packet for an expired flow starts
+/// a new one, and the whole table is swept at most once per timeout period.
+#[derive(Debug)]
+pub struct ConnTrack {
+    flows: HashMap<u32, Vec<Flow>>,
+    timeout: Duration,
+    last_sweep: Instant,
+}
+
+impl ConnTrack {
+    /// Create an empty table whose flows expire after `timeout` without traffic.
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            flows: HashMap::new(),
+            timeout,
+            last_sweep: Instant::now(),
+        }
+    }
+
+    /// Record `frame` against its flow, creating the flow if needed, and return the flow's state.
+    pub fn track(&mut self, frame: &Frame) -> FlowState {
+        let Some(tuple) = FiveTuple::from_frame(frame.payload()) else {
+            return FlowState::Untracked;
+        };
+        let now = Instant::now();
+        if now.duration_since(self.last_sweep) >= self.timeout {
+            self.sweep(now);
+        }
+
+        let timeout = self.timeout;
+        let bucket = self.flows.entry(packet::flow_hash(frame.payload())).or_default();
+        let known = bucket
+            .iter_mut()
+            .find(|flow| flow.origin == tuple || flow.origin == tuple.reversed());
+        match known {
+            Some(flow) if now.duration_since(flow.last_seen) < timeout => {
+                flow.last_seen = now;
+                if flow.origin != tuple {
+                    flow.replied = true;
+                }
+                if flow.replied {
+                    FlowState::Established
+                } else {
+                    FlowState::New
+                }
+            }
+            // An expired flow is replaced, with this packet's direction as its origin.
+            Some(flow) => {
+                *flow = Flow {
+                    origin: tuple,
+                    replied: false,
+                    last_seen: now,
+                };
+                FlowState::New
+            }
+            None => {
+                bucket.push(Flow {
+                    origin: tuple,
+                    replied: false,
+                    last_seen: now,
+                });
+                FlowState::New
+            }
+        }
+    }
+
+    /// Number of flows that have seen traffic within the timeout.
+    pub fn active_flows(&self) -> usize {
+        let now = Instant::now();
+        self.flows
+            .values()
+            .flatten()
+            .filter(|flow| now.duration_since(flow.last_seen) < self.timeout)
+            .count()
+    }
+
+    /// Drop every flow that has been idle for the timeout or longer.
+    fn sweep(&mut self, now: Instant) {
+        let timeout = self.timeout;
+        self.flows.retain(|_, bucket| {
+            bucket.retain(|flow| now.duration_since(flow.last_seen) < timeout);
+            !bucket.is_empty()
+        });
+        self.last_sweep = now;
+    }
+}
@@ -21,18 +21,45 @@ pub enum Error {
     #[error("Invalid ring index: {0}")]
     InvalidRingIndex(usize),
 
+    /// Ring handle is already held elsewhere
+    #[error("Ring {0} is already in use")]
+    RingBusy(usize),
+
     /// Packet too large for ring buffer
     #[error("Packet too large for ring buffer: {0} bytes")]
     PacketTooLarge(usize),
 
-    /// Not enough space in ring buffer
+    /// Frame sent into the host stack is larger than the host interface's MTU allows
+    #[error("Packet of {0} bytes exceeds the host interface MTU")]
+    ExceedsHostMtu(usize),
+
+    /// Not enough free slots in the ring
     #[error("Not enough space in ring buffer")]
     InsufficientSpace,
 
+    /// No netmap buffers left in the extra-buffer pool
+    ///
+    /// Unlike `InsufficientSpace`, waiting for the NIC to drain a ring does not help;
+    /// buffers only come back when the application returns them.
+    #[error("Netmap buffer pool exhausted")]
+    BuffersExhausted,
+
     /// Platform not yet supported
     #[error("Platform not yet supported: {0}")]
     UnsupportedPlatform(String),
 
+    /// Packet contents could not be parsed as the expected protocol headers
+    #[error("Malformed packet: {0}")]
+    MalformedPacket(String),
+
+    /// Integrity trailer did not match the received payload
+    #[error("Checksum mismatch in received packet")]
+    ChecksumMismatch,
+
+    /// Operation did not complete before its deadline
+    #[error("Operation timed out")]
+    Timeout,
+
     /// Feature not  supported in fallback mode
     #[error("Feature not supported in fallback mode: {0}")]
     FallbackUnsupported(String),
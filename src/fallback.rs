@@ -3,13 +3,28 @@
 use std::collections::VecDeque;
 use std::sync::{Arc, Mutex};
 
+use crate::api::{RxRingApi, TxRingApi};
 use crate::error::Error;
 use crate::frame::Frame;
 
+// Each entry is (payload offset, buffer); the first `offset` bytes are headroom.
+type PacketQueue = VecDeque<(usize, Vec<u8>)>;
+
 #[derive(Clone)]
 struct SharedRing {
-    queue: Arc<Mutex<VecDeque<Vec<u8>>>>,
+    queue: Arc<Mutex<PacketQueue>>,
     max_size: usize,
+    headroom: usize,
+}
+
+impl SharedRing {
+    fn new(max_size: usize, headroom: usize) -> Self {
+        Self {
+            queue: Arc::new(Mutex::new(VecDeque::new())),
+            max_size,
+            headroom,
+        }
+    }
 }
 
 /// fallback implememntation for a Netmap TX ring
@@ -21,44 +36,163 @@ pub struct FallbackRxRing(SharedRing);
 impl FallbackTxRing {
     /// create new fallback TX ring
     pub fn new(max_size: usize) -> Self {
-        Self(SharedRing {
-            queue: Arc::new(Mutex::new(VecDeque::new())),
-            max_size,
-        })
+        Self(SharedRing::new(max_size, 0))
     }
 
     /// send a packet
     pub fn send(&self, buf: &[u8]) -> Result<(), Error> {
+        self.send_at_offset(0, buf)
+    }
+
+    /// send a packet whose payload starts `offset` bytes into the buffer
+    ///
+    /// Emulates netmap's per-slot payload offsets: the first `offset` bytes are
+    /// zeroed headroom, which the receiver sees through [`FallbackRxRing::recv_with_offset`].
+    /// `offset` may not exceed the headroom the channel was created with
+    /// (see [`create_fallback_channel_with_headroom`]).
+    pub fn send_at_offset(&self, offset: usize, buf: &[u8]) -> Result<(), Error> {
+        if offset > self.0.headroom {
+            return Err(Error::FallbackUnsupported(format!(
+                "offset {} exceeds the channel's {}-byte headroom",
+                offset, self.0.headroom
+            )));
+        }
+
         let mut queue = self.0.queue.lock().unwrap();
         if queue.len() >= self.0.max_size {
             return Err(Error::WouldBlock);
         }
-        queue.push_back(buf.to_vec());
+        let mut data = vec![0u8; offset + buf.len()];
+        data[offset..].copy_from_slice(buf);
+        queue.push_back((offset, data));
         Ok(())
     }
+
+    /// reserve space for `count` packets, to be filled and then queued together
+    ///
+    /// Mirrors `TxRing::reserve_batch` on a real ring: the packets are
+    /// built in the returned [`FallbackBatchReservation`] and only become visible to the
+    /// receiver when it is committed.
+    ///
+    /// # Errors
+    /// Returns `Error::InsufficientSpace` if fewer than `count` packets fit in the queue.
+    pub fn reserve_batch(&mut self, count: usize) -> Result<FallbackBatchReservation<'_>, Error> {
+        let queued = self.0.queue.lock().unwrap().len();
+        if count > self.0.max_size.saturating_sub(queued) {
+            return Err(Error::InsufficientSpace);
+        }
+
+        Ok(FallbackBatchReservation {
+            ring: self,
+            packets: vec![Vec::new(); count],
+        })
+    }
+}
+
+impl TxRingApi for FallbackTxRing {
+    fn send(&mut self, buf: &[u8]) -> Result<(), Error> {
+        FallbackTxRing::send(self, buf)
+    }
+
+    /// The fallback queues packets of any size.
+    fn max_payload_size(&self) -> usize {
+        usize::MAX
+    }
+
+    fn sync(&mut self) {}
+}
+
+/// a batch reservation on a [`FallbackTxRing`]
+pub struct FallbackBatchReservation<'a> {
+    ring: &'a mut FallbackTxRing,
+    packets: Vec<Vec<u8>>,
+}
+
+impl FallbackBatchReservation<'_> {
+    /// get a zeroed mutable slice of `len` bytes for packet `index` in the batch
+    pub fn packet(&mut self, index: usize, len: usize) -> Result<&mut [u8], Error> {
+        let packet = self
+            .packets
+            .get_mut(index)
+            .ok_or(Error::InvalidRingIndex(index))?;
+        packet.clear();
+        packet.resize(len, 0);
+        Ok(packet)
+    }
+
+    /// commit the batch, queueing every reserved packet in order
+    ///
+    /// Packets that were never filled are queued empty, as on a netmap ring.
+    pub fn commit(self) {
+        // The TX ring is borrowed mutably and only the receiver drains the queue, so the
+        // space checked at reservation time is still there.
+        let mut queue = self.ring.0.queue.lock().unwrap();
+        queue.extend(self.packets.into_iter().map(|data| (0, data)));
+    }
 }
 
 impl FallbackRxRing {
     /// create a new fallback RX ring
     pub fn new(max_size: usize) -> Self {
-        Self(SharedRing {
-            queue: Arc::new(Mutex::new(VecDeque::new())),
-            max_size,
-        })
+        Self(SharedRing::new(max_size, 0))
     }
 
     /// recieve a packet
+    ///
+    /// Any headroom in front of the payload is stripped.
     pub fn recv(&self) -> Option<Frame<'static>> {
         let mut queue = self.0.queue.lock().unwrap();
-        queue.pop_front().map(Frame::new_owned)
+        queue.pop_front().map(|(offset, mut data)| {
+            data.drain(..offset);
+            Frame::new_owned(data)
+        })
     }
+
+    /// recieve a packet together with its payload offset
+    ///
+    /// The frame holds the whole buffer, headroom included, so the payload is at
+    /// `&frame[offset..]`.
+    pub fn recv_with_offset(&self) -> Option<(usize, Frame<'static>)> {
+        let mut queue = self.0.queue.lock().unwrap();
+        queue
+            .pop_front()
+            .map(|(offset, data)| (offset, Frame::new_owned(data)))
+    }
+
+    /// recieve up to `batch.len()` packets into `batch`, returning the filled prefix
+    ///
+    /// Packets fill `batch` from the front; entries past the returned subslice are left
+    /// untouched. Headroom is stripped as in [`recv`](Self::recv).
+    pub fn recv_batch<'b>(&self, batch: &'b mut [Frame<'static>]) -> &'b mut [Frame<'static>] {
+        let mut queue = self.0.queue.lock().unwrap();
+        let count = batch.len().min(queue.len());
+        for (slot, (offset, mut data)) in batch.iter_mut().zip(queue.drain(..count)) {
+            data.drain(..offset);
+            *slot = Frame::new_owned(data);
+        }
+        &mut batch[..count]
+    }
+}
+
+impl RxRingApi for FallbackRxRing {
+    fn recv(&mut self) -> Option<Frame<'_>> {
+        FallbackRxRing::recv(self)
+    }
+
+    fn sync(&mut self) {}
 }
 
 /// Creates a connected pair of fallback TX and RX rings.
 pub fn create_fallback_channel(max_size: usize) -> (FallbackTxRing, FallbackRxRing) {
-    let shared_ring = SharedRing {
-        queue: Arc::new(Mutex::new(VecDeque::new())),
-        max_size,
-    };
+    create_fallback_channel_with_headroom(max_size, 0)
+}
+
+/// Creates a connected pair of fallback TX and RX rings that reserve up to `headroom`
+/// bytes in front of each payload, for exercising offset-based code without hardware.
+pub fn create_fallback_channel_with_headroom(
+    max_size: usize,
+    headroom: usize,
+) -> (FallbackTxRing, FallbackRxRing) {
+    let shared_ring = SharedRing::new(max_size, headroom);
     (FallbackTxRing(shared_ring.clone()), FallbackRxRing(shared_ring))
 }
@@ -1,5 +1,26 @@
 use std::borrow::Cow;
+use std::io::{self, Write};
 use std::ops::Deref;
+use std::sync::{Arc, Mutex};
+
+use crate::error::Error;
+use crate::packet::{self, ETH_HEADER_LEN, UDP_HEADER_LEN};
+
+bitflags::bitflags! {
+    /// Protocol layers whose headers [`Frame::ensure_min_len`] checks are present.
+    ///
+    /// Layers stack in the order Ethernet, IPv4, UDP, so requesting an upper layer also
+    /// checks every layer beneath it.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct Layers: u8 {
+        /// The 14-byte Ethernet header.
+        const ETH = 0b001;
+        /// The IPv4 header, including options as given by its IHL field.
+        const IPV4 = 0b010;
+        /// The 8-byte UDP header following the IPv4 header.
+        const UDP = 0b100;
+    }
+}
 
 /// A view of a packet, potentially zero-copy (for Netmap sys) or owned (for fallback).
 pub struct Frame<'a> {
@@ -37,10 +58,104 @@ impl<'a> Frame<'a> {
         self.data.is_empty()
     }
 
+    /// shorten the frame to its first `len` bytes
+    ///
+    /// Has no effect if `len` is greater than or equal to the frame's current length.
+    pub fn truncate(&mut self, len: usize) {
+        match &mut self.data {
+            Cow::Borrowed(data) => *data = &data[..len.min(data.len())],
+            Cow::Owned(data) => data.truncate(len),
+        }
+    }
+
     /// get the payload as a byte slice
     pub fn payload(&self) -> &[u8] {
         self.data.as_ref()
     }
+
+    /// check the frame is long enough to hold the headers of every layer in `layers`
+    ///
+    /// The IPv4 header length comes from the frame's own IHL field, so options are
+    /// accounted for. Only lengths are checked, not EtherType or protocol numbers. Call
+    /// this once before indexing into headers of untrusted traffic so the accesses that
+    /// follow cannot go out of bounds.
+    ///
+    /// # Errors
+    /// Returns `Error::MalformedPacket` naming the first layer that does not fit, or if
+    /// the IHL field is below the 20-byte minimum.
+    pub fn ensure_min_len(&self, layers: Layers) -> Result<(), Error> {
+        let data = self.payload();
+        let short = |layer: &str| Err(Error::MalformedPacket(format!("frame too short for {} header", layer)));
+
+        if layers.is_empty() {
+            return Ok(());
+        }
+        if data.len() < ETH_HEADER_LEN {
+            return short("Ethernet");
+        }
+        if !layers.intersects(Layers::IPV4 | Layers::UDP) {
+            return Ok(());
+        }
+        let Some(&version_ihl) = data.get(ETH_HEADER_LEN) else {
+            return short("IPv4");
+        };
+        let ihl = ((version_ihl & 0x0f) as usize) * 4;
+        if ihl < 20 {
+            return Err(Error::MalformedPacket(format!("IPv4 IHL of {} bytes is below the minimum", ihl)));
+        }
+        if data.len() < ETH_HEADER_LEN + ihl {
+            return short("IPv4");
+        }
+        if layers.contains(Layers::UDP) && data.len() < ETH_HEADER_LEN + ihl + UDP_HEADER_LEN {
+            return short("UDP");
+        }
+        Ok(())
+    }
+
+    /// strip a VXLAN or GRE tunnel header and return the inner Ethernet frame
+    ///
+    /// Recognizes VXLAN over IPv4/UDP port 4789 and GRE over IPv4, the latter carrying
+    /// either Ethernet frames or IPv4 packets (which get an Ethernet header with this
+    /// frame's MAC addresses). Only one level of encapsulation is removed; call it again
+    /// on the result for nested tunnels. Returns `None` for frames that are not tunneled
+    /// or whose tunnel headers are truncated.
+    pub fn decapsulate(&self) -> Option<Frame<'static>> {
+        packet::decapsulate(self.payload()).map(Frame::new_owned)
+    }
+
+    /// copy the payload into a buffer taken from `pool`
+    ///
+    /// The buffer goes back to `pool` when the returned frame is dropped, so a receive
+    /// loop that keeps frames past the next `recv` reuses the same few allocations
+    /// instead of allocating a `Vec` per packet. A new buffer is only allocated when
+    /// the pool has no idle one.
+    pub fn clone_into_pool(&self, pool: &FramePool) -> PooledFrame {
+        let mut data = pool.take();
+        data.extend_from_slice(self.payload());
+        PooledFrame {
+            data,
+            pool: Arc::clone(&pool.inner),
+        }
+    }
+
+    /// write the payload to `w`, returning the number of bytes written
+    pub fn write_to<W: Write>(&self, w: &mut W) -> io::Result<usize> {
+        w.write_all(&self.data)?;
+        Ok(self.data.len())
+    }
+
+    /// write the payload to `w` preceded by its length as a big-endian `u16`
+    ///
+    /// Useful for framing packets on a byte stream. Returns the total number of bytes
+    /// written, including the 2-byte prefix, or an `InvalidInput` error if the frame is
+    /// longer than `u16::MAX`.
+    pub fn write_framed_to<W: Write>(&self, w: &mut W) -> io::Result<usize> {
+        let len = u16::try_from(self.data.len()).map_err(|_| {
+            io::Error::new(io::ErrorKind::InvalidInput, "frame too long for a u16 length prefix")
+        })?;
+        w.write_all(&len.to_be_bytes())?;
+        Ok(2 + self.write_to(w)?)
+    }
 }
 
 impl Deref for Frame<'_> {
@@ -55,4 +170,95 @@ impl<'a> From<&'a [u8]> for Frame<'a> {
     fn from(data: &'a [u8]) -> Self {
         Self::new(data)
     }
-}
\ No newline at end of file
+}
+
+/// A free-list of packet buffers backing [`PooledFrame`]s.
+///
+/// Cloning a pool is cheap and yields a handle to the same free-list. Frames may be
+/// dropped on any thread; their buffers still return here.
+#[derive(Clone)]
+pub struct FramePool {
+    inner: Arc<PoolInner>,
+}
+
+struct PoolInner {
+    free: Mutex<Vec<Vec<u8>>>,
+    max_idle: usize, // Buffers beyond this many idle ones are freed instead of kept
+}
+
+impl FramePool {
+    /// Create an empty pool that keeps at most `max_idle` idle buffers.
+    ///
+    /// Size it to the number of frames held at once; buffers returned while the pool
+    /// already has `max_idle` idle ones are freed.
+    pub fn new(max_idle: usize) -> Self {
+        Self {
+            inner: Arc::new(PoolInner {
+                free: Mutex::new(Vec::with_capacity(max_idle)),
+                max_idle,
+            }),
+        }
+    }
+
+    /// number of idle buffers waiting to be reused
+    pub fn len(&self) -> usize {
+        self.inner.free.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    /// check if the pool has no idle buffers
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// an empty buffer, reused if one is idle
+    fn take(&self) -> Vec<u8> {
+        self.inner.free.lock().unwrap_or_else(|e| e.into_inner()).pop().unwrap_or_default()
+    }
+}
+
+/// An owned copy of a packet whose buffer returns to its [`FramePool`] on drop.
+pub struct PooledFrame {
+    data: Vec<u8>,
+    pool: Arc<PoolInner>,
+}
+
+impl PooledFrame {
+    /// get the length of the frame
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// check if the frame is empty
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// get the payload as a byte slice
+    pub fn payload(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// borrow the packet as a [`Frame`], for its header helpers
+    pub fn as_frame(&self) -> Frame<'_> {
+        Frame::new(&self.data)
+    }
+}
+
+impl Deref for PooledFrame {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        &self.data
+    }
+}
+
+impl Drop for PooledFrame {
+    fn drop(&mut self) {
+        let mut data = std::mem::take(&mut self.data);
+        data.clear();
+        let mut free = self.pool.free.lock().unwrap_or_else(|e| e.into_inner());
+        if free.len() < self.pool.max_idle {
+            free.push(data);
+        }
+    }
+}
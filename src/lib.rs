@@ -84,6 +84,12 @@ extern crate bitflags;
 #[macro_use]
 extern crate thiserror;
 
+/// Ring traits shared by the netmap and fallback implementations.
+pub mod api;
+/// Link aggregation over several netmap interfaces.
+pub mod bond;
+/// Per-flow connection tracking.
+pub mod conntrack;
 /// Error types for the netmap library.
 pub mod error;
 /// Fallback implementations for non-Netmap platforms.
@@ -92,8 +98,14 @@ pub mod fallback;
 pub mod frame;
 /// Netmap interface and builder types.
 pub mod netmap;
+/// Helpers for constructing packets.
+pub mod packet;
 /// Netmap ring manipulation.
 pub mod ring;
+/// Bounded packet tracing for debugging.
+pub mod trace;
+/// VALE software switch management.
+pub mod vale;
 
 #[cfg(feature = "sys")]
 pub use netmap_min_sys as ffi;
@@ -108,7 +120,11 @@ pub mod tokio_async;
 pub use tokio_async::{AsyncNetmapRxRing, AsyncNetmapTxRing, TokioNetmap};
 
 
-pub use crate::{error::Error, frame::Frame};
+pub use crate::{
+    api::{RxRingApi, TxRingApi},
+    error::Error,
+    frame::{Frame, FramePool, Layers, PooledFrame},
+};
 
 /// The `prelude` module re-exports commonly used types from this crate
 /// for easier access.
@@ -118,12 +134,13 @@ pub use crate::{error::Error, frame::Frame};
 /// use netmap_rs::prelude::*;
 /// ```
 pub mod prelude {
+    pub use crate::api::{RxRingApi, TxRingApi};
     pub use crate::error::Error;
     pub use crate::frame::Frame;
 
     #[cfg(feature = "sys")]
     pub use crate::{
-        netmap::{Netmap, NetmapBuilder},
+        netmap::{Interest, Netmap, NetmapBuilder},
         ring::{Ring, RxRing, TxRing},
     };
 }
@@ -131,8 +148,11 @@ pub mod prelude {
 // Re-export sys-specific types only when sys feature is enabled
 #[cfg(feature = "sys")]
 pub use crate::{
-    netmap::{Netmap, NetmapBuilder},
-    ring::{Ring, RxRing, TxRing},
+    netmap::{
+        Duplex, FlowProtocol, FlowRule, Interest, Netmap, NetmapBuilder, OwnedRxRing, OwnedTxRing, Readiness, RingHandler,
+        RxRingMut, RxWorkers, SharedNetmap, Stats, TxRingMut,
+    },
+    ring::{BatchRecv, BlockingRxRing, BlockingTxRing, FrameMut, Ring, RingDirection, RingSnapshot, RxBatchGuard, RxRing, SyncGuard, TxBatch, TxRing},
 };
 
 #[cfg(test)]
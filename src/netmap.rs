@@ -1,14 +1,37 @@
 #![cfg(feature = "sys")]
 
+use std::cell::{Cell, UnsafeCell};
+use std::collections::HashMap;
 use std::ffi::CString;
+use std::fmt;
+use std::fs;
+use std::io;
 use std::marker::PhantomData;
+use std::mem::{self, MaybeUninit};
+use std::net::Ipv4Addr;
+use std::ops::Deref;
 use std::os::unix::io::{AsRawFd, RawFd};
+use std::os::unix::io::{FromRawFd, OwnedFd};
 use std::ptr;
+use std::slice;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicU8, Ordering};
 use std::sync::Arc;
+use std::task::{Poll, Waker};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
 
+use crate::api::{RxRingApi, TxRingApi};
 use crate::error::Error;
 use crate::ffi;
-use crate::ring::{Ring, RxRing, TxRing};
+use crate::frame::{Frame, FramePool, PooledFrame};
+use crate::packet::ETH_HEADER_LEN;
+use crate::ring::{
+    BatchRecv, BatchReservation, Ring, RingCounters, RingDirection, RingSnapshot, RxBatchGuard, RxRing, TxBatch, TxPeers,
+    TxRing,
+};
+pub(crate) use nifp::Nifp;
+
+mod nifp;
 
 /// Builder for configuring and opening a Netmap interface.
 ///
@@ -45,15 +68,29 @@ pub struct NetmapBuilder {
     // These will be interpreted as HW, Host, or Pipe rings based on above flags
     req_num_tx_rings: u16,
     req_num_rx_rings: u16,
+    // Host ring counts set apart from the above; on a NIC they select NIC+host registration
+    req_num_host_tx_rings: Option<u16>,
+    req_num_host_rx_rings: Option<u16>,
 
-    // For nr_arg1, nr_arg2 (slots, etc.) - advanced usage
-    // For now, let Netmap decide these based on rings, or expose later.
-    // req_tx_slots: u32,
-    // req_rx_slots: u32,
+    // Slots per ring; 0 lets netmap pick its default depth.
+    req_num_tx_slots: u32,
+    req_num_rx_slots: u32,
 
     /// For `nr_flags` like `NETMAP_NO_TX_POLL`, `NETMAP_DO_RX_POLL`, etc.
     /// Registration mode flags (`NR_REG_*`) will be handled internally based on ifname suffix.
     additional_flags: u32,
+    exclusive: bool, // Adds NR_EXCLUSIVE so no other descriptor may bind the same rings
+    transparent: bool, // Sets NR_FORWARD on every ring after opening
+    pipe_wait_peer: Option<Duration>, // How long `build` retries a pipe open waiting for its peer
+    prefault: bool,    // Touch every page of the netmap memory region right after opening
+    lock_memory: bool, // mlock() the netmap memory region right after opening
+    extra_buffers: u32, // Extra buffers requested through `nr_arg3`
+    no_host_rings: bool, // Ask for zero host rings on top of NR_REG_NIC_ONLY
+    inherit_fd: bool,    // Leave FD_CLOEXEC clear so the descriptor survives exec
+    ring_id: Option<u16>, // Bind only this ring (NR_REG_ONE_NIC/NR_REG_ONE_SW)
+    mem_id: u16,          // Memory region to register in (`nr_arg2`), 0 for the interface's default
+    require_mem_id: bool, // Fail the open if the kernel puts the interface in another region
+    no_tx_poll: bool,     // Set NETMAP_NO_TX_POLL so polls leave the TX rings alone, for `RingBinder`
 }
 
 impl NetmapBuilder {
@@ -125,7 +162,23 @@ impl NetmapBuilder {
             is_pipe_if: is_pipe,
             req_num_tx_rings: default_rings,
             req_num_rx_rings: default_rings,
+            req_num_host_tx_rings: None,
+            req_num_host_rx_rings: None,
+            req_num_tx_slots: 0,
+            req_num_rx_slots: 0,
             additional_flags: 0,
+            exclusive: false,
+            transparent: false,
+            pipe_wait_peer: None,
+            prefault: false,
+            lock_memory: false,
+            extra_buffers: 0,
+            no_host_rings: false,
+            inherit_fd: false,
+            ring_id: None,
+            mem_id: 0,
+            require_mem_id: false,
+            no_tx_poll: false,
         }
     }
 
@@ -161,6 +214,59 @@ impl NetmapBuilder {
         self
     }
 
+    /// Sets the number of host stack TX rings (`nr_host_tx_rings`), independently of the NIC rings.
+    ///
+    /// On a hardware interface this registers NIC and host rings together
+    /// (`NR_REG_NIC_SW`): [`num_tx_rings`](Self::num_tx_rings)/[`num_rx_rings`](Self::num_rx_rings)
+    /// keep sizing the NIC rings while this sizes the host side, e.g. 4 NIC rings but a
+    /// single host ring for a bridge whose slow path to the kernel needs no parallelism.
+    /// The ring handles of the resulting `Netmap` are the NIC rings; the host rings are
+    /// bound to the same descriptor, so polling it services them too. With the `^` suffix
+    /// it overrides `num_tx_rings` as the host ring count. 0 lets netmap pick its default.
+    ///
+    /// Drivers may not support the count asked for; check [`Netmap::num_host_tx_rings`]
+    /// after `build`, and a different count is also reported through [`Netmap::warnings`].
+    /// `build` fails with `Error::BindFail` on VALE ports and pipes, which have no host
+    /// rings, and together with [`no_host_rings`](Self::no_host_rings) or, on NIC rings,
+    /// [`ring_id`](Self::ring_id).
+    pub fn num_host_tx_rings(mut self, num: usize) -> Self {
+        self.req_num_host_tx_rings = Some(num as u16);
+        self
+    }
+
+    /// Sets the number of host stack RX rings (`nr_host_rx_rings`), independently of the NIC rings.
+    ///
+    /// See [`num_host_tx_rings`](Self::num_host_tx_rings).
+    pub fn num_host_rx_rings(mut self, num: usize) -> Self {
+        self.req_num_host_rx_rings = Some(num as u16);
+        self
+    }
+
+    /// Host ring counts were set with `num_host_tx_rings`/`num_host_rx_rings`.
+    fn sets_host_ring_counts(&self) -> bool {
+        self.req_num_host_tx_rings.is_some() || self.req_num_host_rx_rings.is_some()
+    }
+
+    /// Sets the number of slots in each TX ring (`nr_tx_slots`).
+    ///
+    /// Larger rings absorb longer bursts, smaller ones keep queueing latency down. `num`
+    /// must be a power of two; 0 (the default) keeps netmap's default depth. Drivers and
+    /// VALE ports clamp the count to what they support, and a VALE port keeps the depth it
+    /// was created with, so check [`Ring::num_slots`](crate::Ring::num_slots) after
+    /// `build`; a different count is also reported through [`Netmap::warnings`].
+    pub fn num_tx_slots(mut self, num: u32) -> Self {
+        self.req_num_tx_slots = num;
+        self
+    }
+
+    /// Sets the number of slots in each RX ring (`nr_rx_slots`).
+    ///
+    /// See [`num_tx_slots`](Self::num_tx_slots).
+    pub fn num_rx_slots(mut self, num: u32) -> Self {
+        self.req_num_rx_slots = num;
+        self
+    }
+
     /// Sets additional flags for the Netmap request (`struct nmreq`'s `nr_flags` field).
     ///
     /// These flags are ORed with internally determined flags (such as those for
@@ -184,6 +290,234 @@ impl NetmapBuilder {
         self
     }
 
+    /// Requests exclusive (`true`) or shared (`false`, the default) access to the rings.
+    ///
+    /// In exclusive mode (`NR_EXCLUSIVE`) no other netmap descriptor may bind the same
+    /// rings while this one is open, and opening fails if another descriptor already holds
+    /// them. For a physical NIC in netmap mode the kernel stack already loses access to the
+    /// hardware rings; exclusive mode additionally keeps other netmap applications off them,
+    /// which is what you want for maximum, predictable performance.
+    ///
+    /// Shared mode lets several descriptors (for example one per ring, or a monitor) attach
+    /// to the same interface, which is needed when other processes must keep using it.
+    pub fn exclusive(mut self, exclusive: bool) -> Self {
+        self.exclusive = exclusive;
+        self
+    }
+
+    /// Enables (`true`) or disables (`false`, the default) netmap's transparent mode.
+    ///
+    /// In transparent mode (`NR_FORWARD` set on each ring) netmap reconnects the NIC and
+    /// the host stack selectively: RX slots the application releases with `NS_FORWARD` set
+    /// are passed to the other side at the next sync, NIC packets to the host stack and
+    /// host packets out of the NIC. Use [`RxRing::pass_through`](crate::RxRing::pass_through)
+    /// to release a packet that way and [`RxRing::recv`](crate::RxRing::recv) for packets
+    /// the application consumes, so a middlebox intercepts only the traffic it matches
+    /// instead of black-holing the rest.
+    ///
+    /// Forwarding only happens on interfaces that have host rings; on VALE ports and pipes
+    /// the flag is set but packets released for pass-through are simply dropped.
+    pub fn transparent(mut self, transparent: bool) -> Self {
+        self.transparent = transparent;
+        self
+    }
+
+    /// For pipe interfaces, keeps retrying the open in [`build`](Self::build) for up to
+    /// `timeout` while the peer endpoint does not exist yet.
+    ///
+    /// This smooths over the start-up race between two processes sharing a pipe: whichever
+    /// starts first waits for the other instead of failing. If the peer has not appeared
+    /// when `timeout` elapses, `build` returns `Error::Timeout`. Only a missing pipe is
+    /// waited out: any other open failure, such as no netmap module or no permission, is
+    /// returned at once as `Error::BindFail` with its errno. Setting it on a non-pipe
+    /// interface makes `build` fail with `Error::BindFail`.
+    pub fn pipe_wait_peer(mut self, timeout: Duration) -> Self {
+        self.pipe_wait_peer = Some(timeout);
+        self
+    }
+
+    /// Requests `count` extra buffers, not attached to any ring slot, at registration.
+    ///
+    /// They are set up as the free list behind [`Netmap::alloc_buf`] and listed by
+    /// [`Netmap::extra_buffers`]. The kernel may grant fewer than requested, or none if
+    /// the memory allocator is out of buffers; check [`Netmap::extra_bufs_granted`] after
+    /// `build`. A short grant is also reported through [`Netmap::warnings`].
+    pub fn extra_buffers(mut self, count: u32) -> Self {
+        self.extra_buffers = count;
+        self
+    }
+
+    /// Opts out of host stack rings entirely, for interfaces used purely for NIC traffic.
+    ///
+    /// Hardware descriptors are already bound with `NR_REG_NIC_ONLY`; this additionally
+    /// asks the kernel for zero host rings, so none are allocated or synced alongside the
+    /// NIC rings. After opening, [`Netmap::num_host_tx_rings`] and
+    /// [`Netmap::num_host_rx_rings`] should both be 0; if the kernel kept host rings anyway,
+    /// that is reported through [`Netmap::warnings`].
+    ///
+    /// Conflicts with the `^` host ring suffix, which `build` rejects with `Error::BindFail`.
+    pub fn no_host_rings(mut self) -> Self {
+        self.no_host_rings = true;
+        self
+    }
+
+    /// Lets child processes inherit the netmap descriptor across `exec`.
+    ///
+    /// By default `build` sets `FD_CLOEXEC` on the descriptor, so it does not leak into
+    /// programs spawned by the application. Pass `true` when the fd is deliberately handed
+    /// to a child; [`Netmap::set_fd_cloexec`] changes the flag after opening.
+    pub fn inherit_fd(mut self, inherit: bool) -> Self {
+        self.inherit_fd = inherit;
+        self
+    }
+
+    /// Binds the descriptor to ring `idx` alone instead of every ring of the interface.
+    ///
+    /// Registers with `NR_REG_ONE_NIC` (or `NR_REG_ONE_SW` for host rings, with the `^`
+    /// suffix) and `nr_ringid = idx`, the same as a `-idx` suffix on the interface name.
+    /// The resulting `Netmap` exposes that ring as TX and RX ring 0, so
+    /// [`Netmap::num_tx_rings`]/[`Netmap::num_rx_rings`] report 1, and syncs or polls on it
+    /// leave the other rings alone. This suits thread-per-ring designs with one descriptor
+    /// per thread; [`Netmap::bound_ring_range`] reports which ring was bound.
+    ///
+    /// Pipes have no ring selection, so `build` rejects this on a pipe with `Error::BindFail`.
+    pub fn ring_id(mut self, idx: u16) -> Self {
+        self.ring_id = Some(idx);
+        self
+    }
+
+    /// Registers the interface in netmap memory region `id` (`nr_arg2`) instead of its default one.
+    ///
+    /// Interfaces in the same region share one buffer pool, so a buffer index taken from
+    /// a slot of one can be placed in a slot of the other: packets move between them by
+    /// swapping buffers (as [`Netmap::batch_forward`] and [`RxRing::forward_filtered`] do)
+    /// rather than by copying. This covers every ring slot buffer and the extra buffers
+    /// from [`extra_buffers`](Self::extra_buffers); rings themselves are still private to
+    /// their descriptor. Swapping buffers between different regions corrupts both.
+    ///
+    /// Hardware NICs use the global region (id 1) unless the driver was configured
+    /// otherwise. A VALE port picks its region when it is created, so this only takes
+    /// effect for the first open of a port; check [`Netmap::memory_id`] after `build`.
+    /// 0, the default, leaves the choice to netmap.
+    pub fn memory_id(mut self, id: u16) -> Self {
+        self.mem_id = id;
+        self
+    }
+
+    /// Registers the interface in the same memory region as `other`, see [`memory_id`](Self::memory_id).
+    ///
+    /// Unlike `memory_id`, the region is required rather than requested: if the kernel
+    /// registers the interface elsewhere, as it does for a VALE port that already exists
+    /// in another region, `build` fails with `Error::BindFail` instead of returning a
+    /// descriptor that cannot swap buffers with `other`.
+    ///
+    /// The new descriptor maps the region itself rather than borrowing `other`'s
+    /// mapping, so either may be dropped first.
+    pub fn share_memory_with(mut self, other: &Netmap) -> Self {
+        self.require_mem_id = true;
+        self.memory_id(other.memory_id())
+    }
+
+    /// Faults in every page of the shared netmap memory region as part of [`build`](Self::build).
+    ///
+    /// The region holding the rings and packet buffers is mapped lazily, so without this
+    /// the first packets through each ring pay for page faults, which shows up as
+    /// cold-start latency spikes. Prefaulting moves that cost to open time; each page is
+    /// touched for writing without changing its contents. Large regions (tens to hundreds
+    /// of MiB are common) make `build` correspondingly slower.
+    ///
+    /// Prefaulted pages can still be reclaimed under memory pressure; combine with
+    /// [`lock_memory`](Self::lock_memory) to pin them.
+    pub fn prefault(mut self, prefault: bool) -> Self {
+        self.prefault = prefault;
+        self
+    }
+
+    /// `mlock`s the shared netmap memory region as part of [`build`](Self::build), so its
+    /// pages are resident and never swapped or reclaimed.
+    ///
+    /// The whole region counts against the process's `RLIMIT_MEMLOCK` (often only 8 MiB for
+    /// unprivileged users, see `ulimit -l`) unless it has `CAP_IPC_LOCK`. Netmap regions are
+    /// usually much larger than that, so `build` fails with `Error::Io` (`ENOMEM`/`EPERM`)
+    /// unless the limit is raised or the process runs as root.
+    pub fn lock_memory(mut self, lock_memory: bool) -> Self {
+        self.lock_memory = lock_memory;
+        self
+    }
+
+    /// Rejects option combinations that cannot be honoured, before anything reaches the kernel.
+    fn validate(&self) -> Result<(), Error> {
+        if self.additional_flags & ffi::NR_REG_MASK != 0 {
+            return Err(Error::BindFail(format!(
+                "flags() must not set a registration mode (NR_REG_* bits {:#x}); it is derived from the interface name '{}'",
+                self.additional_flags & ffi::NR_REG_MASK,
+                self.ifname_raw
+            )));
+        }
+        if self.wants_host_rings && self.base_ifname.contains(':') {
+            return Err(Error::BindFail(format!(
+                "VALE port '{}' has no host stack rings; remove the '^' suffix",
+                self.base_ifname
+            )));
+        }
+        if self.no_host_rings && self.wants_host_rings {
+            return Err(Error::BindFail(format!(
+                "no_host_rings() conflicts with the '^' host ring suffix on '{}'",
+                self.ifname_raw
+            )));
+        }
+        if self.sets_host_ring_counts() {
+            if self.is_pipe_if || self.base_ifname.contains(':') {
+                return Err(Error::BindFail(format!(
+                    "'{}' has no host stack rings; num_host_tx_rings()/num_host_rx_rings() do not apply",
+                    self.ifname_raw
+                )));
+            }
+            if self.no_host_rings {
+                return Err(Error::BindFail(format!(
+                    "no_host_rings() conflicts with num_host_tx_rings()/num_host_rx_rings() on '{}'",
+                    self.ifname_raw
+                )));
+            }
+            if self.ring_id.is_some() && !self.wants_host_rings {
+                return Err(Error::BindFail(format!(
+                    "ring_id() binds a single NIC ring and cannot be combined with host rings on '{}'",
+                    self.ifname_raw
+                )));
+            }
+        }
+        for (dir, slots) in [("TX", self.req_num_tx_slots), ("RX", self.req_num_rx_slots)] {
+            if slots != 0 && !slots.is_power_of_two() {
+                return Err(Error::BindFail(format!(
+                    "{} slot count {} for '{}' is not a power of two",
+                    dir, slots, self.ifname_raw
+                )));
+            }
+        }
+        if let Some(idx) = self.ring_id {
+            if self.is_pipe_if {
+                return Err(Error::BindFail(format!(
+                    "ring_id() does not apply to pipe interface '{}'",
+                    self.ifname_raw
+                )));
+            }
+            if idx as u32 > ffi::NETMAP_RING_MASK {
+                return Err(Error::BindFail(format!(
+                    "ring_id({}) exceeds the largest ring index netmap supports ({})",
+                    idx,
+                    ffi::NETMAP_RING_MASK
+                )));
+            }
+        }
+        if self.pipe_wait_peer.is_some() && !self.is_pipe_if {
+            return Err(Error::BindFail(format!(
+                "pipe_wait_peer() only applies to pipe interfaces, not '{}'",
+                self.ifname_raw
+            )));
+        }
+        Ok(())
+    }
+
     fn build_nmreq(&self) -> Result<ffi::nmreq, Error> {
         // Ensure base_ifname fits in nr_name (IFNAMSIZ - 1 for null terminator)
         if self.base_ifname.len() >= ffi::IFNAMSIZ as usize {
@@ -198,6 +532,9 @@ impl NetmapBuilder {
         }
 
         let mut req_flags = self.additional_flags;
+        if self.exclusive {
+            req_flags |= ffi::NR_EXCLUSIVE;
+        }
         let mut hw_tx_rings = 0;
         let mut hw_rx_rings = 0;
         let mut host_tx_rings = 0;
@@ -210,16 +547,25 @@ impl NetmapBuilder {
             hw_tx_rings = self.req_num_tx_rings; // Netmap uses these for pipes
             hw_rx_rings = self.req_num_rx_rings; // Netmap uses these for pipes
         } else if self.wants_host_rings {
-            req_flags |= ffi::NR_REG_SW_ONLY; // Request only host stack rings
-            host_tx_rings = self.req_num_tx_rings;
-            host_rx_rings = self.req_num_rx_rings;
+            // Request only host stack rings, or just one of them
+            req_flags |= if self.ring_id.is_some() { ffi::NR_REG_ONE_SW } else { ffi::NR_REG_SW_ONLY };
+            host_tx_rings = self.req_num_host_tx_rings.unwrap_or(self.req_num_tx_rings);
+            host_rx_rings = self.req_num_host_rx_rings.unwrap_or(self.req_num_rx_rings);
             // hw_tx_rings and hw_rx_rings remain 0
+        } else if self.sets_host_ring_counts() {
+            // NIC and host rings together, each side sized on its own
+            req_flags |= ffi::NR_REG_NIC_SW;
+            hw_tx_rings = self.req_num_tx_rings;
+            hw_rx_rings = self.req_num_rx_rings;
+            host_tx_rings = self.req_num_host_tx_rings.unwrap_or(0);
+            host_rx_rings = self.req_num_host_rx_rings.unwrap_or(0);
         } else {
             // Default behavior: request hardware rings for physical/VALE interfaces.
-            req_flags |= ffi::NR_REG_NIC_ONLY; // Request only NIC rings
+            // Request only NIC rings, or just one of them
+            req_flags |= if self.ring_id.is_some() { ffi::NR_REG_ONE_NIC } else { ffi::NR_REG_NIC_ONLY };
             hw_tx_rings = self.req_num_tx_rings;
             hw_rx_rings = self.req_num_rx_rings;
-            // host_tx_rings and host_rx_rings remain 0
+            // host_tx_rings and host_rx_rings remain 0, which `no_host_rings` relies on
         }
 
         Ok(ffi::nmreq {
@@ -227,23 +573,40 @@ impl NetmapBuilder {
             nr_version: ffi::NETMAP_API as u16,
             nr_offset: 0,
             nr_memsize: 0,
-            nr_tx_slots: 0,  // Let netmap decide by default, or allow configuration later
-            nr_rx_slots: 0,  // Let netmap decide by default
+            nr_tx_slots: self.req_num_tx_slots, // 0 lets netmap pick the default depth
+            nr_rx_slots: self.req_num_rx_slots,
             nr_tx_rings: hw_tx_rings, // For pipes, these are used for the pipe's TX rings
             nr_rx_rings: hw_rx_rings, // For pipes, these are used for the pipe's RX rings
             nr_host_tx_rings: host_tx_rings,
             nr_host_rx_rings: host_rx_rings,
-            nr_ringid: 0, // Request all rings for the component (NIC/host/pipe endpoint)
+            // The ring is ignored unless registering a single one; NETMAP_NO_TX_POLL shares the field
+            nr_ringid: self.ring_id.unwrap_or(0) | if self.no_tx_poll { ffi::NETMAP_NO_TX_POLL as u16 } else { 0 },
             nr_flags: req_flags,
             nr_arg1: 0,
-            nr_arg2: 0,
-            nr_arg3: 0, // Renamed from spare in newer netmap versions
+            nr_arg2: self.mem_id, // Memory region; the kernel writes back the one it used
+            nr_arg3: self.extra_buffers, // Extra buffers; the kernel writes back how many it granted
             spare1: [0; 1], // Keep spare for compatibility if arg3 is not yet in ffi bindings
         })
     }
 
     /// Consumes the builder and attempts to open the Netmap interface.
+    ///
+    /// For pipes configured with [`pipe_wait_peer`](Self::pipe_wait_peer), an open that
+    /// fails because the pipe does not exist yet is retried until it succeeds or the wait
+    /// times out (`Error::Timeout`).
+    ///
+    /// # Errors
+    /// Returns `Error::BindFail` without touching the kernel if the configured options
+    /// conflict with each other or with the interface type (for example host rings on a
+    /// VALE port, or [`pipe_wait_peer`](Self::pipe_wait_peer) on a non-pipe interface).
     pub fn build(self) -> Result<Netmap, Error> {
+        self.validate()?;
+        // `validate` has rejected `pipe_wait_peer` on anything but a pipe.
+        self.open(self.pipe_wait_peer.map(|timeout| Instant::now() + timeout))
+    }
+
+    /// Opens the interface, retrying until `peer_deadline` while the pipe's peer is missing.
+    fn open(&self, peer_deadline: Option<Instant>) -> Result<Netmap, Error> {
         let req = self.build_nmreq()?;
 
         // Use the raw ifname (e.g., "netmap:eth0^") for nm_open, as netmap parses it.
@@ -251,34 +614,243 @@ impl NetmapBuilder {
             .map_err(|_| Error::BindFail(format!("Invalid raw interface name: {}", self.ifname_raw)))?;
 
         // The actual nm_open call
-        // The third argument to nm_open (nm_ifp) is for reusing memory from another descriptor, pass null.
-        let desc_ptr = unsafe { ffi::nm_open(c_ifname_raw.as_ptr(), &req as *const _, ptr::null_mut(), ptr::null_mut()) };
+        // No parent descriptor: each Netmap maps its memory region itself (see `share_memory_with`).
+        let desc_ptr = loop {
+            let desc_ptr = unsafe { ffi::nm_open(c_ifname_raw.as_ptr(), &req as *const _, ptr::null_mut(), ptr::null_mut()) };
+            if !desc_ptr.is_null() {
+                break desc_ptr;
+            }
+            let err = std::io::Error::last_os_error();
+            // netmap reports a pipe (or its parent port) that does not exist yet as ENODEV or
+            // ENXIO; anything else, such as a missing module or EPERM, will not go away by waiting.
+            if let (Some(deadline), Some(libc::ENODEV | libc::ENXIO)) = (peer_deadline, err.raw_os_error()) {
+                if Instant::now() >= deadline {
+                    return Err(Error::Timeout);
+                }
+                thread::sleep(PIPE_PEER_POLL_INTERVAL);
+                continue;
+            }
+            let slots_hint = if self.req_num_tx_slots != 0 || self.req_num_rx_slots != 0 {
+                format!(
+                    " (requested {} TX and {} RX slots per ring, which the driver may not support)",
+                    self.req_num_tx_slots, self.req_num_rx_slots
+                )
+            } else {
+                String::new()
+            };
+            return Err(Error::BindFail(format!(
+                "Failed to open interface via nm_open for '{}'. Errno: {}{}",
+                self.ifname_raw, err, slots_hint
+            )));
+        };
 
-        if desc_ptr.is_null() {
+        let granted_mem_id = unsafe { (*desc_ptr).req.nr_arg2 };
+        if self.require_mem_id && granted_mem_id != self.mem_id {
+            unsafe { ffi::nm_close(desc_ptr) };
             return Err(Error::BindFail(format!(
-                "Failed to open interface via nm_open for '{}'. Errno: {}",
-                self.ifname_raw, std::io::Error::last_os_error()
+                "'{}' was registered in memory region {}, not region {} of the descriptor it should share memory with; an existing VALE port keeps the region it was created in",
+                self.ifname_raw, granted_mem_id, self.mem_id
             )));
         }
 
         // Determine actual number of rings available from the descriptor
-        let nifp = unsafe { (*desc_ptr).nifp };
-        let (actual_num_tx, actual_num_rx, final_is_host_if) = if self.is_pipe_if {
-            // For pipes, counts come from ni_tx_rings and ni_rx_rings, and it's not a host_if.
-            (unsafe { (*nifp).ni_tx_rings } as usize, unsafe { (*nifp).ni_rx_rings } as usize, false)
-        } else if self.wants_host_rings {
-            (unsafe { (*nifp).ni_host_tx_rings } as usize, unsafe { (*nifp).ni_host_rx_rings } as usize, true)
+        let nifp = unsafe { Nifp::new((*desc_ptr).nifp) };
+        // For pipes, counts come from ni_tx_rings and ni_rx_rings, and it's not a host_if.
+        let final_is_host_if = self.wants_host_rings && !self.is_pipe_if;
+
+        // A descriptor registered for a single ring (`ring_id`, or a "-N" name suffix) still
+        // sees every ring in the nifp, but only syncs and polls that one.
+        let (reg_mode, ringid) = unsafe { ((*desc_ptr).req.nr_flags & ffi::NR_REG_MASK, (*desc_ptr).req.nr_ringid) };
+        let single_ring = (reg_mode == ffi::NR_REG_ONE_NIC || reg_mode == ffi::NR_REG_ONE_SW)
+            .then_some((ringid as u32 & ffi::NETMAP_RING_MASK) as usize);
+        let ring_range = |count: usize| match single_ring {
+            Some(idx) => idx..idx + 1,
+            None => 0..count,
+        };
+
+        // Ring addresses never change for the life of the descriptor, so resolve them
+        // through the nifp offset table once instead of on every ring lookup.
+        // Host rings follow the hardware rings in that table.
+        let (tx_ring_ptrs, rx_ring_ptrs): (Vec<_>, Vec<_>) = if final_is_host_if {
+            (
+                ring_range(nifp.num_host_tx_rings()).filter_map(|i| nifp.host_tx_ring_ptr(i)).collect(),
+                ring_range(nifp.num_host_rx_rings()).filter_map(|i| nifp.host_rx_ring_ptr(i)).collect(),
+            )
+        } else {
+            (
+                ring_range(nifp.num_tx_rings()).filter_map(|i| nifp.tx_ring_ptr(i)).collect(),
+                ring_range(nifp.num_rx_rings()).filter_map(|i| nifp.rx_ring_ptr(i)).collect(),
+            )
+        };
+        let (actual_num_tx, actual_num_rx) = (tx_ring_ptrs.len(), rx_ring_ptrs.len());
+        // Waiting on one of several RX rings needs a descriptor bound to it alone; pipes have
+        // no ring selection to bind one with.
+        let ring_binder = (single_ring.is_none() && !self.is_pipe_if && actual_num_rx > 1).then(|| {
+            Arc::new(RingBinder {
+                ifname: self.ifname_raw.clone(),
+                mem_id: granted_mem_id,
+            })
+        });
+
+        // VALE ports and pipes have no kernel network interface (and no link) behind them.
+        let os_ifname = if self.is_pipe_if || self.base_ifname.contains(':') {
+            None
         } else {
-            (unsafe { (*nifp).ni_tx_rings } as usize, unsafe { (*nifp).ni_rx_rings } as usize, false)
+            Some(self.base_ifname.clone())
         };
+        // Best-effort: an interface whose MTU cannot be read gets no MTU checks.
+        let mtu = os_ifname.as_deref().and_then(|ifname| interface_mtu(ifname).ok());
+        let host_mtu = if final_is_host_if { mtu.map(|mtu| mtu as usize) } else { None };
+
+        // Owned ring handles backing `tx_ring_mut`/`rx_ring_mut`. They point into the
+        // mmap'd region owned by `desc` and are only ever lent out through `&mut self`.
+        // The TX handles are also the mirror targets of `TxRing::set_mirror`, so they sit
+        // in cells that are never borrowed as a whole.
+        let tx_counters: Vec<Arc<RingCounters>> = tx_ring_ptrs.iter().map(|_| Arc::default()).collect();
+        let rx_counters: Vec<Arc<RingCounters>> = rx_ring_ptrs.iter().map(|_| Arc::default()).collect();
+        let fd = unsafe { (*desc_ptr).fd };
+        let tx_rings: Vec<UnsafeCell<TxRing<'static>>> = tx_ring_ptrs
+            .iter()
+            .enumerate()
+            .map(|(i, &ring)| {
+                UnsafeCell::new(
+                    TxRing::new(ring, i)
+                        .with_fd(fd)
+                        .with_host_mtu(host_mtu)
+                        .with_counters(Arc::clone(&tx_counters[i])),
+                )
+            })
+            .collect();
+        let tx_peers = TxPeers::new(&tx_rings);
+        for ring in &tx_rings {
+            // Safety: nothing else refers to the handles yet.
+            unsafe { (*ring.get()).set_peers(tx_peers) };
+        }
+        let rx_rings = rx_ring_ptrs
+            .iter()
+            .enumerate()
+            .map(|(i, &ring)| {
+                RxRing::new(ring, i)
+                    .with_fd(fd)
+                    .with_binder(ring_binder.clone())
+                    .with_pipe(self.is_pipe_if)
+                    .with_counters(Arc::clone(&rx_counters[i]))
+            })
+            .collect();
+
+        let mut warnings = Vec::new();
+        if let (Some(ifname), Some(mtu), Some(&ring)) = (&os_ifname, mtu, tx_ring_ptrs.first().or(rx_ring_ptrs.first())) {
+            let buf_size = unsafe { (*ring).nr_buf_size } as usize;
+            let max_frame = mtu as usize + ETH_HEADER_LEN;
+            if max_frame > buf_size {
+                warnings.push(format!(
+                    "netmap buffers on '{}' hold {} bytes but its MTU of {} allows {}-byte frames; larger packets will be dropped or truncated",
+                    ifname, buf_size, mtu, max_frame
+                ));
+            }
+        }
+        for (dir, requested, rings) in [
+            ("TX", self.req_num_tx_slots, &tx_ring_ptrs),
+            ("RX", self.req_num_rx_slots, &rx_ring_ptrs),
+        ] {
+            let Some(&ring) = rings.first() else { continue };
+            let granted = unsafe { (*ring).num_slots };
+            if requested != 0 && granted != requested {
+                warnings.push(format!(
+                    "{} {} slots per ring were requested on '{}' but its rings have {}",
+                    requested, dir, self.ifname_raw, granted
+                ));
+            }
+        }
+        let extra_bufs_granted = unsafe { (*desc_ptr).req.nr_arg3 };
+        if extra_bufs_granted < self.extra_buffers {
+            warnings.push(format!(
+                "{} extra buffers were requested on '{}' but only {} were granted",
+                self.extra_buffers, self.ifname_raw, extra_bufs_granted
+            ));
+        }
+        for (dir, requested, granted) in [
+            ("TX", self.req_num_host_tx_rings, nifp.num_host_tx_rings()),
+            ("RX", self.req_num_host_rx_rings, nifp.num_host_rx_rings()),
+        ] {
+            if let Some(requested) = requested.filter(|&requested| requested != 0) {
+                if granted != requested as usize {
+                    warnings.push(format!(
+                        "{} host {} rings were requested on '{}' but it has {}",
+                        requested, dir, self.ifname_raw, granted
+                    ));
+                }
+            }
+        }
+        if self.no_host_rings && nifp.num_host_tx_rings() + nifp.num_host_rx_rings() > 0 {
+            warnings.push(format!(
+                "no_host_rings() was requested but '{}' still has {} host TX and {} host RX rings",
+                self.ifname_raw,
+                nifp.num_host_tx_rings(),
+                nifp.num_host_rx_rings()
+            ));
+        }
 
-        Ok(Netmap {
+        let netmap = Netmap {
             desc: desc_ptr,
+            nifp,
+            os_ifname,
             num_tx_rings: actual_num_tx,
             num_rx_rings: actual_num_rx,
             is_host_if: final_is_host_if,
+            is_pipe: self.is_pipe_if,
+            host_mtu,
+            ring_binder,
+            tx_peers,
+            tx_ring_ptrs,
+            rx_ring_ptrs,
+            tx_rings,
+            rx_rings,
+            tx_counters,
+            rx_counters,
+            next_rx_ring: Cell::new(0),
+            rx_burst_taken: Cell::new(0),
+            warnings,
             _marker: PhantomData,
-        })
+        };
+        if self.transparent {
+            for &ring in netmap.tx_ring_ptrs.iter().chain(&netmap.rx_ring_ptrs) {
+                unsafe { (*ring).flags |= ffi::NR_FORWARD as u16 };
+            }
+        }
+        // On failure `netmap` is dropped here, which closes the descriptor.
+        if !self.inherit_fd {
+            netmap.set_fd_cloexec(true)?;
+        }
+        if self.prefault {
+            netmap.prefault_memory();
+        }
+        if self.lock_memory {
+            netmap.lock_memory()?;
+        }
+        Ok(netmap)
+    }
+}
+
+/// Opens descriptors bound to a single RX ring of a `Netmap`'s interface.
+///
+/// Polling the `Netmap`'s own descriptor syncs every ring it covers and reports it
+/// readable for packets on any of them, which does not suit a thread waiting on one ring
+/// while others are consumed elsewhere. A descriptor from [`bind`](Self::bind) covers
+/// that ring alone.
+pub(crate) struct RingBinder {
+    ifname: String, // Name the `Netmap` was opened with, as `NetmapBuilder::new` took it
+    mem_id: u16,    // Region the `Netmap` was registered in
+}
+
+impl RingBinder {
+    /// Opens a descriptor bound to ring `index`, whose polls leave the TX rings alone.
+    pub(crate) fn bind(&self, index: usize) -> Result<Netmap, Error> {
+        let mut builder = NetmapBuilder::new(&self.ifname)
+            .ring_id(index as u16)
+            .memory_id(self.mem_id);
+        builder.no_tx_poll = true;
+        builder.build()
     }
 }
 
@@ -293,9 +865,24 @@ impl NetmapBuilder {
 /// hardware rings or host stack rings.
 pub struct Netmap {
     desc: *mut ffi::nm_desc,
+    nifp: Nifp,                // The descriptor's netmap_if, for ring and free-list lookups
+    os_ifname: Option<String>, // Kernel interface name (e.g. "eth0"), None for VALE ports and pipes
     num_tx_rings: usize, // Actual number of TX rings (either HW or Host based on is_host_if)
     num_rx_rings: usize, // Actual number of RX rings (either HW or Host based on is_host_if)
     is_host_if: bool,    // True if this interface represents host stack rings
+    is_pipe: bool,       // True for a pipe endpoint, which signals its peer on drop
+    host_mtu: Option<usize>, // MTU behind the host rings, enforced by `TxRing::send_to_host`
+    ring_binder: Option<Arc<RingBinder>>, // Binds descriptors to one RX ring; None if `desc` covers one or cannot
+    tx_peers: TxPeers, // Lets TX rings find their siblings in `tx_rings`
+    tx_ring_ptrs: Vec<*mut ffi::netmap_ring>, // Resolved once at build time
+    rx_ring_ptrs: Vec<*mut ffi::netmap_ring>,
+    tx_rings: Vec<UnsafeCell<TxRing<'static>>>, // Handles lent out by `tx_ring_mut`
+    rx_rings: Vec<RxRing<'static>>, // Handles lent out by `rx_ring_mut`
+    tx_counters: Vec<Arc<RingCounters>>, // Per-ring traffic counts shared by every handle, for `stats`
+    rx_counters: Vec<Arc<RingCounters>>,
+    next_rx_ring: Cell<usize>,      // Where `recv_any` starts its next scan
+    rx_burst_taken: Cell<usize>,    // Packets `recv_any_burst` has taken from `next_rx_ring` in a row
+    warnings: Vec<String>,          // Non-fatal configuration problems found by `build()`
     _marker: PhantomData<*mut u8>,
 }
 
@@ -310,7 +897,8 @@ impl Netmap {
     /// This count reflects either hardware TX rings or host TX rings,
     /// depending on whether the interface was opened for hardware access
     /// or for host stack interaction (e.g., using an interface name like "eth0^"
-    /// with [`NetmapBuilder`](struct.NetmapBuilder.html)).
+    /// with [`NetmapBuilder`](struct.NetmapBuilder.html)). It is 1 for a descriptor bound
+    /// to a single ring with [`NetmapBuilder::ring_id`].
     pub fn num_tx_rings(&self) -> usize {
         self.num_tx_rings
     }
@@ -320,7 +908,8 @@ impl Netmap {
     /// This count reflects either hardware RX rings or host RX rings,
     /// depending on whether the interface was opened for hardware access
     /// or for host stack interaction (e.g., using an interface name like "eth0^"
-    /// with [`NetmapBuilder`](struct.NetmapBuilder.html)).
+    /// with [`NetmapBuilder`](struct.NetmapBuilder.html)). It is 1 for a descriptor bound
+    /// to a single ring with [`NetmapBuilder::ring_id`].
     pub fn num_rx_rings(&self) -> usize {
         self.num_rx_rings
     }
@@ -336,56 +925,1979 @@ impl Netmap {
         self.is_host_if
     }
 
-    /// Gets a handle to a specific Transmission (TX) ring.
+    /// Returns the number of host stack TX rings the interface has, whether or not this
+    /// instance is bound to them.
+    pub fn num_host_tx_rings(&self) -> usize {
+        self.nifp.num_host_tx_rings()
+    }
+
+    /// Returns the number of host stack RX rings the interface has, whether or not this
+    /// instance is bound to them.
+    pub fn num_host_rx_rings(&self) -> usize {
+        self.nifp.num_host_rx_rings()
+    }
+
+    /// Non-fatal configuration problems detected while opening the interface.
     ///
-    /// The `index` is relative to the type of rings this `Netmap` instance manages
-    /// (hardware or host). For example, if `is_host_if()` is `true`, `tx_ring(0)`
-    /// returns the first host TX ring.
+    /// Currently this reports netmap buffers too small for the interface MTU (plus the
+    /// Ethernet header), in which case full-size packets cannot fit in one slot, host
+    /// rings the kernel kept despite [`NetmapBuilder::no_host_rings`], and host ring
+    /// counts other than those set with [`NetmapBuilder::num_host_tx_rings`]. Empty if
+    /// nothing looked wrong.
+    pub fn warnings(&self) -> Vec<String> {
+        self.warnings.clone()
+    }
+
+    /// Returns `true` if the rings were bound in exclusive mode.
+    ///
+    /// See [`NetmapBuilder::exclusive`].
+    pub fn is_exclusive(&self) -> bool {
+        unsafe { (*self.desc).req.nr_flags & ffi::NR_EXCLUSIVE != 0 }
+    }
+
+    /// Returns `true` if the rings are in transparent mode (`NR_FORWARD`).
+    ///
+    /// See [`NetmapBuilder::transparent`].
+    pub fn is_transparent(&self) -> bool {
+        self.rx_ring_ptrs
+            .iter()
+            .chain(&self.tx_ring_ptrs)
+            .next()
+            .is_some_and(|&ring| unsafe { (*ring).flags } & ffi::NR_FORWARD as u16 != 0)
+    }
+
+    /// Id of the netmap memory region the interface's rings and buffers live in (`nr_arg2`).
+    ///
+    /// Descriptors reporting the same id share a buffer pool; see [`NetmapBuilder::memory_id`].
+    pub fn memory_id(&self) -> u16 {
+        unsafe { (*self.desc).req.nr_arg2 }
+    }
+
+    /// Number of extra buffers the kernel granted for
+    /// [`NetmapBuilder::extra_buffers`], which may be less than requested.
+    ///
+    /// This is the size the free list behind [`alloc_buf`](Self::alloc_buf) started with,
+    /// not how many buffers are currently left in it.
+    pub fn extra_bufs_granted(&self) -> u32 {
+        unsafe { (*self.desc).req.nr_arg3 }
+    }
+
+    /// Indices of the buffers currently on the extra-buffer free list, head first.
+    ///
+    /// Right after `build` these are all [`extra_bufs_granted`](Self::extra_bufs_granted)
+    /// buffers requested through [`NetmapBuilder::extra_buffers`]. Listing them does not
+    /// take them off the list: an application that manages the pool itself should take
+    /// them with [`alloc_buf`](Self::alloc_buf), or not mix the two.
+    pub fn extra_buffers(&self) -> Vec<u32> {
+        let mut indices = Vec::new();
+        let mut index = self.nifp.bufs_head();
+        // The list never holds more than were granted; the bound also stops a corrupted list.
+        while index != 0 && indices.len() < self.extra_bufs_granted() as usize {
+            indices.push(index);
+            // Each free buffer stores the index of the next one in its first four bytes.
+            index = unsafe { ptr::read_unaligned(self.extra_buf(index) as *const u32) };
+        }
+        indices
+    }
+
+    /// Takes a buffer from the interface's extra-buffer free list and returns its index.
+    ///
+    /// Extra buffers are netmap buffers not attached to any ring slot, e.g. for staging
+    /// packets or swapping into slots. Return them with [`free_buf`](Self::free_buf);
+    /// netmap reclaims the free list when the interface is closed.
     ///
     /// # Errors
-    /// Returns `Error::InvalidRingIndex` if the `index` is out of bounds for the
-    /// configured number of TX rings.
-    pub fn tx_ring(&self, index: usize) -> Result<TxRing, Error> {
-        if index >= self.num_tx_rings {
-            return Err(Error::InvalidRingIndex(index));
+    /// Returns `Error::BuffersExhausted` if the free list is empty.
+    pub fn alloc_buf(&mut self) -> Result<u32, Error> {
+        let head = self.nifp.bufs_head();
+        if head == 0 {
+            return Err(Error::BuffersExhausted);
         }
+        // Each free buffer stores the index of the next one in its first four bytes.
+        let next = self.extra_buf(head) as *const u32;
+        self.nifp.set_bufs_head(unsafe { ptr::read_unaligned(next) });
+        Ok(head)
+    }
 
-        unsafe {
-            let ring = ffi::NETMAP_TXRING((*self.desc).nifp, index as u32);
-            Ok(TxRing::new(ring, index))
+    /// Returns a buffer obtained from [`alloc_buf`](Self::alloc_buf) to the extra-buffer free list.
+    ///
+    /// `index` must not be in use by any ring slot or already be on the free list.
+    pub fn free_buf(&mut self, index: u32) {
+        let next = self.extra_buf(index) as *mut u32;
+        unsafe { ptr::write_unaligned(next, self.nifp.bufs_head()) };
+        self.nifp.set_bufs_head(index);
+    }
+
+    /// Address of buffer `index`. Every ring shares one buffer pool, so any bound ring
+    /// can resolve it.
+    fn extra_buf(&self, index: u32) -> *mut libc::c_char {
+        let ring = *self.tx_ring_ptrs.first().or(self.rx_ring_ptrs.first()).expect("descriptor has no rings");
+        unsafe { ffi::NETMAP_BUF(ring, index) }
+    }
+
+    /// Cached pointer to TX ring `index` of the rings this descriptor is bound to.
+    #[cfg(feature = "tokio-async")]
+    pub(crate) fn tx_ring_ptr(&self, index: usize) -> Option<*mut ffi::netmap_ring> {
+        self.tx_ring_ptrs.get(index).copied()
+    }
+
+    /// Cached pointer to RX ring `index` of the rings this descriptor is bound to.
+    #[cfg(feature = "tokio-async")]
+    pub(crate) fn rx_ring_ptr(&self, index: usize) -> Option<*mut ffi::netmap_ring> {
+        self.rx_ring_ptrs.get(index).copied()
+    }
+
+    /// Takes a snapshot of the `head`/`cur`/`tail` pointers of every TX ring, then every RX ring.
+    ///
+    /// This is intended for monitoring, e.g. a dashboard thread observing a busy forwarder.
+    /// The kernel updates `tail` concurrently with this call, and rings are read one after
+    /// another, so the result is a best-effort view rather than a linearizable snapshot:
+    /// each ring's pointers were valid at some point during the call, but not necessarily
+    /// all at the same instant.
+    pub fn snapshot_rings(&self) -> Vec<RingSnapshot> {
+        let tx = self.tx_ring_ptrs.iter().enumerate().map(|(i, &ring)| unsafe {
+            RingSnapshot::read(ring, RingDirection::Tx, i)
+        });
+        let rx = self.rx_ring_ptrs.iter().enumerate().map(|(i, &ring)| unsafe {
+            RingSnapshot::read(ring, RingDirection::Rx, i)
+        });
+        tx.chain(rx).collect()
+    }
+
+    /// Blocks until the underlying interface reports its link as running, or `timeout` elapses.
+    ///
+    /// This polls the interface flags (`SIOCGIFFLAGS`) for `IFF_RUNNING`, which avoids
+    /// losing the first packets sent right after a physical NIC is brought up.
+    /// VALE ports and pipes have no link and return immediately.
+    ///
+    /// # Errors
+    /// Returns `Error::Timeout` if the link is still down after `timeout`, or
+    /// `Error::Io` if the interface flags cannot be queried.
+    pub fn wait_for_link(&self, timeout: Duration) -> Result<(), Error> {
+        let ifname = match &self.os_ifname {
+            Some(name) => name,
+            None => return Ok(()),
+        };
+
+        let deadline = Instant::now() + timeout;
+        loop {
+            if link_running(ifname)? {
+                return Ok(());
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(Error::Timeout);
+            }
+            thread::sleep(LINK_POLL_INTERVAL.min(deadline - now));
         }
     }
 
-    /// Gets a handle to a specific Reception (RX) ring.
+    /// The shared netmap memory region as `(start, length)`.
+    fn memory_region(&self) -> (*mut u8, usize) {
+        unsafe { ((*self.desc).mem as *mut u8, (*self.desc).memsize as usize) }
+    }
+
+    /// Touches one byte per page of the memory region for writing, faulting it in.
+    fn prefault_memory(&self) {
+        let (mem, len) = self.memory_region();
+        let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) }.max(1) as usize;
+        for offset in (0..len).step_by(page_size) {
+            // An atomic no-op RMW faults the page in as writable without racing the kernel
+            // or other processes sharing the region on the byte's value.
+            unsafe { (*(mem.add(offset) as *const AtomicU8)).fetch_or(0, Ordering::Relaxed) };
+        }
+    }
+
+    /// Returns whether the descriptor has `FD_CLOEXEC` set, i.e. is closed on `exec`.
     ///
-    /// The `index` is relative to the type of rings this `Netmap` instance manages
-    /// (hardware or host). For example, if `is_host_if()` is `true`, `rx_ring(0)`
-    /// returns the first host RX ring.
+    /// This is the default; see [`NetmapBuilder::inherit_fd`].
     ///
     /// # Errors
-    /// Returns `Error::InvalidRingIndex` if the `index` is out of bounds for the
-    /// configured number of RX rings.
-    pub fn rx_ring(&self, index: usize) -> Result<RxRing, Error> {
-        if index >= self.num_rx_rings {
-            return Err(Error::InvalidRingIndex(index));
+    /// Returns `Error::Io` if `fcntl` fails.
+    pub fn fd_cloexec(&self) -> Result<bool, Error> {
+        let flags = unsafe { libc::fcntl(self.as_raw_fd(), libc::F_GETFD) };
+        if flags < 0 {
+            return Err(Error::Io(io::Error::last_os_error()));
         }
-        unsafe {
-            let ring = ffi::NETMAP_RXRING((*self.desc).nifp, index as u32);
-            Ok(RxRing::new(ring, index))
+        Ok(flags & libc::FD_CLOEXEC != 0)
+    }
+
+    /// Sets or clears `FD_CLOEXEC` on the descriptor.
+    ///
+    /// Clear it right before spawning a child that should inherit the fd, and set it
+    /// again afterwards if other children should not.
+    ///
+    /// # Errors
+    /// Returns `Error::Io` if `fcntl` fails.
+    pub fn set_fd_cloexec(&self, cloexec: bool) -> Result<(), Error> {
+        let fd = self.as_raw_fd();
+        let flags = unsafe { libc::fcntl(fd, libc::F_GETFD) };
+        if flags < 0 {
+            return Err(Error::Io(io::Error::last_os_error()));
+        }
+        let flags = if cloexec { flags | libc::FD_CLOEXEC } else { flags & !libc::FD_CLOEXEC };
+        if unsafe { libc::fcntl(fd, libc::F_SETFD, flags) } < 0 {
+            return Err(Error::Io(io::Error::last_os_error()));
         }
+        Ok(())
     }
-}
 
-impl Drop for Netmap {
-    fn drop(&mut self) {
-        unsafe {
-            ffi::nm_close(self.desc);
+    /// `mlock`s the memory region.
+    fn lock_memory(&self) -> Result<(), Error> {
+        let (mem, len) = self.memory_region();
+        if unsafe { libc::mlock(mem as *const libc::c_void, len) } != 0 {
+            return Err(Error::Io(io::Error::last_os_error()));
         }
+        Ok(())
     }
-}
 
-impl AsRawFd for Netmap {
-    fn as_raw_fd(&self) -> RawFd {
-        unsafe { (*self.desc).fd }
+    /// Reads the NIC's RSS indirection table through ethtool (`ETHTOOL_GRXFHINDIR`).
+    ///
+    /// Entry `i` is the RX ring that receives flows whose RSS hash falls in bucket `i`.
+    ///
+    /// # Errors
+    /// Fails with `ErrorKind::Unsupported` for VALE ports and pipes, which have no NIC
+    /// behind them, and with the ioctl's error if the driver does not support RSS.
+    pub fn rss_indirection(&self) -> io::Result<Vec<u16>> {
+        get_rss_indirection(self.ethtool_ifname()?)
+    }
+
+    /// Replaces the NIC's RSS indirection table through ethtool (`ETHTOOL_SRXFHINDIR`).
+    ///
+    /// Entry `i` of `table` names the RX ring for RSS hash bucket `i`, so this is how to
+    /// line buckets up with a thread-per-ring layout (e.g. only use the rings you have
+    /// threads for). `table` must have as many entries as the driver's table, which
+    /// [`rss_indirection`](Self::rss_indirection) reports; an empty table restores the
+    /// driver default.
+    ///
+    /// Requires `CAP_NET_ADMIN` (typically root).
+    ///
+    /// # Errors
+    /// Fails with `ErrorKind::Unsupported` for VALE ports and pipes, `PermissionDenied`
+    /// without `CAP_NET_ADMIN`, and `InvalidInput` if the driver rejects the table.
+    pub fn set_rss_indirection(&self, table: &[u16]) -> io::Result<()> {
+        set_rss_indirection(self.ethtool_ifname()?, table)
+    }
+
+    /// Packets and bytes that went through this descriptor's rings since it was opened.
+    ///
+    /// Netmap rings carry no counters, so every value here is kept by the crate, summed
+    /// over all handles to all rings, including those of a [`SharedNetmap`]. A TX packet
+    /// is counted once queued, not when the NIC has sent it; an RX packet once consumed,
+    /// whether returned, passed through or dropped. Packets the NIC or kernel dropped
+    /// before they reached a ring are not seen at all; for those read the kernel's own
+    /// counters, e.g. with [`ethtool_stats`](Self::ethtool_stats).
+    pub fn stats(&self) -> Stats {
+        self.read_stats(|counter| counter.load(Ordering::Relaxed))
+    }
+
+    /// Zeroes the counters [`stats`](Self::stats) reports.
+    pub fn reset_stats(&self) {
+        self.stats_and_reset();
+    }
+
+    /// Returns the counters as [`stats`](Self::stats) does and zeroes them.
+    ///
+    /// Each counter is read and zeroed in a single atomic swap, so traffic counted while
+    /// this runs lands either in the returned `Stats` or in the next one, never in
+    /// neither: summing the results of periodic calls gives exact totals. The counters
+    /// are swapped one after another, so a packet counted meanwhile may show up in this
+    /// call's `tx_packets` and the next call's `tx_bytes`.
+    pub fn stats_and_reset(&self) -> Stats {
+        self.read_stats(|counter| counter.swap(0, Ordering::Relaxed))
+    }
+
+    /// Sums every ring's counters, reading each one with `read`.
+    fn read_stats(&self, read: impl Fn(&AtomicU64) -> u64) -> Stats {
+        let sum = |counters: &[Arc<RingCounters>], field: fn(&RingCounters) -> &AtomicU64| {
+            counters.iter().map(|ring| read(field(ring))).sum()
+        };
+        Stats {
+            tx_packets: sum(&self.tx_counters, |ring| &ring.packets),
+            tx_bytes: sum(&self.tx_counters, |ring| &ring.bytes),
+            rx_packets: sum(&self.rx_counters, |ring| &ring.packets),
+            rx_bytes: sum(&self.rx_counters, |ring| &ring.bytes),
+            rx_dropped: sum(&self.rx_counters, |ring| &ring.dropped),
+        }
+    }
+
+    /// Reads the NIC's named hardware statistics through ethtool (`ETHTOOL_GSTATS`).
+    ///
+    /// The counter names and their meaning are driver specific (`ethtool -S` shows the
+    /// same set); most drivers include per-queue packet, byte and drop counters such as
+    /// `rx_queue_0_packets`. Counters are cumulative since the driver loaded; ethtool has
+    /// no generic way to clear them, so take a baseline and subtract to measure an interval.
+    ///
+    /// # Errors
+    /// Fails with `ErrorKind::Unsupported` for VALE ports and pipes, which have no NIC
+    /// behind them, and with the ioctl's error if the driver exposes no statistics.
+    pub fn ethtool_stats(&self) -> io::Result<HashMap<String, u64>> {
+        get_ethtool_stats(self.ethtool_ifname()?)
+    }
+
+    /// Installs an ethtool flow steering (ntuple) rule sending packets that match `rule`
+    /// to its RX queue, e.g. UDP port 5000 to ring 2 (`ETHTOOL_SRXCLSRLINS`).
+    ///
+    /// The driver picks where in its rule table the rule goes; that location is returned
+    /// as the rule's id for [`remove_flow_rule`](Self::remove_flow_rule). Rules outlive the
+    /// descriptor, so remove them when done. Most drivers need ntuple filtering enabled
+    /// (`ethtool -K <if> ntuple on`), and installing rules requires `CAP_NET_ADMIN`.
+    ///
+    /// # Errors
+    /// Fails with `ErrorKind::Unsupported` for VALE ports and pipes, `PermissionDenied`
+    /// without `CAP_NET_ADMIN`, and with the ioctl's error if the driver lacks flow
+    /// steering or rejects the rule.
+    pub fn add_flow_rule(&self, rule: FlowRule) -> io::Result<u32> {
+        let mut nfc = rule.to_rxnfc(ETHTOOL_SRXCLSRLINS, RX_CLS_LOC_ANY);
+        ethtool_ioctl_raw(self.ethtool_ifname()?, &mut nfc as *mut EthtoolRxnfc as *mut libc::c_char)?;
+        Ok(nfc.fs.location)
+    }
+
+    /// Removes the flow steering rule `id` returned by [`add_flow_rule`](Self::add_flow_rule)
+    /// (`ETHTOOL_SRXCLSRLDEL`).
+    ///
+    /// # Errors
+    /// Same as [`add_flow_rule`](Self::add_flow_rule); removing an unknown id fails with
+    /// the driver's error (usually `ENOENT`).
+    pub fn remove_flow_rule(&self, id: u32) -> io::Result<()> {
+        // SAFETY: ethtool_rxnfc is a plain C struct, all-zero is a valid value.
+        let mut nfc: EthtoolRxnfc = unsafe { mem::zeroed() };
+        nfc.cmd = ETHTOOL_SRXCLSRLDEL;
+        nfc.fs.location = id;
+        ethtool_ioctl_raw(self.ethtool_ifname()?, &mut nfc as *mut EthtoolRxnfc as *mut libc::c_char)
+    }
+
+    /// Reads the NIC's current link speed in Mbit/s through ethtool (`ETHTOOL_GLINKSETTINGS`,
+    /// or `ETHTOOL_GSET` on drivers that predate it).
+    ///
+    /// Useful for deriving rate limits as a fraction of line rate.
+    ///
+    /// # Errors
+    /// Fails with `ErrorKind::Unsupported` for VALE ports and pipes, which have no NIC
+    /// behind them, with `ErrorKind::NotConnected` if the driver does not know the speed
+    /// (usually because the link is down), and with the ioctl's error otherwise.
+    pub fn link_speed_mbps(&self) -> io::Result<u32> {
+        match get_link_settings(self.ethtool_ifname()?)?.0 {
+            SPEED_UNKNOWN | 0 => Err(io::Error::new(io::ErrorKind::NotConnected, "link speed is unknown; is the link down?")),
+            speed => Ok(speed),
+        }
+    }
+
+    /// Reads the NIC's current duplex mode through ethtool, like
+    /// [`link_speed_mbps`](Self::link_speed_mbps).
+    ///
+    /// # Errors
+    /// Same as [`link_speed_mbps`](Self::link_speed_mbps), except that an unknown duplex
+    /// is reported as [`Duplex::Unknown`] rather than an error.
+    pub fn duplex(&self) -> io::Result<Duplex> {
+        Ok(Duplex::from_ethtool(get_link_settings(self.ethtool_ifname()?)?.1))
+    }
+
+    fn ethtool_ifname(&self) -> io::Result<&str> {
+        self.os_ifname.as_deref().ok_or_else(|| {
+            io::Error::new(io::ErrorKind::Unsupported, "VALE ports and pipes have no NIC behind them")
+        })
+    }
+
+    /// Sets `SO_BUSY_POLL` on the descriptor: how many microseconds a blocking poll may
+    /// busy-wait on the device queue before sleeping, trading CPU for wakeup latency.
+    ///
+    /// This only applies to socket-backed descriptors. A descriptor opened on
+    /// `/dev/netmap` is not a socket, so on most systems this fails with
+    /// `ErrorKind::Unsupported` and has no effect; callers treating busy polling as a
+    /// best-effort tweak can ignore that error. Values above the `net.core.busy_poll`
+    /// limit require `CAP_NET_ADMIN`.
+    pub fn set_busy_poll(&self, micros: usize) -> io::Result<()> {
+        let value = libc::c_int::try_from(micros)
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "busy poll time out of range"))?;
+        let ret = unsafe {
+            libc::setsockopt(
+                self.as_raw_fd(),
+                libc::SOL_SOCKET,
+                libc::SO_BUSY_POLL,
+                &value as *const libc::c_int as *const libc::c_void,
+                mem::size_of::<libc::c_int>() as libc::socklen_t,
+            )
+        };
+        if ret == -1 {
+            return Err(busy_poll_error());
+        }
+        Ok(())
+    }
+
+    /// Reads back the `SO_BUSY_POLL` time in microseconds, see [`set_busy_poll`](Self::set_busy_poll).
+    pub fn busy_poll(&self) -> io::Result<usize> {
+        let mut value: libc::c_int = 0;
+        let mut len = mem::size_of::<libc::c_int>() as libc::socklen_t;
+        let ret = unsafe {
+            libc::getsockopt(
+                self.as_raw_fd(),
+                libc::SOL_SOCKET,
+                libc::SO_BUSY_POLL,
+                &mut value as *mut libc::c_int as *mut libc::c_void,
+                &mut len,
+            )
+        };
+        if ret == -1 {
+            return Err(busy_poll_error());
+        }
+        Ok(value.max(0) as usize)
+    }
+
+    /// Returns the first and last ring indices (inclusive) this descriptor is bound to.
+    ///
+    /// This is derived from the `nr_ringid` netmap recorded at registration. When the
+    /// descriptor was bound to a single ring (for example by opening "netmap:eth0-2"),
+    /// both ends of the range are that ring's index. Otherwise the descriptor controls
+    /// every ring it exposes and the range is `(0, n - 1)`, where `n` is the larger of
+    /// [`num_tx_rings`](Self::num_tx_rings) and [`num_rx_rings`](Self::num_rx_rings).
+    pub fn bound_ring_range(&self) -> (usize, usize) {
+        let req = unsafe { &(*self.desc).req };
+        let reg_mode = req.nr_flags & ffi::NR_REG_MASK;
+        if reg_mode == ffi::NR_REG_ONE_NIC || reg_mode == ffi::NR_REG_ONE_SW {
+            let idx = (req.nr_ringid as u32 & ffi::NETMAP_RING_MASK) as usize;
+            return (idx, idx);
+        }
+        let num_rings = self.num_tx_rings.max(self.num_rx_rings);
+        (0, num_rings.saturating_sub(1))
+    }
+
+    /// Gets a handle to a specific Transmission (TX) ring.
+    ///
+    /// The `index` is relative to the type of rings this `Netmap` instance manages
+    /// (hardware or host). For example, if `is_host_if()` is `true`, `tx_ring(0)`
+    /// returns the first host TX ring.
+    ///
+    /// The handle borrows this `Netmap`, so it cannot be moved into `thread::spawn`; for
+    /// a `'static` handle per ring, convert with [`into_shared`](Self::into_shared) and
+    /// take an [`OwnedTxRing`] from the resulting [`SharedNetmap`].
+    ///
+    /// # Errors
+    /// Returns `Error::InvalidRingIndex` if the `index` is out of bounds for the
+    /// configured number of TX rings.
+    pub fn tx_ring(&self, index: usize) -> Result<TxRing, Error> {
+        if index >= self.tx_ring_ptrs.len() {
+            return Err(Error::InvalidRingIndex(index));
+        }
+        Ok(self.new_tx_ring(index))
+    }
+
+    /// Gets a handle to a specific Reception (RX) ring.
+    ///
+    /// The `index` is relative to the type of rings this `Netmap` instance manages
+    /// (hardware or host). For example, if `is_host_if()` is `true`, `rx_ring(0)`
+    /// returns the first host RX ring.
+    ///
+    /// The handle borrows this `Netmap`; see [`tx_ring`](Self::tx_ring) for moving
+    /// rings into other threads.
+    ///
+    /// # Errors
+    /// Returns `Error::InvalidRingIndex` if the `index` is out of bounds for the
+    /// configured number of RX rings.
+    pub fn rx_ring(&self, index: usize) -> Result<RxRing, Error> {
+        if index >= self.rx_ring_ptrs.len() {
+            return Err(Error::InvalidRingIndex(index));
+        }
+        Ok(self.new_rx_ring(index))
+    }
+
+    /// Handles to every TX ring, in index order, as [`tx_ring`](Self::tx_ring) returns them.
+    ///
+    /// The handles borrow this `Netmap`; to move them into other threads use
+    /// [`SharedNetmap::tx_rings`] instead.
+    pub fn tx_rings(&self) -> impl ExactSizeIterator<Item = TxRing<'_>> + '_ {
+        (0..self.tx_ring_ptrs.len()).map(move |index| self.new_tx_ring(index))
+    }
+
+    /// Handles to every RX ring, in index order, as [`rx_ring`](Self::rx_ring) returns them.
+    ///
+    /// The handles borrow this `Netmap`; to move them into other threads use
+    /// [`SharedNetmap::rx_rings`] instead.
+    pub fn rx_rings(&self) -> impl ExactSizeIterator<Item = RxRing<'_>> + '_ {
+        (0..self.rx_ring_ptrs.len()).map(move |index| self.new_rx_ring(index))
+    }
+
+    /// Handle to TX ring `index`, which must be in range.
+    fn new_tx_ring(&self, index: usize) -> TxRing<'_> {
+        let mut ring = TxRing::new(self.tx_ring_ptrs[index], index)
+            .with_fd(self.as_raw_fd())
+            .with_host_mtu(self.host_mtu)
+            .with_counters(Arc::clone(&self.tx_counters[index]));
+        ring.set_peers(self.tx_peers);
+        ring
+    }
+
+    /// Handle to RX ring `index`, which must be in range.
+    fn new_rx_ring(&self, index: usize) -> RxRing<'_> {
+        RxRing::new(self.rx_ring_ptrs[index], index)
+            .with_fd(self.as_raw_fd())
+            .with_binder(self.ring_binder.clone())
+            .with_pipe(self.is_pipe)
+            .with_counters(Arc::clone(&self.rx_counters[index]))
+    }
+
+    /// Borrows a specific Transmission (TX) ring mutably.
+    ///
+    /// Unlike [`tx_ring`](Self::tx_ring), the returned [`TxRingMut`] is tied to an
+    /// exclusive borrow of this `Netmap`, so the borrow checker rejects holding two
+    /// mutable handles to the same ring at once.
+    ///
+    /// # Errors
+    /// Returns `Error::InvalidRingIndex` if the `index` is out of bounds for the
+    /// configured number of TX rings.
+    pub fn tx_ring_mut(&mut self, index: usize) -> Result<TxRingMut<'_>, Error> {
+        let ring = self.tx_rings.get(index).ok_or(Error::InvalidRingIndex(index))?;
+        // Safety: `&mut self` rules out every other handle to this `Netmap`'s rings, and
+        // mirroring only ever reaches the cached handles of other rings.
+        Ok(TxRingMut {
+            ring: unsafe { &mut *ring.get() },
+        })
+    }
+
+    /// Borrows a specific Reception (RX) ring mutably.
+    ///
+    /// Unlike [`rx_ring`](Self::rx_ring), the returned [`RxRingMut`] is tied to an
+    /// exclusive borrow of this `Netmap`, so the borrow checker rejects holding two
+    /// mutable handles to the same ring at once.
+    ///
+    /// # Errors
+    /// Returns `Error::InvalidRingIndex` if the `index` is out of bounds for the
+    /// configured number of RX rings.
+    pub fn rx_ring_mut(&mut self, index: usize) -> Result<RxRingMut<'_>, Error> {
+        let ring = self
+            .rx_rings
+            .get_mut(index)
+            .ok_or(Error::InvalidRingIndex(index))?;
+        Ok(RxRingMut { ring })
+    }
+
+    /// Converts this instance into a [`SharedNetmap`] that hands out `'static` ring handles.
+    ///
+    /// Use this to give each ring its own thread: the handles keep the interface open
+    /// through an `Arc`, so they can be moved into `thread::spawn` closures without
+    /// borrowing from a `Netmap` that outlives them.
+    pub fn into_shared(self) -> SharedNetmap {
+        SharedNetmap {
+            inner: Arc::new(SharedInner {
+                tx_busy: (0..self.num_tx_rings).map(|_| AtomicBool::new(false)).collect(),
+                rx_busy: (0..self.num_rx_rings).map(|_| AtomicBool::new(false)).collect(),
+                netmap: self,
+            }),
+        }
+    }
+
+    /// Sends one packet on TX ring `hash % num_tx_rings()`, and syncs that ring.
+    ///
+    /// For callers that already classify their traffic: equal hashes always pick the same
+    /// ring, so a flow stays on one ring (and, through a VALE switch, arrives on the peer
+    /// RX ring with the same index) without the crate parsing the packet.
+    ///
+    /// # Errors
+    /// Returns `Error::InvalidRingIndex` if there are no TX rings, or any error from
+    /// sending on the chosen ring.
+    pub fn send_hashed(&self, hash: u64, buf: &[u8]) -> Result<(), Error> {
+        if self.num_tx_rings == 0 {
+            return Err(Error::InvalidRingIndex(0));
+        }
+        let mut tx_ring = self.tx_ring((hash % self.num_tx_rings as u64) as usize)?;
+        tx_ring.sync_on_drop().send(buf)?;
+        Ok(())
+    }
+
+    /// Blocks until a packet is available on any RX ring, or `timeout` elapses.
+    ///
+    /// Returns the index of the ring the packet came from along with the packet, or
+    /// `Ok(None)` on timeout. Rings are scanned round-robin, starting after the ring that
+    /// produced the previous packet, so a busy ring cannot starve the others. Waiting is
+    /// done with `poll(2)` on the netmap file descriptor, which also syncs the RX rings.
+    ///
+    /// The returned frame points into the ring buffer, so it should be consumed before
+    /// the RX rings are synced again.
+    ///
+    /// This is [`recv_any_burst`](Self::recv_any_burst) with a burst of one packet.
+    ///
+    /// # Errors
+    /// Returns `Error::Io` if polling the file descriptor fails.
+    pub fn recv_any(&self, timeout: Duration) -> Result<Option<(usize, Frame<'_>)>, Error> {
+        self.recv_any_burst(timeout, 1)
+    }
+
+    /// Like [`recv_any`](Self::recv_any), but takes up to `max_burst` consecutive packets
+    /// from one ring before moving on to the next.
+    ///
+    /// Larger bursts amortize the cost of switching rings; the limit keeps a saturated
+    /// ring from starving the others. A ring that runs dry ends its burst early. A
+    /// `max_burst` of 0 is treated as 1.
+    ///
+    /// # Errors
+    /// Returns `Error::Io` if polling the file descriptor fails.
+    pub fn recv_any_burst(&self, timeout: Duration, max_burst: usize) -> Result<Option<(usize, Frame<'_>)>, Error> {
+        let num_rings = self.rx_ring_ptrs.len();
+        if num_rings == 0 {
+            return Ok(None);
+        }
+        let max_burst = max_burst.max(1);
+
+        let deadline = Instant::now() + timeout;
+        loop {
+            let start = self.next_rx_ring.get();
+            for index in (start..num_rings).chain(0..start) {
+                let mut ring = self.new_rx_ring(index);
+                if let Some(frame) = ring.recv() {
+                    let taken = if index == start { self.rx_burst_taken.get() + 1 } else { 1 };
+                    if taken >= max_burst {
+                        self.next_rx_ring.set((index + 1) % num_rings);
+                        self.rx_burst_taken.set(0);
+                    } else {
+                        self.next_rx_ring.set(index);
+                        self.rx_burst_taken.set(taken);
+                    }
+                    // Safety: the buffer lives in the netmap memory region owned by `self`,
+                    // not in the temporary ring handle.
+                    let payload = unsafe { slice::from_raw_parts(frame.as_ptr(), frame.len()) };
+                    return Ok(Some((index, Frame::new(payload))));
+                }
+            }
+
+            let now = Instant::now();
+            if now >= deadline {
+                return Ok(None);
+            }
+            self.wait_readable(deadline - now)?;
+        }
+    }
+
+    /// Runs an event loop routing every received packet to the handler registered for its
+    /// RX ring, until `stop` is set.
+    ///
+    /// Each iteration drains every RX ring in index order, passing each frame to its ring's
+    /// handler, then `poll(2)`s the descriptor (which also syncs the RX rings) for more.
+    /// `stop` is checked between iterations, so the loop exits within one poll interval of
+    /// it being set, even if no traffic arrives. Packets on rings without a handler are
+    /// consumed and discarded, as leaving them queued would keep the descriptor readable.
+    ///
+    /// Handlers run on the calling thread; for one thread per ring see
+    /// [`SharedNetmap::spawn_rx_workers`].
+    ///
+    /// # Errors
+    /// Returns `Error::InvalidRingIndex` without receiving anything if a handler is
+    /// registered for a ring that does not exist, or `Error::Io` if polling fails.
+    pub fn run(&self, mut handlers: HashMap<usize, RingHandler>, stop: &AtomicBool) -> Result<(), Error> {
+        if let Some(&index) = handlers.keys().find(|&&index| index >= self.num_rx_rings) {
+            return Err(Error::InvalidRingIndex(index));
+        }
+
+        while !stop.load(Ordering::Relaxed) {
+            for index in 0..self.rx_ring_ptrs.len() {
+                let mut ring = self.new_rx_ring(index);
+                let mut handler = handlers.get_mut(&index);
+                while let Some(frame) = ring.recv() {
+                    if let Some(handler) = handler.as_mut() {
+                        handler(frame);
+                    }
+                }
+            }
+            self.wait_readable(RX_WORKER_POLL_INTERVAL)?;
+        }
+        Ok(())
+    }
+
+    /// Moves up to `max` packets from `rx` to `tx`, passing each through `transform` on the way.
+    ///
+    /// This is the general middlebox primitive: `transform` edits the packet in place
+    /// (rewriting addresses, decrementing a TTL, ...) and returns `false` to drop it.
+    /// Forwarded packets keep their length and are moved by swapping buffers with the TX
+    /// slots, so nothing is copied; dropped packets just release their RX slot. Forwarding
+    /// stops early when `tx` is full, leaving the remaining packets, unseen by `transform`,
+    /// in `rx`. Neither ring is synced.
+    ///
+    /// The rings may belong to the same or to different `Netmap` instances, as long as they
+    /// share a netmap memory region (e.g. ports of one VALE switch, or a NIC and its host
+    /// rings); swapping buffers between regions corrupts both.
+    ///
+    /// Returns the number of packets forwarded.
+    pub fn batch_forward(
+        rx: &mut RxRing<'_>,
+        tx: &mut TxRing<'_>,
+        max: usize,
+        transform: impl FnMut(&mut [u8]) -> bool,
+    ) -> usize {
+        rx.forward_with(tx, max, transform).0
+    }
+
+    /// Blocks until the descriptor is ready for `interest`, or `timeout` elapses.
+    ///
+    /// This is `poll(2)` on the netmap descriptor, which also syncs the rings: a
+    /// readable result means new packets are already visible to [`RxRing::recv`], and a
+    /// writable one that TX slots were reclaimed. No external event loop is needed.
+    /// `None` waits indefinitely; `Some(Duration::ZERO)` only checks.
+    ///
+    /// Interruption by a signal restarts the wait for the remaining time. A timeout is
+    /// not an error: it returns a [`Readiness`] with nothing set (see [`Readiness::is_timeout`]).
+    ///
+    /// # Errors
+    /// Returns `Error::Io` if `poll` fails or reports an error condition on the descriptor.
+    pub fn poll(&self, interest: Interest, timeout: Option<Duration>) -> Result<Readiness, Error> {
+        let mut events = 0;
+        if interest.contains(Interest::READABLE) {
+            events |= libc::POLLIN;
+        }
+        if interest.contains(Interest::WRITABLE) {
+            events |= libc::POLLOUT;
+        }
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+        loop {
+            let mut pfd = libc::pollfd {
+                fd: self.as_raw_fd(),
+                events,
+                revents: 0,
+            };
+            // Round up so a sub-millisecond remainder still waits instead of spinning.
+            let timeout_ms = match deadline {
+                Some(deadline) => {
+                    let remaining = deadline.saturating_duration_since(Instant::now());
+                    remaining.as_nanos().div_ceil(1_000_000).min(libc::c_int::MAX as u128) as libc::c_int
+                }
+                None => -1,
+            };
+            if unsafe { libc::poll(&mut pfd, 1, timeout_ms) } < 0 {
+                let err = io::Error::last_os_error();
+                if err.kind() == io::ErrorKind::Interrupted {
+                    continue;
+                }
+                return Err(Error::Io(err));
+            }
+            if pfd.revents & (libc::POLLERR | libc::POLLNVAL) != 0 {
+                return Err(Error::Io(io::Error::other("poll reported an error on the netmap descriptor")));
+            }
+            return Ok(Readiness {
+                readable: pfd.revents & libc::POLLIN != 0,
+                writable: pfd.revents & libc::POLLOUT != 0,
+            });
+        }
+    }
+
+    /// `poll(2)`s the descriptor for readability for at most `timeout`.
+    fn wait_readable(&self, timeout: Duration) -> Result<(), Error> {
+        self.poll(Interest::READABLE, Some(timeout)).map(|_| ())
+    }
+}
+
+bitflags::bitflags! {
+    /// Events [`Netmap::poll`] waits for.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct Interest: u8 {
+        /// Packets are waiting on an RX ring (`POLLIN`).
+        const READABLE = 0b01;
+        /// A TX ring has free slots (`POLLOUT`).
+        const WRITABLE = 0b10;
+    }
+}
+
+/// Traffic counts returned by [`Netmap::stats`] and [`Netmap::stats_and_reset`], all kept by the crate.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// Packets queued on the TX rings.
+    pub tx_packets: u64,
+    /// Bytes in the packets queued on the TX rings.
+    pub tx_bytes: u64,
+    /// Packets consumed from the RX rings, including those counted in `rx_dropped`.
+    pub rx_packets: u64,
+    /// Bytes in the packets consumed from the RX rings.
+    pub rx_bytes: u64,
+    /// Received packets the crate discarded: rejected by an RX ring's destination MAC
+    /// filter, or by [`RxRing::forward_filtered`] or the transform of [`Netmap::batch_forward`].
+    pub rx_dropped: u64,
+}
+
+/// Events that fired in a [`Netmap::poll`] call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Readiness {
+    /// An RX ring has packets to receive.
+    pub readable: bool,
+    /// A TX ring has room to send.
+    pub writable: bool,
+}
+
+impl Readiness {
+    /// True if the wait ended without any requested event firing.
+    pub fn is_timeout(&self) -> bool {
+        !self.readable && !self.writable
+    }
+}
+
+/// Counts an OS interface's RX and TX queues from sysfs, without opening it.
+///
+/// Reads the `rx-N`/`tx-N` entries under `/sys/class/net/<ifname>/queues/`, so it needs
+/// neither netmap nor any special permissions. For NICs with combined channels each
+/// queue appears once per direction, which matches the hardware rings netmap will expose.
+/// A leading `netmap:` and a trailing `^` are stripped from `ifname`, so builder-style
+/// names work too.
+///
+/// Returns `(rx_queues, tx_queues)`. Linux only; fails with `NotFound` if the interface
+/// (or sysfs) does not exist.
+///
+/// # Examples
+///
+/// ```no_run
+/// let (rx, tx) = netmap_rs::netmap::query_ring_counts("eth0")?;
+/// println!("eth0 has {} RX and {} TX queues", rx, tx);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn query_ring_counts(ifname: &str) -> io::Result<(usize, usize)> {
+    let ifname = ifname.strip_prefix("netmap:").unwrap_or(ifname);
+    let ifname = ifname.strip_suffix('^').unwrap_or(ifname);
+
+    let mut counts = (0, 0);
+    for entry in fs::read_dir(format!("/sys/class/net/{}/queues", ifname))? {
+        let name = entry?.file_name();
+        let name = name.to_string_lossy();
+        if name.starts_with("rx-") {
+            counts.0 += 1;
+        } else if name.starts_with("tx-") {
+            counts.1 += 1;
+        }
+    }
+    Ok(counts)
+}
+
+/// How often `NetmapBuilder::build` retries opening a pipe while waiting for its peer.
+const PIPE_PEER_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// How often `wait_for_link` re-reads the interface flags.
+const LINK_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Queries `SIOCGIFMTU` for `ifname`.
+fn interface_mtu(ifname: &str) -> io::Result<u32> {
+    let mut ifr = ifreq_named(ifname)?;
+    let sock = control_socket()?;
+    if unsafe { libc::ioctl(sock.as_raw_fd(), libc::SIOCGIFMTU as _, &mut ifr as *mut libc::ifreq) } == -1 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(unsafe { ifr.ifr_ifru.ifru_mtu }.max(0) as u32)
+}
+
+/// Queries `SIOCGIFFLAGS` for `ifname` and reports whether `IFF_RUNNING` is set.
+fn link_running(ifname: &str) -> Result<bool, Error> {
+    let mut ifr = ifreq_named(ifname).map_err(|_| Error::BindFail(format!("Interface name '{}' is too long.", ifname)))?;
+    let sock = control_socket()?;
+
+    let ret = unsafe { libc::ioctl(sock.as_raw_fd(), libc::SIOCGIFFLAGS as _, &mut ifr as *mut libc::ifreq) };
+    if ret == -1 {
+        return Err(Error::Io(std::io::Error::last_os_error()));
+    }
+
+    let flags = unsafe { ifr.ifr_ifru.ifru_flags } as libc::c_int;
+    Ok(flags & libc::IFF_RUNNING != 0)
+}
+
+/// A zeroed `ifreq` with `ifr_name` set to `ifname`.
+fn ifreq_named(ifname: &str) -> io::Result<libc::ifreq> {
+    // Safety: ifreq is a plain C struct for which all-zeroes is a valid value.
+    let mut ifr: libc::ifreq = unsafe { std::mem::zeroed() };
+    if ifname.len() >= ifr.ifr_name.len() {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("Interface name '{}' is too long.", ifname)));
+    }
+    for (i, byte) in ifname.bytes().enumerate() {
+        ifr.ifr_name[i] = byte as libc::c_char;
+    }
+    Ok(ifr)
+}
+
+/// A datagram socket to issue interface ioctls on.
+fn control_socket() -> io::Result<OwnedFd> {
+    let sock = unsafe { libc::socket(libc::AF_INET, libc::SOCK_DGRAM, 0) };
+    if sock < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    // Safety: `sock` is a freshly created descriptor that nothing else owns.
+    Ok(unsafe { OwnedFd::from_raw_fd(sock) })
+}
+
+// From <linux/ethtool.h>.
+const ETHTOOL_GSTRINGS: u32 = 0x1b;
+const ETHTOOL_GSTATS: u32 = 0x1d;
+const ETHTOOL_GSSET_INFO: u32 = 0x37;
+const ETHTOOL_GRXFHINDIR: u32 = 0x38;
+const ETHTOOL_SRXFHINDIR: u32 = 0x39;
+const ETHTOOL_SRXCLSRLDEL: u32 = 0x31;
+const ETHTOOL_SRXCLSRLINS: u32 = 0x32;
+const ETH_SS_STATS: u32 = 1;
+const ETH_GSTRING_LEN: usize = 32;
+const TCP_V4_FLOW: u32 = 0x01;
+const UDP_V4_FLOW: u32 = 0x02;
+const RX_CLS_LOC_ANY: u32 = 0x8000_0000;
+const ETHTOOL_GSET: u32 = 0x01;
+const ETHTOOL_GLINKSETTINGS: u32 = 0x4c;
+const SPEED_UNKNOWN: u32 = u32::MAX;
+const DUPLEX_HALF: u8 = 0x00;
+const DUPLEX_FULL: u8 = 0x01;
+
+/// Duplex mode of a NIC's link, see [`Netmap::duplex`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Duplex {
+    /// Half duplex: the link sends or receives, not both at once.
+    Half,
+    /// Full duplex.
+    Full,
+    /// The driver does not know, usually because the link is down.
+    Unknown,
+}
+
+impl Duplex {
+    fn from_ethtool(duplex: u8) -> Self {
+        match duplex {
+            DUPLEX_HALF => Duplex::Half,
+            DUPLEX_FULL => Duplex::Full,
+            _ => Duplex::Unknown,
+        }
+    }
+}
+
+/// Transport protocol a [`FlowRule`] matches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlowProtocol {
+    /// TCP over IPv4.
+    Tcp,
+    /// UDP over IPv4.
+    Udp,
+}
+
+/// An IPv4 5-tuple match and the RX queue matching packets are steered to, for
+/// [`Netmap::add_flow_rule`].
+///
+/// Fields left unset match anything.
+///
+/// ```
+/// use netmap_rs::FlowRule;
+///
+/// // UDP to port 5000 goes to ring 2.
+/// let rule = FlowRule::udp(2).dst_port(5000);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FlowRule {
+    protocol: FlowProtocol,
+    src_ip: Option<Ipv4Addr>,
+    dst_ip: Option<Ipv4Addr>,
+    src_port: Option<u16>,
+    dst_port: Option<u16>,
+    queue: u32,
+}
+
+impl FlowRule {
+    /// Matches `protocol` packets (any addresses and ports) and steers them to RX queue `queue`.
+    pub fn new(protocol: FlowProtocol, queue: u32) -> Self {
+        FlowRule {
+            protocol,
+            src_ip: None,
+            dst_ip: None,
+            src_port: None,
+            dst_port: None,
+            queue,
+        }
+    }
+
+    /// Matches TCP packets and steers them to RX queue `queue`.
+    pub fn tcp(queue: u32) -> Self {
+        Self::new(FlowProtocol::Tcp, queue)
+    }
+
+    /// Matches UDP packets and steers them to RX queue `queue`.
+    pub fn udp(queue: u32) -> Self {
+        Self::new(FlowProtocol::Udp, queue)
+    }
+
+    /// Only matches packets from `ip`.
+    pub fn src_ip(mut self, ip: Ipv4Addr) -> Self {
+        self.src_ip = Some(ip);
+        self
+    }
+
+    /// Only matches packets to `ip`.
+    pub fn dst_ip(mut self, ip: Ipv4Addr) -> Self {
+        self.dst_ip = Some(ip);
+        self
+    }
+
+    /// Only matches packets from source port `port`.
+    pub fn src_port(mut self, port: u16) -> Self {
+        self.src_port = Some(port);
+        self
+    }
+
+    /// Only matches packets to destination port `port`.
+    pub fn dst_port(mut self, port: u16) -> Self {
+        self.dst_port = Some(port);
+        self
+    }
+
+    /// The RX queue (ring) matching packets are steered to.
+    pub fn queue(&self) -> u32 {
+        self.queue
+    }
+
+    /// Encodes the rule as an ethtool `cmd` request for rule table `location`.
+    fn to_rxnfc(self, cmd: u32, location: u32) -> EthtoolRxnfc {
+        // SAFETY: ethtool_rxnfc is a plain C struct, all-zero is a valid value.
+        let mut nfc: EthtoolRxnfc = unsafe { mem::zeroed() };
+        nfc.cmd = cmd;
+        nfc.fs.flow_type = match self.protocol {
+            FlowProtocol::Tcp => TCP_V4_FLOW,
+            FlowProtocol::Udp => UDP_V4_FLOW,
+        };
+        nfc.fs.ring_cookie = self.queue as u64;
+        nfc.fs.location = location;
+
+        // `struct ethtool_tcpip4_spec { __be32 ip4src, ip4dst; __be16 psrc, pdst; u8 tos; }`
+        // in both the value (`h_u`) and the mask (`m_u`); set mask bits are compared.
+        if let Some(ip) = self.src_ip {
+            nfc.fs.match_field(0, &ip.octets());
+        }
+        if let Some(ip) = self.dst_ip {
+            nfc.fs.match_field(4, &ip.octets());
+        }
+        if let Some(port) = self.src_port {
+            nfc.fs.match_field(8, &port.to_be_bytes());
+        }
+        if let Some(port) = self.dst_port {
+            nfc.fs.match_field(10, &port.to_be_bytes());
+        }
+        nfc
+    }
+}
+
+/// `struct ethtool_rx_flow_spec` from <linux/ethtool.h>.
+#[repr(C)]
+struct EthtoolRxFlowSpec {
+    flow_type: u32,
+    h_u: [u8; 52], // union ethtool_flow_union
+    h_ext: [u8; 20], // struct ethtool_flow_ext
+    m_u: [u8; 52],
+    m_ext: [u8; 20],
+    ring_cookie: u64,
+    location: u32,
+}
+
+impl EthtoolRxFlowSpec {
+    /// Match the flow field at `offset` in `h_u` exactly against `value`.
+    fn match_field(&mut self, offset: usize, value: &[u8]) {
+        self.h_u[offset..offset + value.len()].copy_from_slice(value);
+        self.m_u[offset..offset + value.len()].fill(0xff);
+    }
+}
+
+/// `struct ethtool_rxnfc` from <linux/ethtool.h>, without the trailing `rule_locs` array.
+#[repr(C)]
+struct EthtoolRxnfc {
+    cmd: u32,
+    flow_type: u32,
+    data: u64,
+    fs: EthtoolRxFlowSpec,
+    rule_cnt: u32,
+}
+
+/// Issues `SIOCETHTOOL` on `ifname` with `cmd`, a buffer starting with an ethtool command word.
+fn ethtool_ioctl(ifname: &str, cmd: &mut [u32]) -> io::Result<()> {
+    ethtool_ioctl_raw(ifname, cmd.as_mut_ptr() as *mut libc::c_char)
+}
+
+/// Issues `SIOCETHTOOL` on `ifname` with `data`, which must point to a complete ethtool
+/// request structure for the command in its first word.
+fn ethtool_ioctl_raw(ifname: &str, data: *mut libc::c_char) -> io::Result<()> {
+    let mut ifr = ifreq_named(ifname)?;
+    ifr.ifr_ifru.ifru_data = data;
+    let sock = control_socket()?;
+    if unsafe { libc::ioctl(sock.as_raw_fd(), libc::SIOCETHTOOL as _, &mut ifr as *mut libc::ifreq) } == -1 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// Issues `ETHTOOL_GRXFHINDIR` for `ifname`, returning its RSS indirection table.
+fn get_rss_indirection(ifname: &str) -> io::Result<Vec<u16>> {
+    // `struct ethtool_rxfh_indir { u32 cmd; u32 size; u32 ring_index[]; }`. A first call
+    // with `size == 0` only reports the table size.
+    let mut probe = [ETHTOOL_GRXFHINDIR, 0];
+    ethtool_ioctl(ifname, &mut probe)?;
+
+    let mut buf = vec![0u32; 2 + probe[1] as usize];
+    buf[0] = ETHTOOL_GRXFHINDIR;
+    buf[1] = probe[1];
+    ethtool_ioctl(ifname, &mut buf)?;
+    Ok(buf[2..].iter().map(|&ring| ring as u16).collect())
+}
+
+/// Reads a `u64` the kernel stored at `words[0..2]` of a `u32` ioctl buffer.
+fn read_u64(words: &[u32]) -> u64 {
+    let mut bytes = [0u8; 8];
+    bytes[..4].copy_from_slice(&words[0].to_ne_bytes());
+    bytes[4..].copy_from_slice(&words[1].to_ne_bytes());
+    u64::from_ne_bytes(bytes)
+}
+
+/// Issues `ETHTOOL_GSSET_INFO`, `ETHTOOL_GSTRINGS` and `ETHTOOL_GSTATS` for `ifname`,
+/// returning its statistics keyed by name.
+fn get_ethtool_stats(ifname: &str) -> io::Result<HashMap<String, u64>> {
+    // `struct ethtool_sset_info { u32 cmd; u32 reserved; u64 sset_mask; u32 data[]; }`.
+    // The kernel clears mask bits for sets it doesn't support and fills one count per
+    // remaining bit.
+    let mask = (1u64 << ETH_SS_STATS).to_ne_bytes();
+    let mut info = [
+        ETHTOOL_GSSET_INFO,
+        0,
+        u32::from_ne_bytes([mask[0], mask[1], mask[2], mask[3]]),
+        u32::from_ne_bytes([mask[4], mask[5], mask[6], mask[7]]),
+        0,
+    ];
+    ethtool_ioctl(ifname, &mut info)?;
+    if read_u64(&info[2..4]) == 0 {
+        return Err(io::Error::new(io::ErrorKind::Unsupported, "driver exposes no ethtool statistics"));
+    }
+    let count = info[4] as usize;
+
+    // `struct ethtool_gstrings { u32 cmd; u32 string_set; u32 len; u8 data[]; }` with
+    // `len` NUL-padded names of ETH_GSTRING_LEN bytes each.
+    let mut strings = vec![0u32; 3 + count * ETH_GSTRING_LEN / 4];
+    strings[0] = ETHTOOL_GSTRINGS;
+    strings[1] = ETH_SS_STATS;
+    strings[2] = count as u32;
+    ethtool_ioctl(ifname, &mut strings)?;
+    let name_bytes: Vec<u8> = strings[3..].iter().flat_map(|w| w.to_ne_bytes()).collect();
+
+    // `struct ethtool_stats { u32 cmd; u32 n_stats; u64 data[]; }`.
+    let mut stats = vec![0u32; 2 + count * 2];
+    stats[0] = ETHTOOL_GSTATS;
+    stats[1] = count as u32;
+    ethtool_ioctl(ifname, &mut stats)?;
+
+    // The driver may report fewer entries than it announced if its set shrank in between.
+    let reported = (strings[2] as usize).min(stats[1] as usize).min(count);
+    Ok(name_bytes
+        .chunks_exact(ETH_GSTRING_LEN)
+        .zip(stats[2..].chunks_exact(2))
+        .take(reported)
+        .map(|(name, value)| {
+            let end = name.iter().position(|&b| b == 0).unwrap_or(name.len());
+            (String::from_utf8_lossy(&name[..end]).into_owned(), read_u64(value))
+        })
+        .collect())
+}
+
+/// Issues `ETHTOOL_SRXFHINDIR` for `ifname` with `table`.
+fn set_rss_indirection(ifname: &str, table: &[u16]) -> io::Result<()> {
+    let mut buf = Vec::with_capacity(2 + table.len());
+    buf.push(ETHTOOL_SRXFHINDIR);
+    buf.push(table.len() as u32);
+    buf.extend(table.iter().map(|&ring| ring as u32));
+    ethtool_ioctl(ifname, &mut buf)
+}
+
+/// Byte `offset` of an ethtool request laid out in `u32` words.
+fn byte_at(words: &[u32], offset: usize) -> u8 {
+    words[offset / 4].to_ne_bytes()[offset % 4]
+}
+
+/// Issues `ETHTOOL_GLINKSETTINGS` for `ifname`, falling back to the legacy `ETHTOOL_GSET`
+/// if the driver does not support it, and returns the raw `(speed, duplex)` fields.
+fn get_link_settings(ifname: &str) -> io::Result<(u32, u8)> {
+    // `struct ethtool_link_settings` has a 48-byte header: `u32 cmd; u32 speed; u8 duplex;`
+    // ... with `s8 link_mode_masks_nwords` at byte 15, followed by three link mode
+    // bitmaps of that many words. A first call with `nwords == 0` only reports the
+    // bitmap size, negated.
+    const HEADER_WORDS: usize = 12;
+    let mut probe = [0u32; HEADER_WORDS];
+    probe[0] = ETHTOOL_GLINKSETTINGS;
+    match ethtool_ioctl(ifname, &mut probe) {
+        Ok(()) => {
+            let nwords = (byte_at(&probe, 15) as i8).unsigned_abs();
+            let mut buf = vec![0u32; HEADER_WORDS + 3 * nwords as usize];
+            buf[0] = ETHTOOL_GLINKSETTINGS;
+            let mut word = buf[3].to_ne_bytes();
+            word[3] = nwords;
+            buf[3] = u32::from_ne_bytes(word);
+            ethtool_ioctl(ifname, &mut buf)?;
+            Ok((buf[1], byte_at(&buf, 8)))
+        }
+        Err(err) if err.raw_os_error() == Some(libc::EOPNOTSUPP) => {
+            // `struct ethtool_cmd` (44 bytes): `u16 speed` at byte 12, `u8 duplex` at byte 14
+            // and `u16 speed_hi` at byte 28.
+            let mut cmd = [0u32; 11];
+            cmd[0] = ETHTOOL_GSET;
+            ethtool_ioctl(ifname, &mut cmd)?;
+            let speed = u16::from_ne_bytes([byte_at(&cmd, 12), byte_at(&cmd, 13)]) as u32
+                | (u16::from_ne_bytes([byte_at(&cmd, 28), byte_at(&cmd, 29)]) as u32) << 16;
+            // The legacy interface reports an unknown speed as 0xffff in the low half only.
+            let speed = if speed as u16 == u16::MAX { SPEED_UNKNOWN } else { speed };
+            Ok((speed, byte_at(&cmd, 14)))
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// The last `setsockopt`/`getsockopt` error, with `ENOTSOCK` reported as `Unsupported`.
+fn busy_poll_error() -> io::Error {
+    let err = io::Error::last_os_error();
+    if err.raw_os_error() == Some(libc::ENOTSOCK) {
+        return io::Error::new(io::ErrorKind::Unsupported, "SO_BUSY_POLL needs a socket-backed descriptor");
+    }
+    err
+}
+
+impl Drop for Netmap {
+    fn drop(&mut self) {
+        if self.is_pipe {
+            // Close sentinel for `RxRing::peer_closed` on the other endpoint; best-effort.
+            for (index, &ring) in self.tx_ring_ptrs.iter().enumerate() {
+                let mut tx = TxRing::new(ring, index);
+                if tx.send(&[]).is_ok() {
+                    tx.sync();
+                }
+            }
+        }
+        unsafe {
+            ffi::nm_close(self.desc);
+        }
+    }
+}
+
+impl fmt::Debug for Netmap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Netmap")
+            .field("fd", &self.as_raw_fd())
+            .field("os_ifname", &self.os_ifname)
+            .field("num_tx_rings", &self.num_tx_rings)
+            .field("num_rx_rings", &self.num_rx_rings)
+            .field("is_host_if", &self.is_host_if)
+            .field("is_pipe", &self.is_pipe)
+            .finish_non_exhaustive()
+    }
+}
+
+impl AsRawFd for Netmap {
+    fn as_raw_fd(&self) -> RawFd {
+        unsafe { (*self.desc).fd }
+    }
+}
+
+/// A [`Netmap`] shared between threads, created by [`Netmap::into_shared`].
+///
+/// Cloning is cheap and every clone refers to the same open interface, which is closed
+/// once the last clone and the last ring handle are gone. Each ring can be held by at
+/// most one [`OwnedTxRing`]/[`OwnedRxRing`] at a time.
+#[derive(Clone)]
+pub struct SharedNetmap {
+    inner: Arc<SharedInner>,
+}
+
+struct SharedInner {
+    netmap: Netmap,
+    tx_busy: Vec<AtomicBool>, // Set while an `OwnedTxRing` holds the ring
+    rx_busy: Vec<AtomicBool>, // Set while an `OwnedRxRing` holds the ring
+}
+
+// Safety: `SharedInner` only exposes the immutable ring counts, cached ring pointers and
+// descriptor, never `Netmap`'s interior-mutable state, and the busy flags keep each ring
+// in a single handle.
+unsafe impl Sync for SharedInner {}
+
+impl SharedNetmap {
+    /// Number of TX rings, see [`Netmap::num_tx_rings`].
+    pub fn num_tx_rings(&self) -> usize {
+        self.inner.netmap.num_tx_rings
+    }
+
+    /// Number of RX rings, see [`Netmap::num_rx_rings`].
+    pub fn num_rx_rings(&self) -> usize {
+        self.inner.netmap.num_rx_rings
+    }
+
+    /// Traffic counts over every ring, see [`Netmap::stats`].
+    pub fn stats(&self) -> Stats {
+        self.inner.netmap.stats()
+    }
+
+    /// Zeroes the traffic counts, see [`Netmap::reset_stats`].
+    pub fn reset_stats(&self) {
+        self.inner.netmap.reset_stats()
+    }
+
+    /// Returns and zeroes the traffic counts, see [`Netmap::stats_and_reset`].
+    pub fn stats_and_reset(&self) -> Stats {
+        self.inner.netmap.stats_and_reset()
+    }
+
+    /// Takes the TX ring at `index` as a handle that can move to another thread.
+    ///
+    /// The ring becomes available again when the handle is dropped.
+    ///
+    /// # Errors
+    /// Returns `Error::InvalidRingIndex` if `index` is out of bounds and
+    /// `Error::RingBusy` if another `OwnedTxRing` currently holds the ring.
+    pub fn tx_ring(&self, index: usize) -> Result<OwnedTxRing, Error> {
+        let ring = *self.inner.netmap.tx_ring_ptrs.get(index).ok_or(Error::InvalidRingIndex(index))?;
+        claim(&self.inner.tx_busy[index], index)?;
+        Ok(OwnedTxRing {
+            // No peers: other threads may own the sibling rings, so this handle must not
+            // be able to mirror into them.
+            ring: TxRing::new(ring, index)
+                .with_fd(self.as_raw_fd())
+                .with_host_mtu(self.inner.netmap.host_mtu)
+                .with_counters(Arc::clone(&self.inner.netmap.tx_counters[index])),
+            index,
+            shared: Arc::clone(&self.inner),
+        })
+    }
+
+    /// Takes the RX ring at `index` as a handle that can move to another thread.
+    ///
+    /// The ring becomes available again when the handle is dropped.
+    ///
+    /// # Errors
+    /// Returns `Error::InvalidRingIndex` if `index` is out of bounds and
+    /// `Error::RingBusy` if another `OwnedRxRing` currently holds the ring.
+    pub fn rx_ring(&self, index: usize) -> Result<OwnedRxRing, Error> {
+        let ring = *self.inner.netmap.rx_ring_ptrs.get(index).ok_or(Error::InvalidRingIndex(index))?;
+        claim(&self.inner.rx_busy[index], index)?;
+        Ok(OwnedRxRing {
+            ring: RxRing::new(ring, index)
+                .with_fd(self.as_raw_fd())
+                .with_binder(self.inner.netmap.ring_binder.clone())
+                .with_pipe(self.inner.netmap.is_pipe)
+                .with_counters(Arc::clone(&self.inner.netmap.rx_counters[index])),
+            index,
+            shared: Arc::clone(&self.inner),
+        })
+    }
+
+    /// Takes every TX ring in index order, see [`tx_ring`](Self::tx_ring).
+    ///
+    /// Each item is taken as the iterator reaches it, so a ring held elsewhere yields
+    /// `Error::RingBusy` without affecting the others.
+    pub fn tx_rings(&self) -> impl ExactSizeIterator<Item = Result<OwnedTxRing, Error>> + '_ {
+        (0..self.num_tx_rings()).map(move |index| self.tx_ring(index))
+    }
+
+    /// Takes every RX ring in index order, see [`rx_ring`](Self::rx_ring).
+    ///
+    /// Each item is taken as the iterator reaches it, so a ring held elsewhere yields
+    /// `Error::RingBusy` without affecting the others.
+    pub fn rx_rings(&self) -> impl ExactSizeIterator<Item = Result<OwnedRxRing, Error>> + '_ {
+        (0..self.num_rx_rings()).map(move |index| self.rx_ring(index))
+    }
+}
+
+impl SharedNetmap {
+    /// Runs the thread-per-ring receive pattern: one thread per RX ring, each pinned to a
+    /// core and calling `worker(ring_index, frame)` for every packet its ring receives.
+    ///
+    /// Ring `i` is pinned to the `i`-th available core, wrapping around when there are
+    /// more rings than cores; pinning is best-effort and skipped where unsupported. Each
+    /// thread drains its ring and then `poll(2)`s for more packets, until the returned
+    /// [`RxWorkers`] is stopped or dropped. A thread also exits if polling fails.
+    ///
+    /// Each thread touches only its own ring: it opens a descriptor bound to that ring
+    /// alone (see [`NetmapBuilder::ring_id`]) and polls that, so its polls neither sync
+    /// nor wake on the rings other threads are reading. Where no such descriptor can be
+    /// opened (a pipe with several rings, or rings registered with
+    /// [`exclusive`](NetmapBuilder::exclusive)) the thread polls the shared descriptor
+    /// instead, which syncs every RX ring of the interface while the other threads may
+    /// be reading them; use a single ring, or a `Netmap` per ring, where that matters.
+    ///
+    /// # Errors
+    /// Returns `Error::RingBusy` if any RX ring is already held by an [`OwnedRxRing`], and
+    /// `Error::Io` if a thread cannot be spawned. Threads started before the failure are
+    /// stopped again.
+    pub fn spawn_rx_workers<F>(&self, worker: F) -> Result<RxWorkers, Error>
+    where
+        F: Fn(usize, Frame<'_>) + Send + Sync + 'static,
+    {
+        let rings = (0..self.num_rx_rings())
+            .map(|i| self.rx_ring(i))
+            .collect::<Result<Vec<_>, _>>()?;
+        let core_ids = core_affinity::get_core_ids().unwrap_or_default();
+        let binder = self.inner.netmap.ring_binder.clone();
+        let worker = Arc::new(worker);
+        let mut workers = RxWorkers {
+            stop: Arc::new(AtomicBool::new(false)),
+            handles: Vec::with_capacity(rings.len()),
+        };
+
+        for (i, mut ring) in rings.into_iter().enumerate() {
+            let core_id = core_ids.get(i % core_ids.len().max(1)).copied();
+            let worker = Arc::clone(&worker);
+            let stop = Arc::clone(&workers.stop);
+            let binder = binder.clone();
+            let handle = thread::Builder::new()
+                .name(format!("netmap-rx-{}", i))
+                .spawn(move || {
+                    if let Some(core_id) = core_id {
+                        core_affinity::set_for_current(core_id);
+                    }
+                    // Polling syncs the RX rings the descriptor is bound to, so poll one
+                    // bound to this ring alone where possible.
+                    let bound = binder.and_then(|binder| binder.bind(i).ok());
+                    let shared = Arc::clone(&ring.shared);
+                    let netmap = bound.as_ref().unwrap_or(&shared.netmap);
+                    while !stop.load(Ordering::Relaxed) {
+                        while let Some(frame) = ring.recv() {
+                            worker(i, frame);
+                        }
+                        if netmap.wait_readable(RX_WORKER_POLL_INTERVAL).is_err() {
+                            break;
+                        }
+                    }
+                })?;
+            workers.handles.push(handle);
+        }
+        Ok(workers)
+    }
+}
+
+/// Per-ring packet handler for [`Netmap::run`].
+pub type RingHandler = Box<dyn FnMut(Frame)>;
+
+/// How long an RX worker or [`Netmap::run`] blocks in `poll` before rechecking whether it should stop.
+const RX_WORKER_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Receive threads started by [`SharedNetmap::spawn_rx_workers`].
+///
+/// Dropping this stops and joins the threads, like [`stop`](Self::stop) but ignoring
+/// worker panics.
+pub struct RxWorkers {
+    stop: Arc<AtomicBool>,
+    handles: Vec<thread::JoinHandle<()>>,
+}
+
+impl RxWorkers {
+    /// Number of running worker threads, one per RX ring.
+    pub fn len(&self) -> usize {
+        self.handles.len()
+    }
+
+    /// Returns `true` if the interface had no RX rings to spawn workers for.
+    pub fn is_empty(&self) -> bool {
+        self.handles.is_empty()
+    }
+
+    /// Signals every worker to finish after its current batch and waits for them.
+    ///
+    /// The RX rings are released once this returns.
+    ///
+    /// # Errors
+    /// Returns the panic payload of the first worker that panicked; the others are still
+    /// joined.
+    pub fn stop(mut self) -> thread::Result<()> {
+        self.shutdown()
+    }
+
+    fn shutdown(&mut self) -> thread::Result<()> {
+        self.stop.store(true, Ordering::Relaxed);
+        let mut result = Ok(());
+        for handle in self.handles.drain(..) {
+            if let Err(panic) = handle.join() {
+                result = result.and(Err(panic));
+            }
+        }
+        result
+    }
+}
+
+impl Drop for RxWorkers {
+    fn drop(&mut self) {
+        let _ = self.shutdown();
+    }
+}
+
+impl AsRawFd for SharedNetmap {
+    fn as_raw_fd(&self) -> RawFd {
+        self.inner.netmap.as_raw_fd()
+    }
+}
+
+/// Forwards [`TxRing`]'s `&mut self` methods to a wrapper's `ring` field, with `$a` as
+/// the lifetime of the ring memory.
+///
+/// Wrappers forward instead of implementing `DerefMut`, which would let callers swap the
+/// ring they hold for another one. `sync_on_drop` is left out for the same reason.
+macro_rules! forward_tx_ring {
+    ($a:lifetime) => {
+        /// See [`TxRing::set_mirror`].
+        pub fn set_mirror(&mut self, index: Option<usize>) -> Result<(), Error> {
+            self.ring.set_mirror(index)
+        }
+
+        /// See [`TxRing::send`].
+        pub fn send(&mut self, buf: &[u8]) -> Result<(), Error> {
+            self.ring.send(buf)
+        }
+
+        /// See [`TxRing::send_blocking`].
+        pub fn send_blocking(&mut self, buf: &[u8], timeout: Option<Duration>) -> Result<(), Error> {
+            self.ring.send_blocking(buf, timeout)
+        }
+
+        /// See [`TxRing::send_at`].
+        pub fn send_at(&mut self, buf: &[u8]) -> Result<u32, Error> {
+            self.ring.send_at(buf)
+        }
+
+        /// See [`TxRing::reserve_slot`].
+        pub fn reserve_slot(&mut self, len: usize) -> Result<&mut [u8], Error> {
+            self.ring.reserve_slot(len)
+        }
+
+        /// See [`TxRing::commit_one`].
+        pub fn commit_one(&mut self) {
+            self.ring.commit_one()
+        }
+
+        /// See [`TxRing::send_checked`].
+        #[cfg(feature = "checksum")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "checksum")))]
+        pub fn send_checked(&mut self, buf: &[u8]) -> Result<(), Error> {
+            self.ring.send_checked(buf)
+        }
+
+        /// See [`TxRing::try_send`].
+        pub fn try_send(&mut self, buf: &[u8]) -> Result<(), Error> {
+            self.ring.try_send(buf)
+        }
+
+        /// See [`TxRing::send_padded`].
+        pub fn send_padded(&mut self, buf: &[u8]) -> Result<(), Error> {
+            self.ring.send_padded(buf)
+        }
+
+        /// See [`TxRing::send_to_host`].
+        pub fn send_to_host(&mut self, packet: &[u8]) -> Result<(), Error> {
+            self.ring.send_to_host(packet)
+        }
+
+        /// See [`TxRing::send_host_timed`].
+        pub fn send_host_timed(&mut self, packet: &[u8], timestamp: SystemTime) -> Result<(), Error> {
+            self.ring.send_host_timed(packet, timestamp)
+        }
+
+        /// See [`TxRing::send_tso`].
+        pub fn send_tso(&mut self, buf: &[u8], mss: u16) -> Result<(), Error> {
+            self.ring.send_tso(buf, mss)
+        }
+
+        /// See [`TxRing::reserve_batch`].
+        pub fn reserve_batch(&mut self, count: usize) -> Result<BatchReservation<$a>, Error> {
+            self.ring.reserve_batch(count)
+        }
+
+        /// See [`TxRing::batch`].
+        pub fn batch(&mut self, count: usize) -> Result<TxBatch<'_, 'static>, Error> {
+            self.ring.batch(count)
+        }
+
+        /// See [`TxRing::send_batch`].
+        pub fn send_batch(&mut self, packets: &[&[u8]]) -> Result<usize, Error> {
+            self.ring.send_batch(packets)
+        }
+    };
+}
+
+/// Forwards [`RxRing`]'s `&mut self` methods to a wrapper's `ring` field, see
+/// `forward_tx_ring`.
+macro_rules! forward_rx_ring {
+    () => {
+        /// See [`RxRing::enable_trace`].
+        pub fn enable_trace(&mut self, capacity: usize) {
+            self.ring.enable_trace(capacity)
+        }
+
+        /// See [`RxRing::disable_trace`].
+        pub fn disable_trace(&mut self) {
+            self.ring.disable_trace()
+        }
+
+        /// See [`RxRing::set_dst_mac_filter`].
+        pub fn set_dst_mac_filter(&mut self, mac: Option<[u8; 6]>) {
+            self.ring.set_dst_mac_filter(mac)
+        }
+
+        /// See [`RxRing::set_pass_multicast`].
+        pub fn set_pass_multicast(&mut self, pass: bool) {
+            self.ring.set_pass_multicast(pass)
+        }
+
+        /// See [`RxRing::recv`].
+        pub fn recv(&mut self) -> Option<Frame<'_>> {
+            self.ring.recv()
+        }
+
+        /// See [`RxRing::recv_pooled`].
+        pub fn recv_pooled(&mut self, pool: &FramePool) -> Option<PooledFrame> {
+            self.ring.recv_pooled(pool)
+        }
+
+        /// See [`RxRing::recv_timeout`].
+        pub fn recv_timeout(&mut self, timeout: Duration) -> Result<Option<Frame<'_>>, Error> {
+            self.ring.recv_timeout(timeout)
+        }
+
+        /// See [`RxRing::recv_checked`].
+        #[cfg(feature = "checksum")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "checksum")))]
+        pub fn recv_checked(&mut self) -> Result<Option<Frame<'_>>, Error> {
+            self.ring.recv_checked()
+        }
+
+        /// See [`RxRing::try_recv`].
+        pub fn try_recv(&mut self) -> Option<Frame<'_>> {
+            self.ring.try_recv()
+        }
+
+        /// See [`RxRing::recv_into_uninit`].
+        pub fn recv_into_uninit(&mut self, buf: &mut [MaybeUninit<u8>]) -> Option<usize> {
+            self.ring.recv_into_uninit(buf)
+        }
+
+        /// See [`RxRing::forward_filtered`].
+        pub fn forward_filtered(
+            &mut self,
+            tx: &mut TxRing<'_>,
+            max: usize,
+            keep: impl Fn(&[u8]) -> bool,
+        ) -> (usize, usize) {
+            self.ring.forward_filtered(tx, max, keep)
+        }
+
+        /// See [`RxRing::pass_through`].
+        pub fn pass_through(&mut self) -> bool {
+            self.ring.pass_through()
+        }
+
+        /// See [`RxRing::poll_recv`].
+        pub fn poll_recv(&mut self, waker: &Waker) -> Poll<Option<Frame<'_>>> {
+            self.ring.poll_recv(waker)
+        }
+
+        /// See [`RxRing::recv_batch_guard`].
+        pub fn recv_batch_guard(&mut self, max: usize) -> RxBatchGuard<'_, 'static> {
+            self.ring.recv_batch_guard(max)
+        }
+
+        /// See [`RxRing::recv_batch_borrowed`].
+        pub fn recv_batch_borrowed(&mut self, max: usize) -> BatchRecv<'_, 'static> {
+            self.ring.recv_batch_borrowed(max)
+        }
+
+        /// See [`RxRing::recv_auto_batch`].
+        pub fn recv_auto_batch(&mut self) -> Vec<Frame<'static>> {
+            self.ring.recv_auto_batch()
+        }
+
+        /// See [`RxRing::recv_batch`].
+        pub fn recv_batch<'f>(&'f mut self, batch: &'f mut [Frame<'f>]) -> &'f mut [Frame<'f>] {
+            self.ring.recv_batch(batch)
+        }
+    };
+}
+
+/// A TX ring lent out by [`Netmap::tx_ring_mut`].
+///
+/// Derefs to [`TxRing`] and forwards its sending methods, but never hands out the
+/// `&mut TxRing` itself, so the handle stored in the `Netmap` cannot be replaced.
+pub struct TxRingMut<'b> {
+    ring: &'b mut TxRing<'static>,
+}
+
+impl<'b> TxRingMut<'b> {
+    forward_tx_ring!('b);
+}
+
+impl<'b> Deref for TxRingMut<'b> {
+    type Target = TxRing<'b>;
+
+    fn deref(&self) -> &Self::Target {
+        self.ring
+    }
+}
+
+impl TxRingApi for TxRingMut<'_> {
+    fn send(&mut self, buf: &[u8]) -> Result<(), Error> {
+        self.ring.send(buf)
+    }
+
+    fn max_payload_size(&self) -> usize {
+        self.ring.max_payload_size()
+    }
+
+    fn sync(&mut self) {
+        self.ring.sync()
+    }
+}
+
+/// An RX ring lent out by [`Netmap::rx_ring_mut`].
+///
+/// Derefs to [`RxRing`] and forwards its receiving methods, but never hands out the
+/// `&mut RxRing` itself, so the handle stored in the `Netmap` cannot be replaced.
+pub struct RxRingMut<'b> {
+    ring: &'b mut RxRing<'static>,
+}
+
+impl RxRingMut<'_> {
+    forward_rx_ring!();
+}
+
+impl<'b> Deref for RxRingMut<'b> {
+    type Target = RxRing<'b>;
+
+    fn deref(&self) -> &Self::Target {
+        self.ring
+    }
+}
+
+impl RxRingApi for RxRingMut<'_> {
+    fn recv(&mut self) -> Option<Frame<'_>> {
+        self.ring.recv()
+    }
+
+    fn sync(&mut self) {
+        self.ring.sync()
+    }
+}
+
+/// Marks a ring as held, failing if it already is.
+fn claim(busy: &AtomicBool, index: usize) -> Result<(), Error> {
+    busy.compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+        .map(|_| ())
+        .map_err(|_| Error::RingBusy(index))
+}
+
+/// A TX ring handle that keeps its [`SharedNetmap`] open, see [`SharedNetmap::tx_ring`].
+///
+/// Derefs to [`TxRing`] and forwards its sending methods. The ring itself is never
+/// handed out mutably, so it cannot be swapped with a ring of another `SharedNetmap`.
+pub struct OwnedTxRing {
+    ring: TxRing<'static>,
+    index: usize,
+    shared: Arc<SharedInner>,
+}
+
+impl OwnedTxRing {
+    forward_tx_ring!('_);
+}
+
+impl Deref for OwnedTxRing {
+    type Target = TxRing<'static>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.ring
+    }
+}
+
+impl TxRingApi for OwnedTxRing {
+    fn send(&mut self, buf: &[u8]) -> Result<(), Error> {
+        self.ring.send(buf)
+    }
+
+    fn max_payload_size(&self) -> usize {
+        self.ring.max_payload_size()
+    }
+
+    fn sync(&mut self) {
+        self.ring.sync()
+    }
+}
+
+impl Drop for OwnedTxRing {
+    fn drop(&mut self) {
+        self.shared.tx_busy[self.index].store(false, Ordering::Release);
+    }
+}
+
+/// An RX ring handle that keeps its [`SharedNetmap`] open, see [`SharedNetmap::rx_ring`].
+///
+/// Derefs to [`RxRing`] and forwards its receiving methods. The ring itself is never
+/// handed out mutably, so it cannot be swapped with a ring of another `SharedNetmap`.
+pub struct OwnedRxRing {
+    ring: RxRing<'static>,
+    index: usize,
+    shared: Arc<SharedInner>,
+}
+
+impl OwnedRxRing {
+    forward_rx_ring!();
+}
+
+impl Deref for OwnedRxRing {
+    type Target = RxRing<'static>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.ring
+    }
+}
+
+impl RxRingApi for OwnedRxRing {
+    fn recv(&mut self) -> Option<Frame<'_>> {
+        self.ring.recv()
+    }
+
+    fn sync(&mut self) {
+        self.ring.sync()
+    }
+}
+
+impl Drop for OwnedRxRing {
+    fn drop(&mut self) {
+        self.shared.rx_busy[self.index].store(false, Ordering::Release);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bind_fail_message(builder: NetmapBuilder) -> String {
+        match builder.build() {
+            Err(Error::BindFail(msg)) => msg,
+            Err(e) => panic!("Expected BindFail, got {:?}", e),
+            Ok(_) => panic!("Expected BindFail, but build succeeded"),
+        }
+    }
+
+    #[test]
+    fn validate_rejects_registration_mode_in_flags() {
+        let msg = bind_fail_message(NetmapBuilder::new("eth0").flags(ffi::NR_REG_ONE_NIC));
+        assert!(msg.contains("must not set a registration mode"), "{}", msg);
+    }
+
+    #[test]
+    fn validate_rejects_host_rings_on_vale_port() {
+        let msg = bind_fail_message(NetmapBuilder::new("vale0:p1^"));
+        assert!(msg.contains("VALE port 'vale0:p1' has no host stack rings"), "{}", msg);
+    }
+
+    #[test]
+    fn validate_rejects_no_host_rings_with_host_suffix() {
+        let msg = bind_fail_message(NetmapBuilder::new("eth0^").no_host_rings());
+        assert!(msg.contains("no_host_rings() conflicts with the '^' host ring suffix"), "{}", msg);
+    }
+
+    #[test]
+    fn no_host_rings_requests_nic_rings_only() {
+        let req = NetmapBuilder::new("eth0").no_host_rings().build_nmreq().unwrap();
+        assert_eq!(req.nr_flags & ffi::NR_REG_MASK, ffi::NR_REG_NIC_ONLY);
+        assert_eq!((req.nr_host_tx_rings, req.nr_host_rx_rings), (0, 0));
+    }
+
+    #[test]
+    fn host_ring_counts_are_set_apart_from_nic_rings() {
+        let req = NetmapBuilder::new("eth0")
+            .num_tx_rings(4)
+            .num_rx_rings(4)
+            .num_host_tx_rings(1)
+            .num_host_rx_rings(1)
+            .build_nmreq()
+            .unwrap();
+        assert_eq!(req.nr_flags & ffi::NR_REG_MASK, ffi::NR_REG_NIC_SW);
+        assert_eq!((req.nr_tx_rings, req.nr_rx_rings), (4, 4));
+        assert_eq!((req.nr_host_tx_rings, req.nr_host_rx_rings), (1, 1));
+
+        let req = NetmapBuilder::new("eth0^").num_tx_rings(2).num_host_rx_rings(1).build_nmreq().unwrap();
+        assert_eq!(req.nr_flags & ffi::NR_REG_MASK, ffi::NR_REG_SW_ONLY);
+        assert_eq!((req.nr_tx_rings, req.nr_rx_rings), (0, 0));
+        assert_eq!((req.nr_host_tx_rings, req.nr_host_rx_rings), (2, 1));
+    }
+
+    #[test]
+    fn validate_rejects_host_ring_counts_without_host_rings() {
+        let msg = bind_fail_message(NetmapBuilder::new("vale0:p1").num_host_tx_rings(1));
+        assert!(msg.contains("has no host stack rings"), "{}", msg);
+        let msg = bind_fail_message(NetmapBuilder::new("pipe{host}").num_host_rx_rings(1));
+        assert!(msg.contains("has no host stack rings"), "{}", msg);
+        let msg = bind_fail_message(NetmapBuilder::new("eth0").no_host_rings().num_host_tx_rings(1));
+        assert!(msg.contains("no_host_rings() conflicts"), "{}", msg);
+        let msg = bind_fail_message(NetmapBuilder::new("eth0").ring_id(0).num_host_tx_rings(1));
+        assert!(msg.contains("cannot be combined with host rings"), "{}", msg);
+    }
+
+    #[test]
+    fn validate_rejects_slot_counts_that_are_not_powers_of_two() {
+        let msg = bind_fail_message(NetmapBuilder::new("eth0").num_tx_slots(1000));
+        assert!(msg.contains("TX slot count 1000"), "{}", msg);
+        let msg = bind_fail_message(NetmapBuilder::new("eth0").num_rx_slots(3));
+        assert!(msg.contains("RX slot count 3"), "{}", msg);
+    }
+
+    #[test]
+    fn slot_counts_go_into_the_request() {
+        let req = NetmapBuilder::new("eth0").build_nmreq().unwrap();
+        assert_eq!((req.nr_tx_slots, req.nr_rx_slots), (0, 0));
+        let req = NetmapBuilder::new("eth0").num_tx_slots(2048).num_rx_slots(256).build_nmreq().unwrap();
+        assert_eq!((req.nr_tx_slots, req.nr_rx_slots), (2048, 256));
+    }
+
+    #[test]
+    fn ring_id_registers_a_single_ring() {
+        let req = NetmapBuilder::new("eth0").ring_id(3).build_nmreq().unwrap();
+        assert_eq!(req.nr_flags & ffi::NR_REG_MASK, ffi::NR_REG_ONE_NIC);
+        assert_eq!(req.nr_ringid, 3);
+        let req = NetmapBuilder::new("eth0^").ring_id(0).build_nmreq().unwrap();
+        assert_eq!(req.nr_flags & ffi::NR_REG_MASK, ffi::NR_REG_ONE_SW);
+    }
+
+    #[test]
+    fn no_tx_poll_shares_the_ring_id() {
+        let mut builder = NetmapBuilder::new("eth0").ring_id(2);
+        builder.no_tx_poll = true;
+        let req = builder.build_nmreq().unwrap();
+        assert_eq!(req.nr_ringid as u32 & ffi::NETMAP_RING_MASK, 2);
+        assert_ne!(req.nr_ringid as u32 & ffi::NETMAP_NO_TX_POLL, 0);
+    }
+
+    #[test]
+    fn memory_id_goes_into_the_request() {
+        assert_eq!(NetmapBuilder::new("eth0").build_nmreq().unwrap().nr_arg2, 0);
+        assert_eq!(NetmapBuilder::new("eth0").memory_id(3).build_nmreq().unwrap().nr_arg2, 3);
+    }
+
+    #[test]
+    fn validate_rejects_ring_id_on_pipe() {
+        let msg = bind_fail_message(NetmapBuilder::new("pipe{ring_id}").ring_id(0));
+        assert!(msg.contains("ring_id() does not apply to pipe interface"), "{}", msg);
+    }
+
+    #[test]
+    fn validate_rejects_pipe_wait_peer_on_non_pipe() {
+        let msg = bind_fail_message(NetmapBuilder::new("eth0").pipe_wait_peer(Duration::from_secs(1)));
+        assert!(msg.contains("pipe_wait_peer() only applies to pipe interfaces"), "{}", msg);
+    }
+
+    #[test]
+    fn ethtool_bytes_and_duplex_decode() {
+        let words = [u32::from_ne_bytes([0, 1, 2, 3]), u32::from_ne_bytes([4, 5, 6, 7])];
+        assert_eq!(byte_at(&words, 2), 2);
+        assert_eq!(byte_at(&words, 7), 7);
+        assert_eq!(Duplex::from_ethtool(DUPLEX_FULL), Duplex::Full);
+        assert_eq!(Duplex::from_ethtool(DUPLEX_HALF), Duplex::Half);
+        assert_eq!(Duplex::from_ethtool(0xff), Duplex::Unknown);
+    }
+
+    #[test]
+    fn ethtool_rxnfc_matches_kernel_layout() {
+        assert_eq!(mem::size_of::<EthtoolRxFlowSpec>(), 168);
+        assert_eq!(mem::size_of::<EthtoolRxnfc>(), 192);
+    }
+
+    #[test]
+    fn flow_rule_encodes_only_the_fields_it_sets() {
+        let nfc = FlowRule::udp(2)
+            .dst_ip(Ipv4Addr::new(10, 0, 0, 1))
+            .dst_port(5000)
+            .to_rxnfc(ETHTOOL_SRXCLSRLINS, RX_CLS_LOC_ANY);
+
+        assert_eq!(nfc.cmd, ETHTOOL_SRXCLSRLINS);
+        assert_eq!(nfc.fs.flow_type, UDP_V4_FLOW);
+        assert_eq!(nfc.fs.ring_cookie, 2);
+        assert_eq!(nfc.fs.location, RX_CLS_LOC_ANY);
+        assert_eq!(&nfc.fs.h_u[..12], &[0, 0, 0, 0, 10, 0, 0, 1, 0, 0, 0x13, 0x88]);
+        assert_eq!(&nfc.fs.m_u[..12], &[0, 0, 0, 0, 0xff, 0xff, 0xff, 0xff, 0, 0, 0xff, 0xff]);
+        assert!(nfc.fs.h_u[12..].iter().chain(&nfc.fs.m_u[12..]).all(|&b| b == 0));
     }
 }
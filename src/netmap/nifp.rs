@@ -0,0 +1,147 @@
+//! Bounds-checked view of a descriptor's `netmap_if`.
+//!
+//! The `netmap_if` ("nifp") lives in the shared memory region and holds the ring counts
+//! plus the offset table netmap uses to locate every ring. Indexing that table past the
+//! rings that exist reads an arbitrary offset and yields a wild ring pointer, so all
+//! lookups go through [`Nifp`], which checks the index against the matching count first.
+//!
+//! The table lists hardware rings before host rings: host TX ring `i` is TX table entry
+//! `ni_tx_rings + i`, and likewise for RX.
+
+use crate::ffi;
+
+/// Copyable handle to the `netmap_if` of an open descriptor.
+#[derive(Clone, Copy)]
+pub(crate) struct Nifp {
+    ptr: *mut ffi::netmap_if,
+}
+
+impl Nifp {
+    /// Wrap `ptr`.
+    ///
+    /// # Safety
+    /// `ptr` must point to a `netmap_if` whose offset table has an entry for every ring it
+    /// counts, and must stay valid for as long as the `Nifp` (or a ring pointer obtained
+    /// from it) is used.
+    pub(crate) unsafe fn new(ptr: *mut ffi::netmap_if) -> Self {
+        Nifp { ptr }
+    }
+
+    /// Number of hardware (or VALE/pipe) TX rings.
+    pub(crate) fn num_tx_rings(&self) -> usize {
+        unsafe { (*self.ptr).ni_tx_rings as usize }
+    }
+
+    /// Number of hardware (or VALE/pipe) RX rings.
+    pub(crate) fn num_rx_rings(&self) -> usize {
+        unsafe { (*self.ptr).ni_rx_rings as usize }
+    }
+
+    /// Number of host stack TX rings.
+    pub(crate) fn num_host_tx_rings(&self) -> usize {
+        unsafe { (*self.ptr).ni_host_tx_rings as usize }
+    }
+
+    /// Number of host stack RX rings.
+    pub(crate) fn num_host_rx_rings(&self) -> usize {
+        unsafe { (*self.ptr).ni_host_rx_rings as usize }
+    }
+
+    /// Pointer to hardware TX ring `idx`, or `None` if there is no such ring.
+    pub(crate) fn tx_ring_ptr(&self, idx: usize) -> Option<*mut ffi::netmap_ring> {
+        (idx < self.num_tx_rings()).then(|| unsafe { ffi::NETMAP_TXRING(self.ptr, idx as u32) })
+    }
+
+    /// Pointer to hardware RX ring `idx`, or `None` if there is no such ring.
+    pub(crate) fn rx_ring_ptr(&self, idx: usize) -> Option<*mut ffi::netmap_ring> {
+        (idx < self.num_rx_rings()).then(|| unsafe { ffi::NETMAP_RXRING(self.ptr, idx as u32) })
+    }
+
+    /// Pointer to host TX ring `idx`, or `None` if there is no such ring.
+    pub(crate) fn host_tx_ring_ptr(&self, idx: usize) -> Option<*mut ffi::netmap_ring> {
+        (idx < self.num_host_tx_rings())
+            .then(|| unsafe { ffi::NETMAP_TXRING(self.ptr, (self.num_tx_rings() + idx) as u32) })
+    }
+
+    /// Pointer to host RX ring `idx`, or `None` if there is no such ring.
+    pub(crate) fn host_rx_ring_ptr(&self, idx: usize) -> Option<*mut ffi::netmap_ring> {
+        (idx < self.num_host_rx_rings())
+            .then(|| unsafe { ffi::NETMAP_RXRING(self.ptr, (self.num_rx_rings() + idx) as u32) })
+    }
+
+    /// Head of the extra-buffer free list, 0 when it is empty.
+    pub(crate) fn bufs_head(&self) -> u32 {
+        unsafe { (*self.ptr).ni_bufs_head }
+    }
+
+    /// Replace the head of the extra-buffer free list.
+    pub(crate) fn set_bufs_head(&self, index: u32) {
+        unsafe { (*self.ptr).ni_bufs_head = index }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::mem;
+
+    /// A `netmap_if` followed by a zeroed offset table, enough for the counts used below.
+    #[repr(C)]
+    struct MockNifp {
+        nifp: ffi::netmap_if,
+        ring_ofs: [isize; 16],
+    }
+
+    fn mock_nifp(tx: u32, rx: u32, host_tx: u32, host_rx: u32) -> Box<MockNifp> {
+        // SAFETY: netmap_if is a plain C struct, all-zero is a valid value.
+        let mut mock: Box<MockNifp> = Box::new(unsafe { mem::zeroed() });
+        mock.nifp.ni_tx_rings = tx;
+        mock.nifp.ni_rx_rings = rx;
+        mock.nifp.ni_host_tx_rings = host_tx;
+        mock.nifp.ni_host_rx_rings = host_rx;
+        mock
+    }
+
+    #[test]
+    fn counts_come_from_the_nifp() {
+        let mut mock = mock_nifp(4, 2, 1, 1);
+        let nifp = unsafe { Nifp::new(&mut mock.nifp) };
+        assert_eq!(nifp.num_tx_rings(), 4);
+        assert_eq!(nifp.num_rx_rings(), 2);
+        assert_eq!(nifp.num_host_tx_rings(), 1);
+        assert_eq!(nifp.num_host_rx_rings(), 1);
+    }
+
+    #[test]
+    fn ring_lookups_stop_at_the_ring_count() {
+        let mut mock = mock_nifp(4, 2, 1, 1);
+        let nifp = unsafe { Nifp::new(&mut mock.nifp) };
+        assert!((0..4).all(|i| nifp.tx_ring_ptr(i).is_some()));
+        assert!(nifp.tx_ring_ptr(4).is_none());
+        assert!((0..2).all(|i| nifp.rx_ring_ptr(i).is_some()));
+        assert!(nifp.rx_ring_ptr(2).is_none());
+        assert!(nifp.host_tx_ring_ptr(0).is_some());
+        assert!(nifp.host_tx_ring_ptr(1).is_none());
+        assert!(nifp.host_rx_ring_ptr(0).is_some());
+        assert!(nifp.host_rx_ring_ptr(1).is_none());
+    }
+
+    #[test]
+    fn no_host_rings_means_no_host_lookups() {
+        let mut mock = mock_nifp(1, 1, 0, 0);
+        let nifp = unsafe { Nifp::new(&mut mock.nifp) };
+        assert!(nifp.host_tx_ring_ptr(0).is_none());
+        assert!(nifp.host_rx_ring_ptr(0).is_none());
+        assert!(nifp.tx_ring_ptr(usize::MAX).is_none());
+    }
+
+    #[test]
+    fn bufs_head_round_trips() {
+        let mut mock = mock_nifp(1, 1, 0, 0);
+        let nifp = unsafe { Nifp::new(&mut mock.nifp) };
+        assert_eq!(nifp.bufs_head(), 0);
+        nifp.set_bufs_head(7);
+        assert_eq!(nifp.bufs_head(), 7);
+        assert_eq!(mock.nifp.ni_bufs_head, 7);
+    }
+}
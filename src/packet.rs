@@ -0,0 +1,335 @@
+//! Helpers for building packets before handing them to a ring.
+
+use crate::error::Error;
+
+/// Minimum Ethernet frame length in bytes, excluding the 4-byte FCS the NIC appends.
+pub const MIN_FRAME_LEN: usize = 60;
+
+/// Pads `buf` with zero bytes up to the Ethernet minimum frame length.
+///
+/// Buffers that are already [`MIN_FRAME_LEN`] bytes or longer are left untouched.
+///
+/// # Example
+/// ```
+/// use netmap_rs::packet::{pad_to_min, MIN_FRAME_LEN};
+///
+/// let mut buf = b"short".to_vec();
+/// pad_to_min(&mut buf);
+/// assert_eq!(buf.len(), MIN_FRAME_LEN);
+/// assert_eq!(&buf[..5], b"short");
+/// ```
+pub fn pad_to_min(buf: &mut Vec<u8>) {
+    if buf.len() < MIN_FRAME_LEN {
+        buf.resize(MIN_FRAME_LEN, 0);
+    }
+}
+
+/// Length of an Ethernet II header (no VLAN tag).
+pub const ETH_HEADER_LEN: usize = 14;
+
+const ETHERTYPE_IPV4: u16 = 0x0800;
+pub(crate) const IPPROTO_TCP: u8 = 6;
+pub(crate) const IPPROTO_UDP: u8 = 17;
+pub(crate) const UDP_HEADER_LEN: usize = 8;
+const IPPROTO_GRE: u8 = 47;
+const VXLAN_PORT: u16 = 4789;
+const VXLAN_HEADER_LEN: usize = 8;
+const VXLAN_FLAG_VNI: u8 = 0x08;
+const GRE_HEADER_LEN: usize = 4;
+const GRE_FLAG_CSUM: u8 = 0x80;
+const GRE_FLAG_KEY: u8 = 0x20;
+const GRE_FLAG_SEQ: u8 = 0x10;
+const ETHERTYPE_TEB: u16 = 0x6558; // Transparent Ethernet Bridging: GRE carrying Ethernet frames
+const TCP_FLAG_FIN: u8 = 0x01;
+const TCP_FLAG_PSH: u8 = 0x08;
+
+/// Splits an Ethernet/IPv4/TCP frame into segments carrying at most `mss` bytes of TCP payload each.
+///
+/// This is software TCP segmentation: every segment gets a copy of the original headers with
+/// the IPv4 total length, identification and header checksum, and the TCP sequence number and
+/// checksum, rewritten to match. `FIN` and `PSH` are only kept on the last segment. A frame
+/// whose payload already fits in `mss` is returned unchanged as a single segment.
+///
+/// # Errors
+/// Returns `Error::MalformedPacket` if `mss` is zero or `frame` is not a well-formed
+/// Ethernet/IPv4/TCP frame.
+pub fn segment_tcp(frame: &[u8], mss: u16) -> Result<Vec<Vec<u8>>, Error> {
+    if mss == 0 {
+        return Err(Error::MalformedPacket("MSS must be non-zero".to_string()));
+    }
+    if !is_ipv4(frame) {
+        return Err(Error::MalformedPacket("not an Ethernet/IPv4 frame".to_string()));
+    }
+    let (ihl, total_len) = ipv4_lengths(frame)?;
+    if total_len < ihl + 20 {
+        return Err(Error::MalformedPacket("inconsistent IPv4 lengths".to_string()));
+    }
+
+    let ip = &frame[ETH_HEADER_LEN..];
+    if ip[9] != IPPROTO_TCP {
+        return Err(Error::MalformedPacket("not a TCP segment".to_string()));
+    }
+
+    let tcp = &ip[ihl..total_len];
+    let data_offset = ((tcp[12] >> 4) as usize) * 4;
+    if data_offset < 20 || data_offset > tcp.len() {
+        return Err(Error::MalformedPacket("invalid TCP data offset".to_string()));
+    }
+
+    let headers_len = ETH_HEADER_LEN + ihl + data_offset;
+    let payload = &frame[headers_len..ETH_HEADER_LEN + total_len];
+    if payload.len() <= mss as usize {
+        return Ok(vec![frame[..ETH_HEADER_LEN + total_len].to_vec()]);
+    }
+
+    let headers = &frame[..headers_len];
+    let ip_id = u16::from_be_bytes([ip[4], ip[5]]);
+    let seq = u32::from_be_bytes([tcp[4], tcp[5], tcp[6], tcp[7]]);
+    let num_segments = payload.len().div_ceil(mss as usize);
+
+    let segments = payload
+        .chunks(mss as usize)
+        .enumerate()
+        .map(|(i, chunk)| {
+            let mut seg = Vec::with_capacity(headers_len + chunk.len());
+            seg.extend_from_slice(headers);
+            seg.extend_from_slice(chunk);
+
+            let (_, rest) = seg.split_at_mut(ETH_HEADER_LEN);
+            let (ip_hdr, tcp_seg) = rest.split_at_mut(ihl);
+
+            ip_hdr[2..4].copy_from_slice(&((ihl + data_offset + chunk.len()) as u16).to_be_bytes());
+            ip_hdr[4..6].copy_from_slice(&ip_id.wrapping_add(i as u16).to_be_bytes());
+            ip_hdr[10..12].copy_from_slice(&[0, 0]);
+            let ip_csum = internet_checksum(&[ip_hdr]);
+            ip_hdr[10..12].copy_from_slice(&ip_csum.to_be_bytes());
+
+            let offset = (i * mss as usize) as u32;
+            tcp_seg[4..8].copy_from_slice(&seq.wrapping_add(offset).to_be_bytes());
+            if i + 1 < num_segments {
+                tcp_seg[13] &= !(TCP_FLAG_FIN | TCP_FLAG_PSH);
+            }
+            tcp_seg[16..18].copy_from_slice(&[0, 0]);
+            let tcp_csum = tcp_checksum(ip_hdr, tcp_seg);
+            tcp_seg[16..18].copy_from_slice(&tcp_csum.to_be_bytes());
+
+            seg
+        })
+        .collect();
+
+    Ok(segments)
+}
+
+/// Hashes the flow a frame belongs to, for spreading flows across links or rings.
+///
+/// IPv4 TCP/UDP frames hash their addresses, ports and protocol; other IPv4 frames
+/// their addresses and protocol; anything else its MAC addresses and EtherType. The
+/// hash is symmetric, so both directions of a flow map to the same value. Truncated
+/// frames fall back to hashing whatever headers are present.
+pub fn flow_hash(frame: &[u8]) -> u32 {
+    let mut key = Vec::with_capacity(13);
+    let lengths = if is_ipv4(frame) { ipv4_lengths(frame).ok() } else { None };
+    match lengths {
+        Some((ihl, total_len)) => {
+            let ip = &frame[ETH_HEADER_LEN..];
+            let protocol = ip[9];
+            let has_ports = (protocol == IPPROTO_TCP || protocol == IPPROTO_UDP) && total_len >= ihl + 4;
+            let (mut a, mut b) = (ip[12..16].to_vec(), ip[16..20].to_vec());
+            if has_ports {
+                a.extend_from_slice(&ip[ihl..ihl + 2]);
+                b.extend_from_slice(&ip[ihl + 2..ihl + 4]);
+            }
+            let (lo, hi) = if a <= b { (a, b) } else { (b, a) };
+            key.extend_from_slice(&lo);
+            key.extend_from_slice(&hi);
+            key.push(protocol);
+        }
+        None => {
+            let header = &frame[..frame.len().min(ETH_HEADER_LEN)];
+            let (dst, src) = header.split_at(header.len().min(6));
+            let src = &src[..src.len().min(6)];
+            let (lo, hi) = if dst <= src { (dst, src) } else { (src, dst) };
+            key.extend_from_slice(lo);
+            key.extend_from_slice(hi);
+            key.extend_from_slice(&header[header.len().min(12)..]);
+        }
+    }
+
+    // 32-bit FNV-1a
+    key.iter()
+        .fold(0x811c_9dc5u32, |hash, &byte| (hash ^ byte as u32).wrapping_mul(0x0100_0193))
+}
+
+/// Computes and stores the UDP checksum of an Ethernet/IPv4/UDP frame.
+///
+/// A zero UDP checksum means "no checksum" for IPv4, but some host-stack configurations
+/// drop such packets when they are injected through a host ring, so frames bound for
+/// the host stack should carry a real one. A computed value of zero is stored as
+/// `0xffff`, as RFC 768 requires.
+///
+/// # Errors
+/// Returns `Error::MalformedPacket` if `frame` is not a well-formed Ethernet/IPv4/UDP frame.
+pub fn fill_udp_checksum(frame: &mut [u8]) -> Result<(), Error> {
+    let lengths = udp_lengths(frame)?;
+    store_udp_checksum(frame, lengths);
+    Ok(())
+}
+
+/// Checks that `frame` is a complete Ethernet frame, as host rings expect.
+///
+/// Beyond the Ethernet header, IPv4 frames must hold their whole IP packet. If the frame
+/// is IPv4/UDP with no checksum, returns the lengths to pass to [`store_udp_checksum`].
+#[cfg(feature = "sys")]
+pub(crate) fn check_host_frame(frame: &[u8]) -> Result<Option<(usize, usize)>, Error> {
+    if frame.len() < ETH_HEADER_LEN {
+        return Err(Error::MalformedPacket("shorter than an Ethernet header".to_string()));
+    }
+    if !is_ipv4(frame) {
+        return Ok(None);
+    }
+
+    ipv4_lengths(frame)?;
+    if frame[ETH_HEADER_LEN + 9] != IPPROTO_UDP {
+        return Ok(None);
+    }
+    let lengths = udp_lengths(frame)?;
+    let csum_at = ETH_HEADER_LEN + lengths.0 + 6;
+    Ok((frame[csum_at..csum_at + 2] == [0, 0]).then_some(lengths))
+}
+
+/// Writes the UDP checksum of a frame already validated by [`udp_lengths`].
+pub(crate) fn store_udp_checksum(frame: &mut [u8], (ihl, total_len): (usize, usize)) {
+    let (_, rest) = frame.split_at_mut(ETH_HEADER_LEN);
+    let (ip_hdr, datagram) = rest[..total_len].split_at_mut(ihl);
+
+    datagram[6..8].copy_from_slice(&[0, 0]);
+    let csum = match l4_checksum(ip_hdr, IPPROTO_UDP, datagram) {
+        0 => 0xffff,
+        csum => csum,
+    };
+    datagram[6..8].copy_from_slice(&csum.to_be_bytes());
+}
+
+/// IPv4 header length and total length of the Ethernet/IPv4/UDP frame in `frame`.
+fn udp_lengths(frame: &[u8]) -> Result<(usize, usize), Error> {
+    if !is_ipv4(frame) {
+        return Err(Error::MalformedPacket("not an Ethernet/IPv4 frame".to_string()));
+    }
+    let (ihl, total_len) = ipv4_lengths(frame)?;
+
+    let ip = &frame[ETH_HEADER_LEN..];
+    if ip[9] != IPPROTO_UDP {
+        return Err(Error::MalformedPacket("not a UDP datagram".to_string()));
+    }
+    let datagram = &ip[ihl..total_len];
+    if datagram.len() < UDP_HEADER_LEN
+        || u16::from_be_bytes([datagram[4], datagram[5]]) as usize != datagram.len()
+    {
+        return Err(Error::MalformedPacket("inconsistent UDP length".to_string()));
+    }
+    Ok((ihl, total_len))
+}
+
+/// Inner Ethernet frame of a VXLAN (UDP port 4789) or GRE over IPv4 frame, or `None` if
+/// `frame` is not one of those or is malformed.
+///
+/// GRE may carry Ethernet frames (Transparent Ethernet Bridging) or bare IPv4 packets;
+/// the latter are given an Ethernet header with the outer frame's MAC addresses, so the
+/// result is always an Ethernet frame.
+pub(crate) fn decapsulate(frame: &[u8]) -> Option<Vec<u8>> {
+    if !is_ipv4(frame) {
+        return None;
+    }
+    let (ihl, total_len) = ipv4_lengths(frame).ok()?;
+    let ip = &frame[ETH_HEADER_LEN..ETH_HEADER_LEN + total_len];
+    let l4 = &ip[ihl..];
+
+    match ip[9] {
+        IPPROTO_UDP => {
+            let (ihl, total_len) = udp_lengths(frame).ok()?;
+            let datagram = &frame[ETH_HEADER_LEN + ihl..ETH_HEADER_LEN + total_len];
+            if u16::from_be_bytes([datagram[2], datagram[3]]) != VXLAN_PORT {
+                return None;
+            }
+            let vxlan = &datagram[UDP_HEADER_LEN..];
+            if vxlan.len() < VXLAN_HEADER_LEN + ETH_HEADER_LEN || vxlan[0] & VXLAN_FLAG_VNI == 0 {
+                return None;
+            }
+            Some(vxlan[VXLAN_HEADER_LEN..].to_vec())
+        }
+        IPPROTO_GRE => {
+            if l4.len() < GRE_HEADER_LEN || l4[1] & 0x07 != 0 {
+                return None; // truncated, or not GRE version 0
+            }
+            let flags = l4[0];
+            let options = [GRE_FLAG_CSUM, GRE_FLAG_KEY, GRE_FLAG_SEQ]
+                .iter()
+                .filter(|&&flag| flags & flag != 0)
+                .count();
+            let inner = l4.get(GRE_HEADER_LEN + 4 * options..)?;
+            match u16::from_be_bytes([l4[2], l4[3]]) {
+                ETHERTYPE_TEB if inner.len() >= ETH_HEADER_LEN => Some(inner.to_vec()),
+                ETHERTYPE_IPV4 if !inner.is_empty() => {
+                    let mut decapsulated = Vec::with_capacity(ETH_HEADER_LEN + inner.len());
+                    decapsulated.extend_from_slice(&frame[..12]);
+                    decapsulated.extend_from_slice(&ETHERTYPE_IPV4.to_be_bytes());
+                    decapsulated.extend_from_slice(inner);
+                    Some(decapsulated)
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+pub(crate) fn is_ipv4(frame: &[u8]) -> bool {
+    frame.len() >= ETH_HEADER_LEN && u16::from_be_bytes([frame[12], frame[13]]) == ETHERTYPE_IPV4
+}
+
+/// IPv4 header length and total length of the packet in `frame`, checked against the frame size.
+pub(crate) fn ipv4_lengths(frame: &[u8]) -> Result<(usize, usize), Error> {
+    let ip = &frame[ETH_HEADER_LEN..];
+    if ip.len() < 20 || ip[0] >> 4 != 4 {
+        return Err(Error::MalformedPacket("truncated or non-IPv4 header".to_string()));
+    }
+    let ihl = ((ip[0] & 0x0f) as usize) * 4;
+    let total_len = u16::from_be_bytes([ip[2], ip[3]]) as usize;
+    if ihl < 20 || total_len > ip.len() || total_len < ihl {
+        return Err(Error::MalformedPacket("inconsistent IPv4 lengths".to_string()));
+    }
+    Ok((ihl, total_len))
+}
+
+/// RFC 1071 Internet checksum over the concatenation of `parts`.
+pub(crate) fn internet_checksum(parts: &[&[u8]]) -> u16 {
+    let mut sum: u32 = 0;
+    let mut odd: Option<u8> = None;
+    for part in parts {
+        for &byte in part.iter() {
+            match odd.take() {
+                Some(hi) => sum += u16::from_be_bytes([hi, byte]) as u32,
+                None => odd = Some(byte),
+            }
+        }
+    }
+    if let Some(hi) = odd {
+        sum += u16::from_be_bytes([hi, 0]) as u32;
+    }
+    while sum >> 16 != 0 {
+        sum = (sum & 0xffff) + (sum >> 16);
+    }
+    !(sum as u16)
+}
+
+/// TCP checksum of `segment` (header + payload, checksum field zeroed) under `ip_hdr`'s pseudo-header.
+fn tcp_checksum(ip_hdr: &[u8], segment: &[u8]) -> u16 {
+    l4_checksum(ip_hdr, IPPROTO_TCP, segment)
+}
+
+/// Checksum of a `protocol` segment (checksum field zeroed) under `ip_hdr`'s IPv4 pseudo-header.
+fn l4_checksum(ip_hdr: &[u8], protocol: u8, segment: &[u8]) -> u16 {
+    let len = (segment.len() as u16).to_be_bytes();
+    let pseudo = [0, protocol, len[0], len[1]];
+    internet_checksum(&[&ip_hdr[12..20], &pseudo, segment])
+}
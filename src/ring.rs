@@ -1,27 +1,245 @@
 #![cfg(feature = "sys")]
 
+use std::cell::UnsafeCell;
+use std::io;
 use std::marker::PhantomData;
+use std::mem::{self, MaybeUninit};
+use std::ops::{Deref, DerefMut};
+use std::os::unix::io::RawFd;
 use std::ptr;
 use std::slice;
+use std::sync::atomic::{self, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::task::{Poll, Waker};
+use std::time::{Duration, Instant, SystemTime};
 
+use crate::api::{RxRingApi, TxRingApi};
 use crate::error::Error;
 use crate::ffi;
-use crate::frame::Frame;
+use crate::frame::{Frame, FramePool, PooledFrame};
+use crate::netmap::RingBinder;
+use crate::packet::{self, ETH_HEADER_LEN, MIN_FRAME_LEN};
+use crate::trace::PacketTrace;
+use watch::FdWatcher;
+
+pub use blocking::{BlockingRxRing, BlockingTxRing};
+
+mod blocking;
+#[cfg(test)]
+mod mock;
+mod watch;
+
+/// Length of the CRC32 trailer appended by [`TxRing::send_checked`].
+#[cfg(feature = "checksum")]
+#[cfg_attr(docsrs, doc(cfg(feature = "checksum")))]
+pub const CHECKSUM_LEN: usize = 4;
+
+/// Batch size [`RxRing::recv_auto_batch`] starts from.
+const AUTO_BATCH_INITIAL: usize = 32;
+/// Bounds [`RxRing::recv_auto_batch`] adapts its batch size within.
+const AUTO_BATCH_MIN: usize = 4;
+const AUTO_BATCH_MAX: usize = 1024;
+/// How many calls in a row must fill (or nearly miss) the batch before its size changes.
+const AUTO_BATCH_STREAK: u32 = 4;
+
+/// Whether `slot` is followed by further fragments of the same packet.
+fn has_more_frags(slot: &ffi::netmap_slot) -> bool {
+    slot.flags & ffi::NS_MOREFRAG as u16 != 0
+}
+
+/// Read the packet starting at slot `start` of an RX ring with `avail` slots visible from there.
+///
+/// A chain of `NS_MOREFRAG` slots is gathered into one owned frame; a single slot is
+/// borrowed. Returns the frame and the number of slots it spans, or `None` if no slot is
+/// visible or the chain runs past the visible ones because its tail has not arrived yet.
+///
+/// # Safety
+/// `ring` must point to a live ring whose `avail` slots from `start` are owned by
+/// userspace for as long as `'f`.
+unsafe fn read_packet<'f>(ring: *const ffi::netmap_ring, start: u32, avail: usize) -> Option<(Frame<'f>, usize)> {
+    let num_slots = (*ring).num_slots;
+    let slot_data = |frag: usize| -> (bool, &'f [u8]) {
+        let slot = &*(*ring).slot.add(((start + frag as u32) % num_slots) as usize);
+        (has_more_frags(slot), slice::from_raw_parts(slot.buf as *const u8, slot.len as usize))
+    };
+    if avail == 0 {
+        return None;
+    }
+    let (mut more, buf) = slot_data(0);
+    if !more {
+        return Some((Frame::new(buf), 1));
+    }
+    let mut data = buf.to_vec();
+    let mut frags = 1;
+    while more {
+        if frags == avail {
+            return None;
+        }
+        let (next_more, next_buf) = slot_data(frags);
+        data.extend_from_slice(next_buf);
+        more = next_more;
+        frags += 1;
+    }
+    Some((Frame::new_owned(data), frags))
+}
+
+/// Adaptation state behind [`RxRing::recv_auto_batch`].
+struct AutoBatch {
+    size: usize,
+    full_streak: u32,   // Consecutive calls that filled the whole batch
+    sparse_streak: u32, // Consecutive calls that filled at most a quarter of it
+}
+
+impl AutoBatch {
+    fn new() -> Self {
+        AutoBatch {
+            size: AUTO_BATCH_INITIAL,
+            full_streak: 0,
+            sparse_streak: 0,
+        }
+    }
+
+    /// Account for a call that received `count` packets, doubling the batch after a streak
+    /// of full ones and halving it after a streak of sparse ones.
+    fn record(&mut self, count: usize) {
+        if count >= self.size {
+            self.full_streak += 1;
+            self.sparse_streak = 0;
+        } else if count <= self.size / 4 {
+            self.sparse_streak += 1;
+            self.full_streak = 0;
+        } else {
+            self.full_streak = 0;
+            self.sparse_streak = 0;
+        }
+
+        if self.full_streak >= AUTO_BATCH_STREAK {
+            self.size = (self.size * 2).min(AUTO_BATCH_MAX);
+            self.full_streak = 0;
+        } else if self.sparse_streak >= AUTO_BATCH_STREAK {
+            self.size = (self.size / 2).max(AUTO_BATCH_MIN);
+            self.sparse_streak = 0;
+        }
+    }
+}
+
+/// Direction of a ring, as reported in a [`RingSnapshot`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RingDirection {
+    /// A transmission ring.
+    Tx,
+    /// A reception ring.
+    Rx,
+}
+
+/// A point-in-time copy of one ring's pointers, taken by [`Netmap::snapshot_rings`](crate::Netmap::snapshot_rings).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RingSnapshot {
+    /// Whether this is a TX or RX ring.
+    pub direction: RingDirection,
+    /// Ring index within its direction.
+    pub index: usize,
+    /// Total number of slots in the ring.
+    pub num_slots: u32,
+    /// First slot owned by userspace.
+    pub head: u32,
+    /// Userspace wakeup/progress pointer.
+    pub cur: u32,
+    /// First slot owned by the kernel.
+    pub tail: u32,
+}
+
+impl RingSnapshot {
+    /// Read `ring`'s pointers. `tail` is written by the kernel concurrently, so it is read
+    /// with [`load_tail`] and the rest volatile; the fields are not read atomically as a group.
+    pub(crate) unsafe fn read(ring: *const ffi::netmap_ring, direction: RingDirection, index: usize) -> Self {
+        Self {
+            direction,
+            index,
+            num_slots: ptr::read_volatile(ptr::addr_of!((*ring).num_slots)),
+            head: ptr::read_volatile(ptr::addr_of!((*ring).head)),
+            cur: ptr::read_volatile(ptr::addr_of!((*ring).cur)),
+            tail: load_tail(ring),
+        }
+    }
+}
 
 /// A Netmap ring (tx/rx)
 pub struct Ring<'a> {
     ring: *mut ffi::netmap_ring,
     index: usize,
+    counters: Option<Arc<RingCounters>>, // Shared with the owning `Netmap`, for `Netmap::stats`
     _marker: PhantomData<&'a mut ffi::netmap_ring>,
 }
 
 unsafe impl<'a> Send for Ring<'a> {}
 
+/// Traffic through one ring, kept by the crate as netmap rings carry no counters.
+///
+/// A packet is counted when its slots are handed back to the kernel: on TX once it is
+/// queued, on RX once it is consumed. Every handle to the ring shares the same counters.
+#[derive(Debug, Default)]
+pub(crate) struct RingCounters {
+    pub(crate) packets: AtomicU64,
+    pub(crate) bytes: AtomicU64,
+    pub(crate) dropped: AtomicU64, // RX packets consumed but discarded by the crate
+}
+
 /// A TX ring
-pub struct TxRing<'a>(Ring<'a>);
+pub struct TxRing<'a> {
+    inner: Ring<'a>,
+    host_mtu: Option<usize>, // MTU of the interface behind a host TX ring, checked by `send_to_host`
+    slot_reserved: bool,     // `reserve_slot` handed out the slot at `cur` and `commit_one` has not published it
+    peers: Option<TxPeers>, // The owning `Netmap`'s handles to every TX ring, for `set_mirror`
+    mirror: Option<usize>,  // Index of the peer every sent packet is copied to
+    fd: Option<RawFd>, // Descriptor `send_blocking` waits on, if the ring came from a `Netmap`
+}
 
 /// An RX ring
-pub struct RxRing<'a>(Ring<'a>);
+pub struct RxRing<'a> {
+    inner: Ring<'a>,
+    trace: Option<PacketTrace>,
+    fd: Option<RawFd>,          // Descriptor `poll_recv` waits on, if the ring came from a `Netmap`
+    watcher: Option<FdWatcher>, // Started by the first `poll_recv` that finds the ring empty
+    binder: Option<Arc<RingBinder>>, // Opens a descriptor bound to this ring for the watcher
+    dst_mac_filter: Option<[u8; 6]>,
+    pass_multicast: bool, // Let group-addressed frames through `dst_mac_filter`
+    auto_batch: AutoBatch, // Batch size `recv_auto_batch` has adapted to
+    pipe: bool, // Pipe endpoint: a zero-length last slot is the peer's close sentinel
+}
+
+/// The TX ring handles a `Netmap` keeps for [`Netmap::tx_ring_mut`](crate::Netmap::tx_ring_mut),
+/// through which [`TxRing::set_mirror`] copies packets.
+#[derive(Clone, Copy)]
+pub(crate) struct TxPeers {
+    rings: *const UnsafeCell<TxRing<'static>>,
+    len: usize,
+}
+
+// Safety: the handles behind `rings` are only read, through `get`, while their `Netmap`
+// is borrowed by the `TxRing` holding these peers; it cannot touch them then, since
+// `tx_ring_mut` needs it exclusively. So moving that `TxRing` to another thread shares no
+// more with it than a second handle to the mirror ring would, and `TxRing` stays `Send`.
+// Owned handles, which borrow no `Netmap`, are built without peers.
+unsafe impl Send for TxPeers {}
+
+impl TxPeers {
+    pub(crate) fn new(rings: &[UnsafeCell<TxRing<'static>>]) -> Self {
+        Self {
+            rings: rings.as_ptr(),
+            len: rings.len(),
+        }
+    }
+
+    /// The handle to TX ring `index`, if there is one.
+    ///
+    /// # Safety
+    /// The `Netmap` owning the handles must be alive, and handle `index` must not be
+    /// borrowed mutably for as long as the returned reference is used.
+    unsafe fn get<'r>(&self, index: usize) -> Option<&'r TxRing<'static>> {
+        (index < self.len).then(|| &*(*self.rings.add(index)).get())
+    }
+}
 
 impl<'a> Ring<'a> {
     /// Create a new ring
@@ -29,10 +247,23 @@ impl<'a> Ring<'a> {
         Self {
             ring,
             index,
+            counters: None,
             _marker: PhantomData,
         }
     }
 
+    /// Hands the slots up to `head` back to the kernel, counting them first.
+    unsafe fn release(&self, head: u32) {
+        release(self.ring, head, self.counters.as_deref());
+    }
+
+    /// Counts a received packet the crate discarded instead of returning it.
+    fn count_dropped(&self) {
+        if let Some(counters) = &self.counters {
+            counters.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
     /// Get the ring index (the ID of this ring).
     pub fn index(&self) -> usize {
         self.index
@@ -43,77 +274,790 @@ impl<'a> Ring<'a> {
         unsafe { (*self.ring).num_slots as usize }
     }
 
+    /// Get the ring's `head`: the first slot userspace still owns.
+    pub fn head(&self) -> u32 {
+        unsafe { ptr::read_volatile(ptr::addr_of!((*self.ring).head)) }
+    }
+
+    /// Get the ring's `cur`: where userspace will read or write next.
+    pub fn cur(&self) -> u32 {
+        unsafe { ptr::read_volatile(ptr::addr_of!((*self.ring).cur)) }
+    }
+
+    /// Get the ring's `tail`: the first slot owned by the kernel, as of the last sync.
+    pub fn tail(&self) -> u32 {
+        unsafe { load_tail(self.ring) }
+    }
+
+    /// Number of slots userspace may currently consume, as `nm_ring_space()` computes it:
+    /// free slots on a TX ring, received packets on an RX ring.
+    pub(crate) fn space(&self) -> usize {
+        unsafe {
+            let ring = self.ring;
+            let num_slots = (*ring).num_slots;
+            ((load_tail(ring) + num_slots - (*ring).cur % num_slots) % num_slots) as usize
+        }
+    }
+
     /// sync the ring with the NIC
+    ///
+    /// Failures are ignored; use [`try_sync`](Self::try_sync) to observe them.
     pub fn sync(&self) {
+        let _ = self.try_sync();
+    }
+
+    /// sync the ring with the NIC, reporting whether the kernel accepted the sync
+    ///
+    /// # Errors
+    /// Returns `Error::Io` with the OS error if the sync fails, e.g. because the
+    /// descriptor was closed or revoked.
+    pub fn try_sync(&self) -> Result<(), Error> {
         unsafe {
             if (*self.ring).flags & ffi::NR_TX as u16 != 0 {
-                ffi::nm_txsync(self.ring, 0);
+                sync_result(ffi::nm_txsync(self.ring, 0))
             } else {
-                ffi::nm_rxsync(self.ring, 0);
+                sync_result(ffi::nm_rxsync(self.ring, 0))
             }
         }
     }
 }
 
+// Memory ordering on the ring pointers.
+//
+// A ring is shared with the kernel, which moves `tail` whenever the descriptor is synced
+// or polled, possibly from another thread while this one is reading the ring. Userspace
+// owns the slots from `head` to `tail` and hands them back by moving `head` and `cur`.
+// Every access to those pointers goes through the two helpers below:
+//
+// - `load_tail` is acquire: slot contents are read only after `tail` says they are ours,
+//   never speculated ahead of it, so a packet is not read before the kernel wrote it.
+// - `publish_head` is release: everything written to the slots being returned (packet
+//   data, lengths, flags, swapped buffer indices) is visible before `head` moves past them.
+//
+// Both use volatile accesses so the compiler cannot cache, merge or drop them, plus
+// `atomic::fence` so the CPU keeps the order too, matching the barriers netmap issues on
+// its side of `tail` and `head`.
+
+/// Reads `ring`'s `tail`, then keeps later slot reads from moving before it.
+pub(crate) unsafe fn load_tail(ring: *const ffi::netmap_ring) -> u32 {
+    let tail = ptr::read_volatile(ptr::addr_of!((*ring).tail));
+    atomic::fence(Ordering::Acquire);
+    tail
+}
+
+/// Returns the slots before `head` to the kernel, after all earlier writes to them.
+pub(crate) unsafe fn publish_head(ring: *mut ffi::netmap_ring, head: u32) {
+    atomic::fence(Ordering::Release);
+    ptr::write_volatile(ptr::addr_of_mut!((*ring).head), head);
+    ptr::write_volatile(ptr::addr_of_mut!((*ring).cur), head);
+}
+
+/// [`publish_head`], first adding the packets and bytes in the released slots to `counters`.
+///
+/// A chain of `NS_MOREFRAG` slots counts as one packet.
+pub(crate) unsafe fn release(ring: *mut ffi::netmap_ring, head: u32, counters: Option<&RingCounters>) {
+    if let Some(counters) = counters {
+        let num_slots = (*ring).num_slots;
+        let (mut packets, mut bytes) = (0, 0);
+        let mut idx = (*ring).head % num_slots;
+        while idx != head % num_slots {
+            let slot = &*(*ring).slot.add(idx as usize);
+            bytes += slot.len as u64;
+            if !has_more_frags(slot) {
+                packets += 1;
+            }
+            idx = (idx + 1) % num_slots;
+        }
+        counters.packets.fetch_add(packets, Ordering::Relaxed);
+        counters.bytes.fetch_add(bytes, Ordering::Relaxed);
+    }
+    publish_head(ring, head);
+}
+
+/// Queues copies of `ring`'s slots from `start` up to `end` on `mirror`, as [`TxRing::set_mirror`] describes.
+///
+/// Nothing is copied unless `mirror` has room for every slot and no slot handed out by
+/// `reserve_slot`, which the copies would overwrite.
+unsafe fn mirror_slots(ring: *const ffi::netmap_ring, mirror: &TxRing<'_>, start: u32, end: u32) {
+    let (num_slots, dst) = ((*ring).num_slots, mirror.inner.ring);
+    let count = (end + num_slots - start) % num_slots;
+    if mirror.slot_reserved || count as usize > mirror.inner.space() {
+        return;
+    }
+    let mut cur = (*dst).cur;
+    for i in 0..count {
+        let from = &*(*ring).slot.add(((start + i) % num_slots) as usize);
+        let to = &mut *(*dst).slot.add(cur as usize);
+        ptr::copy_nonoverlapping(from.buf as *const u8, to.buf as *mut u8, from.len as usize);
+        to.len = from.len;
+        to.flags = (to.flags & !(ffi::NS_MOREFRAG as u16)) | (from.flags & ffi::NS_MOREFRAG as u16);
+        cur = (cur + 1) % (*dst).num_slots;
+    }
+    mirror.inner.release(cur);
+}
+
+/// Turns the return value of `nm_txsync`/`nm_rxsync` into a `Result`.
+fn sync_result(ret: libc::c_int) -> Result<(), Error> {
+    if ret < 0 {
+        return Err(Error::Io(io::Error::last_os_error()));
+    }
+    Ok(())
+}
+
+/// `poll(2)` timeout in milliseconds until `deadline`, `-1` for none, or `None` once it has passed
+fn poll_timeout_ms(deadline: Option<Instant>) -> Option<libc::c_int> {
+    let Some(deadline) = deadline else {
+        return Some(-1);
+    };
+    let remaining = deadline.saturating_duration_since(Instant::now());
+    if remaining.is_zero() {
+        return None;
+    }
+    // Round up so a sub-millisecond remainder still waits instead of spinning.
+    Some(remaining.as_nanos().div_ceil(1_000_000).min(libc::c_int::MAX as u128) as libc::c_int)
+}
+
 impl<'a> TxRing<'a> {
     /// create a new tx ring
     pub(crate) fn new(ring: *mut ffi::netmap_ring, index: usize) -> Self {
-        Self(Ring::new(ring, index))
+        Self {
+            inner: Ring::new(ring, index),
+            host_mtu: None,
+            slot_reserved: false,
+            peers: None,
+            mirror: None,
+            fd: None,
+        }
+    }
+
+    /// attach the descriptor of the `Netmap` this ring belongs to, for [`send_blocking`](Self::send_blocking)
+    pub(crate) fn with_fd(mut self, fd: RawFd) -> Self {
+        self.fd = Some(fd);
+        self
+    }
+
+    /// record the handles of the `Netmap` this ring belongs to, so [`set_mirror`](Self::set_mirror) can find its siblings
+    ///
+    /// Not a `with_` builder, since the `Netmap`'s own handles can only be given their
+    /// peers once they are in place.
+    pub(crate) fn set_peers(&mut self, peers: TxPeers) {
+        self.peers = Some(peers);
+    }
+
+    /// record the MTU of the interface whose host stack this ring feeds, for [`send_to_host`](Self::send_to_host)
+    pub(crate) fn with_host_mtu(mut self, mtu: Option<usize>) -> Self {
+        self.host_mtu = mtu;
+        self
+    }
+
+    /// count packets queued on this ring in `counters`, shared with the owning `Netmap`
+    pub(crate) fn with_counters(mut self, counters: Arc<RingCounters>) -> Self {
+        self.inner.counters = Some(counters);
+        self
+    }
+
+    /// index of this ring among the interface's TX rings
+    ///
+    /// Netmap slots carry no source tag: a VALE switch identifies where a packet came from
+    /// by the port and the TX ring it was queued on, and delivers it to the destination
+    /// port's RX ring with the same index (modulo that port's RX ring count). Sending on
+    /// a given ring is therefore enough to steer traffic deterministically across rings.
+    pub fn logical_index(&self) -> usize {
+        self.inner.index()
+    }
+
+    /// hand queued packets to the kernel and reclaim slots it has finished sending (`txsync`)
+    ///
+    /// Failures are ignored; use [`try_sync`](Self::try_sync) to observe them.
+    pub fn sync(&self) {
+        let _ = self.try_sync();
+    }
+
+    /// like [`sync`](Self::sync), but reports a failed `txsync`
+    ///
+    /// # Errors
+    /// Returns `Error::Io` with the OS error if the sync fails, e.g. because the
+    /// descriptor was closed or revoked after a link flap.
+    pub fn try_sync(&self) -> Result<(), Error> {
+        if let Some(mirror) = self.mirror_ring() {
+            sync_result(unsafe { ffi::nm_txsync(mirror.inner.ring, 0) })?;
+        }
+        sync_result(unsafe { ffi::nm_txsync(self.inner.ring, 0) })
+    }
+
+    /// copy every packet queued on this ring to TX ring `index` of the same descriptor
+    ///
+    /// This is the egress counterpart of a monitor port: a forwarder can mirror what it
+    /// transmits to a ring a capture tool listens behind. Every way of queuing packets is
+    /// mirrored, including batches and [`commit_one`](Self::commit_one). The copy is
+    /// best-effort, as on a switch SPAN port: it is dropped when the mirror ring is full
+    /// or has a slot handed out by [`reserve_slot`](Self::reserve_slot) through
+    /// [`Netmap::tx_ring_mut`](crate::Netmap::tx_ring_mut), and it never makes the
+    /// primary send fail. Copies are counted in the mirror ring's [`stats`](crate::Netmap::stats).
+    /// [`sync`](Self::sync) syncs the mirror ring as well. `None` stops mirroring.
+    ///
+    /// Mirroring is only available on rings obtained from a `Netmap`; the index counts
+    /// the same rings as [`Netmap::tx_ring`](crate::Netmap::tx_ring). It is refused on an
+    /// [`OwnedTxRing`](crate::OwnedTxRing), since another thread may hold the mirror ring.
+    ///
+    /// # Errors
+    /// Returns `Error::InvalidRingIndex` if there is no such ring or `index` is this ring's own.
+    pub fn set_mirror(&mut self, index: Option<usize>) -> Result<(), Error> {
+        let Some(index) = index else {
+            self.mirror = None;
+            return Ok(());
+        };
+        if index == self.inner.index() || !matches!(self.peers, Some(peers) if index < peers.len) {
+            return Err(Error::InvalidRingIndex(index));
+        }
+        self.mirror = Some(index);
+        Ok(())
+    }
+
+    /// index of the ring packets are mirrored to, if [`set_mirror`](Self::set_mirror) enabled mirroring
+    pub fn mirror(&self) -> Option<usize> {
+        self.mirror
+    }
+
+    /// the `Netmap`'s handle to the ring packets are mirrored to, if any
+    fn mirror_ring(&self) -> Option<&TxRing<'static>> {
+        // Safety: the peers outlive this ring's borrow of their `Netmap`. `set_mirror`
+        // refuses this ring's own index, and another ring's handle is only borrowed mutably
+        // through `Netmap::tx_ring_mut`, which no handle can be used alongside.
+        unsafe { self.peers?.get(self.mirror?) }
+    }
+
+    /// hand the slots up to `head` to the kernel and queue copies of them on the mirror ring
+    unsafe fn publish(&self, head: u32) {
+        let start = (*self.inner.ring).head;
+        self.inner.release(head);
+        if let Some(mirror) = self.mirror_ring() {
+            mirror_slots(self.inner.ring, mirror, start, head);
+        }
     }
 
     /// send a single packet
+    ///
+    /// A packet larger than [`max_payload_size`](Self::max_payload_size) is split across
+    /// consecutive slots, with `NS_MOREFRAG` set on every slot but the last, as netmap
+    /// expects for jumbo frames. Either every fragment is queued or none is.
+    ///
+    /// # Errors
+    /// Returns `Error::InsufficientSpace` if there are not enough free slots (all of them
+    /// may hold packets waiting for a [`sync`](Self::sync)), or `Error::PacketTooLarge`
+    /// if `buf` needs more slots than the ring can ever have free.
     pub fn send(&mut self, buf: &[u8]) -> Result<(), Error> {
         if buf.len() > self.max_payload_size() {
+            self.send_fragmented(buf)?;
+        } else {
+            self.send_with(buf.len(), |slot| {
+                // copy data to the slot
+                unsafe { ptr::copy_nonoverlapping(buf.as_ptr(), slot.as_mut_ptr(), buf.len()) }
+            })?;
+        }
+        Ok(())
+    }
+
+    /// send a single packet, waiting up to `timeout` for room in the ring (`None` waits indefinitely)
+    ///
+    /// Behaves like [`send`](Self::send) while the ring has space. When it is full, the
+    /// ring is synced to reclaim slots the kernel has finished with and, if that frees
+    /// too few, the descriptor is `poll(2)`ed for `POLLOUT` until it does, so callers
+    /// need no retry loop of their own. The packet is only queued; it still goes out on
+    /// the next [`sync`](Self::sync), as with `send`.
+    ///
+    /// # Errors
+    /// Returns `Error::Timeout` if the ring is still full once `timeout` passes, and
+    /// `Error::Io` if syncing or polling fails. A ring not obtained from a `Netmap` has
+    /// no descriptor to wait on and reports `Error::InsufficientSpace` right away.
+    /// Otherwise the same as [`send`](Self::send).
+    pub fn send_blocking(&mut self, buf: &[u8], timeout: Option<Duration>) -> Result<(), Error> {
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+        let slots_needed = buf.len().div_ceil(self.max_payload_size()).max(1);
+        loop {
+            match self.send(buf) {
+                Err(Error::InsufficientSpace) => {}
+                result => return result,
+            }
+            let Some(fd) = self.fd else {
+                return Err(Error::InsufficientSpace);
+            };
+
+            self.try_sync()?;
+            if self.inner.space() >= slots_needed {
+                continue;
+            }
+
+            let Some(timeout_ms) = poll_timeout_ms(deadline) else {
+                return Err(Error::Timeout);
+            };
+            let mut pfd = libc::pollfd {
+                fd,
+                events: libc::POLLOUT,
+                revents: 0,
+            };
+            if unsafe { libc::poll(&mut pfd, 1, timeout_ms) } < 0 {
+                let err = io::Error::last_os_error();
+                if err.kind() != io::ErrorKind::Interrupted {
+                    return Err(Error::Io(err));
+                }
+            }
+        }
+    }
+
+    /// send a single packet and return the index of the slot it was placed in
+    ///
+    /// Behaves exactly like [`send`](Self::send). For a fragmented packet the index is
+    /// that of the first fragment; the rest follow in consecutive slots. The packet has
+    /// been transmitted once a later [`sync`](Self::sync) moves [`tail`](Ring::tail) past
+    /// its slot, which lets callers match sends to completions without extra bookkeeping.
+    ///
+    /// # Errors
+    /// Same as [`send`](Self::send).
+    pub fn send_at(&mut self, buf: &[u8]) -> Result<u32, Error> {
+        let slot = self.inner.cur();
+        self.send(buf)?;
+        Ok(slot)
+    }
+
+    /// queue `buf` as a chain of `NS_MOREFRAG` slots, each filled up to the buffer size
+    fn send_fragmented(&mut self, buf: &[u8]) -> Result<(), Error> {
+        let buf_size = self.max_payload_size();
+        let frags = buf.len().div_ceil(buf_size);
+        if frags >= self.inner.num_slots() {
             return Err(Error::PacketTooLarge(buf.len()));
         }
+        if frags > self.inner.space() {
+            return Err(Error::InsufficientSpace);
+        }
 
         unsafe {
-            let ring = self.0.ring;
-            let cur = (*ring).cur;
-            let slot = (*ring).slot.add(cur as usize);
+            let ring = self.inner.ring;
+            let num_slots = (*ring).num_slots;
+            let mut cur = (*ring).cur;
+            for (i, chunk) in buf.chunks(buf_size).enumerate() {
+                let slot = (*ring).slot.add(cur as usize);
+                ptr::copy_nonoverlapping(chunk.as_ptr(), (*slot).buf as *mut u8, chunk.len());
+                (*slot).len = chunk.len() as u16;
+                if i + 1 < frags {
+                    (*slot).flags |= ffi::NS_MOREFRAG as u16;
+                } else {
+                    (*slot).flags &= !(ffi::NS_MOREFRAG as u16);
+                }
+                cur = (cur + 1) % num_slots;
+            }
+            self.publish(cur);
+        }
+        Ok(())
+    }
+
+    /// claim the slot at `cur`, let `fill` write exactly `len` bytes into it, then advance the ring
+    fn send_with(&mut self, len: usize, fill: impl FnOnce(&mut [u8])) -> Result<(), Error> {
+        fill(self.reserve_slot(len)?);
+        self.commit_one();
+        Ok(())
+    }
+
+    /// borrow the next free slot's buffer to build a `len`-byte packet in place
+    ///
+    /// The slot's length is set to `len` up front; write the packet into the returned
+    /// slice, then call [`commit_one`](Self::commit_one) to queue it. The slice borrows
+    /// the ring mutably, so it must be dropped before `commit_one` (or anything else on
+    /// the ring) can be called, and the packet cannot be modified after it is committed.
+    ///
+    /// Until it is committed the slot stays owned by userspace and invisible to the
+    /// kernel. Calling `reserve_slot` again first hands back the same slot, resized to the
+    /// new `len`; the bytes already written are left in place.
+    ///
+    /// # Errors
+    /// Returns `Error::PacketTooLarge` if `len` exceeds the buffer size, or
+    /// `Error::InsufficientSpace` if the ring has no free slot.
+    pub fn reserve_slot(&mut self, len: usize) -> Result<&mut [u8], Error> {
+        if len > self.max_payload_size() {
+            return Err(Error::PacketTooLarge(len));
+        }
+        // cur == tail: every free slot is already filled and waiting for a sync
+        if self.inner.space() == 0 {
+            return Err(Error::InsufficientSpace);
+        }
+
+        self.slot_reserved = true;
+        unsafe {
+            let ring = self.inner.ring;
+            let slot = (*ring).slot.add((*ring).cur as usize);
+            (*slot).len = len as u16;
+            // a single-slot packet, even if this slot last carried a fragment
+            (*slot).flags &= !(ffi::NS_MOREFRAG as u16);
+            Ok(slice::from_raw_parts_mut((*slot).buf as *mut u8, len))
+        }
+    }
+
+    /// queue the packet built through [`reserve_slot`](Self::reserve_slot)
+    ///
+    /// This advances `head`/`cur` past the slot; like [`send`](Self::send) it does not
+    /// sync. Does nothing if no slot is reserved.
+    pub fn commit_one(&mut self) {
+        if !mem::take(&mut self.slot_reserved) {
+            return;
+        }
+        unsafe {
+            let ring = self.inner.ring;
+            self.publish(((*ring).cur + 1) % (*ring).num_slots);
+        }
+    }
+
+    /// send a single packet followed by a little-endian CRC32 of its contents
+    ///
+    /// The receiver should use [`RxRing::recv_checked`] to validate and strip the trailer.
+    /// The trailer adds [`CHECKSUM_LEN`] bytes, which count against [`max_payload_size`](Self::max_payload_size).
+    #[cfg(feature = "checksum")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "checksum")))]
+    pub fn send_checked(&mut self, buf: &[u8]) -> Result<(), Error> {
+        let crc = crc32fast::hash(buf).to_le_bytes();
+        self.send_with(buf.len() + CHECKSUM_LEN, |slot| {
+            let (payload, trailer) = slot.split_at_mut(buf.len());
+            payload.copy_from_slice(buf);
+            trailer.copy_from_slice(&crc);
+        })
+    }
+
+    /// send a single packet only if a free slot is already visible, without syncing
+    ///
+    /// This never issues a `txsync`, so the packet is not handed to the NIC until the
+    /// caller syncs the ring on its own schedule. Freed slots only become visible after
+    /// such a sync, so a ring that looks full here may have room after the next one.
+    ///
+    /// # Errors
+    /// Returns `Error::WouldBlock` if no free slot is currently visible, or
+    /// `Error::PacketTooLarge` if `buf` does not fit in a slot.
+    pub fn try_send(&mut self, buf: &[u8]) -> Result<(), Error> {
+        if self.inner.space() == 0 {
+            return Err(Error::WouldBlock);
+        }
+        self.send(buf)
+    }
+
+    /// send a single packet, zero-padding it up to the Ethernet minimum frame size
+    ///
+    /// Payloads of at least [`MIN_FRAME_LEN`] bytes are sent unchanged.
+    pub fn send_padded(&mut self, buf: &[u8]) -> Result<(), Error> {
+        if buf.len() >= MIN_FRAME_LEN {
+            return self.send(buf);
+        }
+
+        let mut padded = [0u8; MIN_FRAME_LEN];
+        padded[..buf.len()].copy_from_slice(buf);
+        self.send(&padded)
+    }
 
-            // copy data to the slot
-            ptr::copy_nonoverlapping(buf.as_ptr(), (*slot).buf as *mut u8, buf.len());
+    /// send a complete Ethernet frame into the host stack through a host TX ring (`^` interface)
+    ///
+    /// Host rings expect full L2 frames, and some host-stack configurations silently drop
+    /// UDP datagrams without a checksum. So the frame is first checked to be complete (an
+    /// Ethernet header, plus the whole IP packet for IPv4), and an IPv4/UDP frame whose
+    /// checksum is zero has one computed with [`packet::fill_udp_checksum`] in the slot.
+    /// `packet` itself is not modified.
+    ///
+    /// # Errors
+    /// Returns `Error::ExceedsHostMtu` if the frame's payload is larger than the MTU of
+    /// the interface behind the host ring (when `build()` could read it),
+    /// `Error::MalformedPacket` if `packet` is not a complete frame, or
+    /// `Error::PacketTooLarge` if it does not fit in a slot.
+    pub fn send_to_host(&mut self, packet: &[u8]) -> Result<(), Error> {
+        if let Some(mtu) = self.host_mtu {
+            if packet.len().saturating_sub(ETH_HEADER_LEN) > mtu {
+                return Err(Error::ExceedsHostMtu(packet.len()));
+            }
+        }
+        let missing_udp_checksum = packet::check_host_frame(packet)?;
+        self.send_with(packet.len(), |slot| {
+            slot.copy_from_slice(packet);
+            if let Some(lengths) = missing_udp_checksum {
+                packet::store_udp_checksum(slot, lengths);
+            }
+        })
+    }
 
-            (*slot).len = buf.len() as u16;
-            (*ring).head = (*ring).cur.wrapping_add(1);
-            (*ring).cur = (*ring).head;
+    /// send a frame into the host stack, asking for it to carry `timestamp` as its arrival time
+    ///
+    /// Intended for replaying captures into the host stack with their original timing
+    /// metadata. Honouring the timestamp needs a netmap kernel module whose host-ring
+    /// (`^`) injection path hands a user-supplied time to the socket layer, so that
+    /// receivers using `SO_TIMESTAMP` see it. No netmap release currently does this: the
+    /// per-slot `ptr` field such an extension would use is already claimed for payload
+    /// offsets, and the host stack stamps injected packets when it processes them. Until
+    /// such support exists this falls back to a plain [`send_to_host`](Self::send_to_host)
+    /// and `timestamp` is ignored; the packet is still delivered.
+    ///
+    /// # Errors
+    /// Same as [`send_to_host`](Self::send_to_host).
+    pub fn send_host_timed(&mut self, packet: &[u8], timestamp: SystemTime) -> Result<(), Error> {
+        let _ = timestamp;
+        self.send_to_host(packet)
+    }
 
-            Ok(())
+    /// send an Ethernet/IPv4/TCP frame whose payload may exceed `mss`, split into `mss`-sized TCP segments
+    ///
+    /// Netmap slots carry no segmentation-offload flags for hardware rings, so the frame is
+    /// always segmented in software with [`packet::segment_tcp`], one segment per slot. Either
+    /// every segment is queued or none is. The ring is not synced.
+    ///
+    /// # Errors
+    /// Returns `Error::MalformedPacket` if `buf` is not a TCP/IPv4 frame, `Error::InsufficientSpace`
+    /// if the ring cannot take all segments, or `Error::PacketTooLarge` if a segment exceeds a slot.
+    pub fn send_tso(&mut self, buf: &[u8], mss: u16) -> Result<(), Error> {
+        let segments = packet::segment_tcp(buf, mss)?;
+        if self.inner.space() < segments.len() {
+            return Err(Error::InsufficientSpace);
+        }
+        if let Some(seg) = segments.iter().find(|seg| seg.len() > self.max_payload_size()) {
+            return Err(Error::PacketTooLarge(seg.len()));
         }
+        for seg in &segments {
+            self.send(seg)?;
+        }
+        Ok(())
+    }
+
+    /// get the raw `netmap_ring` pointer backing this ring
+    ///
+    /// This is an escape hatch for advanced use, e.g. reading slot or ring fields this
+    /// crate does not wrap.
+    ///
+    /// # Safety
+    /// The pointer is only valid while the owning `Netmap` is alive. Writing through it
+    /// (in particular to `head`, `cur` or slot contents) can desynchronize this handle's
+    /// view of the ring or hand the kernel inconsistent state.
+    pub unsafe fn as_raw_ring(&self) -> *mut ffi::netmap_ring {
+        self.inner.ring
     }
 
     /// get the maximum payload size for this ring
     pub fn max_payload_size(&self) -> usize {
-        unsafe { (*self.0.ring).nr_buf_size as usize }
+        unsafe { (*self.inner.ring).nr_buf_size as usize }
+    }
+
+    /// borrow this ring through a guard that syncs it when dropped
+    ///
+    /// Send through the guard (it derefs to the ring); the queued packets are flushed
+    /// to the NIC on every exit path, including early returns and `?`.
+    ///
+    /// ```no_run
+    /// # use netmap_rs::{Error, TxRing};
+    /// fn send_all(tx: &mut TxRing<'_>, packets: &[&[u8]]) -> Result<(), Error> {
+    ///     let mut tx = tx.sync_on_drop();
+    ///     for packet in packets {
+    ///         tx.send(packet)?; // an error still syncs what was already queued
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn sync_on_drop(&mut self) -> SyncGuard<'_, 'a> {
+        SyncGuard { ring: self }
     }
 
     /// reserve space for batch sending
+    ///
+    /// The batch starts at `head`. A netmap ring keeps one slot empty to tell full from
+    /// empty, so at most `num_slots - 1` packets can be queued at once; the free ones are
+    /// those between `head` and `tail`.
+    ///
+    /// # Errors
+    /// Returns `Error::InsufficientSpace` if fewer than `count` slots are free.
     pub fn reserve_batch(&mut self, count: usize) -> Result<BatchReservation<'a>, Error> {
-        unsafe {
-            let ring_ptr = self.0.ring;
-            let head = (*ring_ptr).head;
-            let tail = (*ring_ptr).tail;
-            let num_slots = (*ring_ptr).num_slots as u32;
-
-            // Calculate available space. Netmap rings are full when head == tail + 1 (modulo num_slots)
-            // So, available space is num_slots - 1 - current_used_slots
-            // current_used_slots = (head - tail + num_slots) % num_slots
-            let current_used_slots = (head.wrapping_sub(tail).wrapping_add(num_slots)) % num_slots;
-            let available_slots = (num_slots - 1).saturating_sub(current_used_slots) as usize;
+        if count > self.inner.space() {
+            return Err(Error::InsufficientSpace);
+        }
 
-            if available_slots < count {
-                return Err(Error::InsufficientSpace);
-            }
+        let ring = self.inner.ring;
+        let start = unsafe { (*ring).head };
+        // every packet in the batch is a single slot, whatever the slot carried before
+        for i in 0..count as u32 {
+            unsafe { (*(*ring).slot.add(((start + i) % (*ring).num_slots) as usize)).flags &= !(ffi::NS_MOREFRAG as u16) };
         }
 
         Ok(BatchReservation {
-            ring: self.0.ring,
-            start: unsafe { (*self.0.ring).head },
+            ring,
+            start,
             count,
+            counters: self.inner.counters.clone(),
+            mirror: self.mirror_ring().map(ptr::from_ref),
             _marker: PhantomData,
         })
     }
+
+    /// reserve `count` slots to build packets in one after another, committed together
+    ///
+    /// Unlike [`reserve_batch`](Self::reserve_batch), no length is needed up front: each
+    /// [`FrameMut`] from [`TxBatch::frames`] exposes its slot's whole buffer and a length
+    /// that starts at 0 and is set as the packet is built. The frames handed out are
+    /// queued when the batch is committed or dropped; reserved slots never handed out
+    /// stay free. The ring is not synced.
+    ///
+    /// # Errors
+    /// Returns `Error::InsufficientSpace` if fewer than `count` slots are free.
+    pub fn batch(&mut self, count: usize) -> Result<TxBatch<'_, 'a>, Error> {
+        let start = self.reserve_batch(count)?.start;
+        Ok(TxBatch {
+            ring: self,
+            start,
+            count,
+            taken: 0,
+        })
+    }
+
+    /// send as many of `packets` as there are free slots for, in order
+    ///
+    /// Each packet is copied into its own slot through a [`reserve_batch`](Self::reserve_batch)
+    /// reservation, which is committed in one step. Returns how many packets were queued,
+    /// which is less than `packets.len()` if the ring fills up (0 on a full ring); the rest
+    /// can be retried after a [`sync`](Self::sync). The ring is not synced.
+    ///
+    /// # Errors
+    /// Returns `Error::PacketTooLarge` if any packet exceeds
+    /// [`max_payload_size`](Self::max_payload_size), in which case nothing is queued.
+    pub fn send_batch(&mut self, packets: &[&[u8]]) -> Result<usize, Error> {
+        if let Some(packet) = packets.iter().find(|packet| packet.len() > self.max_payload_size()) {
+            return Err(Error::PacketTooLarge(packet.len()));
+        }
+
+        let count = packets.len().min(self.inner.space());
+        if count == 0 {
+            return Ok(0);
+        }
+        let mut reservation = self.reserve_batch(count)?;
+        for (i, packet) in packets[..count].iter().enumerate() {
+            reservation.packet(i, packet.len())?.copy_from_slice(packet);
+        }
+        reservation.commit();
+        Ok(count)
+    }
+}
+
+impl TxRingApi for TxRing<'_> {
+    fn send(&mut self, buf: &[u8]) -> Result<(), Error> {
+        TxRing::send(self, buf)
+    }
+
+    fn max_payload_size(&self) -> usize {
+        TxRing::max_payload_size(self)
+    }
+
+    fn sync(&mut self) {
+        TxRing::sync(self)
+    }
+}
+
+impl<'a> Deref for TxRing<'a> {
+    type Target = Ring<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+/// a tx ring borrow that syncs the ring when dropped, created by [`TxRing::sync_on_drop`]
+pub struct SyncGuard<'r, 'a> {
+    ring: &'r mut TxRing<'a>,
+}
+
+impl<'a> Deref for SyncGuard<'_, 'a> {
+    type Target = TxRing<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        self.ring
+    }
+}
+
+impl DerefMut for SyncGuard<'_, '_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.ring
+    }
+}
+
+impl Drop for SyncGuard<'_, '_> {
+    fn drop(&mut self) {
+        self.ring.sync();
+    }
+}
+
+/// a batch of received frames borrowed from an rx ring, created by [`RxRing::recv_batch_guard`]
+///
+/// Dropping the guard releases the frames' slots back to the ring.
+pub struct RxBatchGuard<'r, 'a> {
+    ring: *mut ffi::netmap_ring,
+    counters: Option<Arc<RingCounters>>,
+    frames: Vec<Frame<'r>>,
+    // Slots to release, including those of frames the MAC filter dropped.
+    consumed: u32,
+    _marker: PhantomData<&'r mut RxRing<'a>>,
+}
+
+impl<'r> Deref for RxBatchGuard<'r, '_> {
+    type Target = [Frame<'r>];
+
+    fn deref(&self) -> &Self::Target {
+        &self.frames
+    }
+}
+
+impl Drop for RxBatchGuard<'_, '_> {
+    fn drop(&mut self) {
+        unsafe {
+            let num_slots = (*self.ring).num_slots;
+            let head = ((*self.ring).head + self.consumed) % num_slots;
+            release(self.ring, head, self.counters.as_deref());
+        }
+    }
+}
+
+/// an iterator over received frames borrowed from an rx ring, created by [`RxRing::recv_batch_borrowed`]
+///
+/// Frames are read lazily, without allocating, and point straight into the ring buffers.
+/// Dropping the iterator releases the slots of the frames it yielded; any it did not get
+/// to stay in the ring for the next receive.
+pub struct BatchRecv<'r, 'a> {
+    ring: &'r mut RxRing<'a>,
+    start: u32,
+    count: u32,
+    taken: u32,
+}
+
+impl<'r> Iterator for BatchRecv<'r, '_> {
+    type Item = Frame<'r>;
+
+    fn next(&mut self) -> Option<Frame<'r>> {
+        if self.taken == self.count {
+            return None;
+        }
+        unsafe {
+            let ring = self.ring.inner.ring;
+            let slot = (*ring).slot.add(((self.start + self.taken) % (*ring).num_slots) as usize);
+            // The slot stays owned by userspace until this iterator drops, which ends `'r`.
+            let buf: &'r [u8] = slice::from_raw_parts((*slot).buf as *const u8, (*slot).len as usize);
+            self.taken += 1;
+            if let Some(trace) = &mut self.ring.trace {
+                trace.record(buf);
+            }
+            Some(Frame::new(buf))
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let left = (self.count - self.taken) as usize;
+        (left, Some(left))
+    }
+}
+
+impl ExactSizeIterator for BatchRecv<'_, '_> {}
+
+impl Drop for BatchRecv<'_, '_> {
+    fn drop(&mut self) {
+        unsafe {
+            let ring = self.ring.inner.ring;
+            self.ring.inner.release((self.start + self.taken) % (*ring).num_slots);
+        }
+    }
 }
 
 /// a batch reservation for tx packets
@@ -121,6 +1065,8 @@ pub struct BatchReservation<'a> {
     ring: *mut ffi::netmap_ring,
     start: u32,
     count: usize,
+    counters: Option<Arc<RingCounters>>,
+    mirror: Option<*const TxRing<'static>>, // Handle of the ring the committed packets are copied to
     _marker: PhantomData<&'a mut ffi::netmap_ring>,
 }
 
@@ -139,58 +1085,1759 @@ impl<'a> BatchReservation<'a> {
         }
     }
 
-    /// commit the batch (make packets visible to NIC)
-    pub fn commit(self) {
+    /// get the whole buffer of packet `index` in the batch, without setting its length
+    ///
+    /// Use this with [`commit_lens`](Self::commit_lens), which sets every length in one
+    /// pass at commit time. The slice is the slot's full `nr_buf_size` bytes.
+    pub fn buffer(&mut self, index: usize) -> Result<&mut [u8], Error> {
+        if index >= self.count {
+            return Err(Error::InvalidRingIndex(index));
+        }
+
         unsafe {
-            (*self.ring).head = self.start + self.count as u32;
-            (*self.ring).cur = (*self.ring).head;
+            let slot_idx = (self.start + index as u32) % (*self.ring).num_slots;
+            let slot = (*self.ring).slot.add(slot_idx as usize);
+            Ok(slice::from_raw_parts_mut((*slot).buf as *mut u8, (*self.ring).nr_buf_size as usize))
         }
     }
-}
 
-impl<'a> RxRing<'a> {
-    /// create a new rx ring
-    pub(crate) fn new(ring: *mut ffi::netmap_ring, index: usize) -> Self {
-        Self(Ring::new(ring, index))
-    }
+    /// commit the first `lens.len()` packets of the batch, packet `i` being `lens[i]` bytes long
+    ///
+    /// All slot lengths are written first, then a single release fence orders them (and
+    /// the packet contents) before one store each to `head` and `cur`, so a large batch
+    /// is published with one barrier instead of one per packet. Reserved packets past
+    /// `lens.len()` are left unsent.
+    ///
+    /// # Errors
+    /// Returns `Error::InvalidRingIndex` if `lens` is longer than the reservation and
+    /// `Error::PacketTooLarge` if a length exceeds the buffer size; nothing is committed
+    /// in either case.
+    pub fn commit_lens(self, lens: &[usize]) -> Result<(), Error> {
+        if lens.len() > self.count {
+            return Err(Error::InvalidRingIndex(self.count));
+        }
 
-    /// receive single packet
-    pub fn recv(&mut self) -> Option<Frame> {
         unsafe {
-            let ring = self.0.ring;
-            if (*ring).head == (*ring).tail {
+            let ring = self.ring;
+            let num_slots = (*ring).num_slots;
+            let buf_size = (*ring).nr_buf_size as usize;
+            if let Some(&len) = lens.iter().find(|&&len| len > buf_size) {
+                return Err(Error::PacketTooLarge(len));
+            }
+
+            for (i, &len) in lens.iter().enumerate() {
+                let slot_idx = (self.start + i as u32) % num_slots;
+                (*(*ring).slot.add(slot_idx as usize)).len = len as u16;
+            }
+
+            self.publish((self.start + lens.len() as u32) % num_slots);
+        }
+        Ok(())
+    }
+
+    /// commit the batch (make packets visible to NIC)
+    pub fn commit(self) {
+        unsafe { self.publish((self.start + self.count as u32) % (*self.ring).num_slots) }
+    }
+
+    /// hand the slots up to `head` to the kernel and queue copies of them on the mirror ring
+    unsafe fn publish(&self, head: u32) {
+        release(self.ring, head, self.counters.as_deref());
+        // Safety: the mirror handle was taken from the ring this reservation borrows, see
+        // `TxRing::mirror_ring`.
+        if let Some(mirror) = self.mirror {
+            mirror_slots(self.ring, &*mirror, self.start, head);
+        }
+    }
+}
+
+/// tx slots reserved by [`TxRing::batch`], filled through [`FrameMut`] writers
+///
+/// Committing, explicitly or on drop, queues every frame handed out so far in order.
+pub struct TxBatch<'r, 'a> {
+    ring: &'r mut TxRing<'a>,
+    start: u32,
+    count: usize,
+    taken: usize, // Frames handed out by `frames`, all of which are committed
+}
+
+impl TxBatch<'_, '_> {
+    /// writers for the batch's remaining slots, in ring order
+    ///
+    /// Each frame handed out is committed with the batch, even if left empty, so take only
+    /// as many as there are packets to send. Calling this again resumes after the frames
+    /// already taken.
+    pub fn frames(&mut self) -> impl Iterator<Item = FrameMut<'_>> + '_ {
+        let ring = self.ring.inner.ring;
+        let start = self.start;
+        let count = self.count;
+        let taken = &mut self.taken;
+        std::iter::from_fn(move || {
+            if *taken == count {
                 return None;
             }
+            unsafe {
+                let slot = (*ring).slot.add(((start + *taken as u32) % (*ring).num_slots) as usize);
+                *taken += 1;
+                (*slot).len = 0;
+                Some(FrameMut {
+                    slot,
+                    buf: slice::from_raw_parts_mut((*slot).buf as *mut u8, (*ring).nr_buf_size as usize),
+                })
+            }
+        })
+    }
 
-            let slot_idx = (*ring).tail % (*ring).num_slots;
-            let slot = (*ring).slot.add(slot_idx as usize);
-            let buf = slice::from_raw_parts((*slot).buf as *const u8, (*slot).len as usize);
+    /// commit the frames handed out so far; the same as dropping the batch
+    pub fn commit(self) {}
+}
 
-            (*ring).head = (*ring).tail.wrapping_add(1);
-            (*ring).tail = (*ring).head;
+impl Drop for TxBatch<'_, '_> {
+    fn drop(&mut self) {
+        let ring = self.ring.inner.ring;
+        unsafe { self.ring.publish((self.start + self.taken as u32) % (*ring).num_slots) };
+    }
+}
 
-            Some(Frame::new(buf))
+/// a writer for one tx slot of a [`TxBatch`]
+///
+/// The slot's whole buffer is writable; [`len`](Self::len) is how much of it is sent.
+pub struct FrameMut<'b> {
+    slot: *mut ffi::netmap_slot,
+    buf: &'b mut [u8],
+}
+
+impl FrameMut<'_> {
+    /// the slot's whole buffer, whatever the current length
+    pub fn buffer_mut(&mut self) -> &mut [u8] {
+        self.buf
+    }
+
+    /// the packet built so far, the first [`len`](Self::len) bytes of the buffer
+    pub fn payload(&self) -> &[u8] {
+        &self.buf[..self.len()]
+    }
+
+    /// number of bytes that will be sent
+    pub fn len(&self) -> usize {
+        unsafe { (*self.slot).len as usize }
+    }
+
+    /// true if nothing has been written yet
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// set the number of bytes to send, after writing them through [`buffer_mut`](Self::buffer_mut)
+    ///
+    /// # Errors
+    /// Returns `Error::PacketTooLarge` if `len` exceeds the buffer size.
+    pub fn set_len(&mut self, len: usize) -> Result<(), Error> {
+        if len > self.buf.len() {
+            return Err(Error::PacketTooLarge(len));
+        }
+        unsafe { (*self.slot).len = len as u16 };
+        Ok(())
+    }
+
+    /// append `data` to the packet
+    ///
+    /// # Errors
+    /// Returns `Error::PacketTooLarge` if the packet would no longer fit in the buffer;
+    /// nothing is appended then.
+    pub fn extend_from_slice(&mut self, data: &[u8]) -> Result<(), Error> {
+        let len = self.len();
+        self.set_len(len + data.len())?;
+        self.buf[len..len + data.len()].copy_from_slice(data);
+        Ok(())
+    }
+}
+
+impl<'a> RxRing<'a> {
+    /// create a new rx ring
+    pub(crate) fn new(ring: *mut ffi::netmap_ring, index: usize) -> Self {
+        Self {
+            inner: Ring::new(ring, index),
+            trace: None,
+            fd: None,
+            watcher: None,
+            binder: None,
+            dst_mac_filter: None,
+            pass_multicast: false,
+            auto_batch: AutoBatch::new(),
+            pipe: false,
         }
     }
 
-    /// receive a  batch of packets
-    pub fn recv_batch(&mut self, batch: &mut [Frame]) -> usize {
+    /// mark this ring as the RX side of a pipe endpoint, for [`peer_closed`](Self::peer_closed)
+    pub(crate) fn with_pipe(mut self, pipe: bool) -> Self {
+        self.pipe = pipe;
+        self
+    }
+
+    /// release consumed slots to the kernel and pick up newly received packets (`rxsync`)
+    ///
+    /// Failures are ignored; use [`try_sync`](Self::try_sync) to observe them.
+    pub fn sync(&self) {
+        let _ = self.try_sync();
+    }
+
+    /// like [`sync`](Self::sync), but reports a failed `rxsync`
+    ///
+    /// # Errors
+    /// Returns `Error::Io` with the OS error if the sync fails, e.g. because the
+    /// descriptor was closed or revoked after a link flap.
+    pub fn try_sync(&self) -> Result<(), Error> {
+        sync_result(unsafe { ffi::nm_rxsync(self.inner.ring, 0) })
+    }
+
+    /// attach the descriptor of the `Netmap` this ring belongs to, for [`poll_recv`](Self::poll_recv)
+    pub(crate) fn with_fd(mut self, fd: RawFd) -> Self {
+        self.fd = Some(fd);
+        self
+    }
+
+    /// let [`poll_recv`](Self::poll_recv) wait on a descriptor bound to this ring alone, opened through `binder`
+    pub(crate) fn with_binder(mut self, binder: Option<Arc<RingBinder>>) -> Self {
+        self.binder = binder;
+        self
+    }
+
+    /// count packets consumed from this ring in `counters`, shared with the owning `Netmap`
+    pub(crate) fn with_counters(mut self, counters: Arc<RingCounters>) -> Self {
+        self.inner.counters = Some(counters);
+        self
+    }
+
+    /// start keeping copies of the last `capacity` received packets, with their arrival time
+    ///
+    /// Replaces any trace that was already enabled.
+    pub fn enable_trace(&mut self, capacity: usize) {
+        self.trace = Some(PacketTrace::new(capacity));
+    }
+
+    /// stop tracing and discard any retained packets
+    pub fn disable_trace(&mut self) {
+        self.trace = None;
+    }
+
+    /// only deliver frames addressed to `mac`
+    ///
+    /// Frames with another destination MAC, or too short to carry one, are released
+    /// without being returned (or traced) and counted as dropped. `None` removes the
+    /// filter. Broadcast and multicast frames are dropped too unless
+    /// [`set_pass_multicast`](Self::set_pass_multicast) is enabled.
+    ///
+    /// The filter applies to [`recv`](Self::recv) and the calls built on it,
+    /// [`poll_recv`](Self::poll_recv), [`recv_batch`](Self::recv_batch) and
+    /// [`recv_batch_guard`](Self::recv_batch_guard). [`recv_batch_borrowed`](Self::recv_batch_borrowed)
+    /// yields every slot unfiltered, [`forward_filtered`](Self::forward_filtered) applies only
+    /// its own `keep` predicate, and [`pass_through`](Self::pass_through) marks the next slot
+    /// whatever its destination.
+    pub fn set_dst_mac_filter(&mut self, mac: Option<[u8; 6]>) {
+        self.dst_mac_filter = mac;
+    }
+
+    /// let broadcast and multicast frames through the destination MAC filter
+    ///
+    /// Has no effect while no filter is set. Off by default.
+    pub fn set_pass_multicast(&mut self, pass: bool) {
+        self.pass_multicast = pass;
+    }
+
+    /// whether `frame` passes the destination MAC filter
+    fn accepts(&self, frame: &[u8]) -> bool {
+        let Some(mac) = self.dst_mac_filter else {
+            return true;
+        };
+        match frame.get(..6) {
+            // The I/G bit marks group (broadcast and multicast) addresses.
+            Some(dst) => dst == mac || (self.pass_multicast && dst[0] & 0x01 != 0),
+            None => false,
+        }
+    }
+
+    /// copy out the traced packets, oldest first
+    ///
+    /// Returns an empty vector if tracing is not enabled.
+    pub fn dump_trace(&self) -> Vec<(Instant, Frame<'static>)> {
+        self.trace.as_ref().map(PacketTrace::dump).unwrap_or_default()
+    }
+
+    /// get the raw `netmap_ring` pointer backing this ring
+    ///
+    /// This is an escape hatch for advanced use, e.g. reading slot or ring fields this
+    /// crate does not wrap.
+    ///
+    /// # Safety
+    /// The pointer is only valid while the owning `Netmap` is alive. Writing through it
+    /// (in particular to `head`, `cur` or slot contents) can desynchronize this handle's
+    /// view of the ring or hand the kernel inconsistent state.
+    pub unsafe fn as_raw_ring(&self) -> *mut ffi::netmap_ring {
+        self.inner.ring
+    }
+
+    /// number of received packets ready to read, without syncing or consuming anything
+    ///
+    /// This is `(tail - head)` modulo the ring size, i.e. the packets exposed by the last
+    /// sync that have not been read yet. On a pipe it leaves out the close sentinel
+    /// described at [`peer_closed`](Self::peer_closed).
+    pub fn available(&self) -> usize {
+        let (visible, closed) = self.visible_slots();
+        visible - usize::from(closed)
+    }
+
+    /// whether the other endpoint of this pipe has been closed
+    ///
+    /// A pipe endpoint whose `Netmap` is dropped queues a zero-length packet on each of
+    /// its TX rings as a close sentinel. The sentinel is never handed out as a packet:
+    /// reads stop just before it, so everything the peer sent before closing can still
+    /// be received, and this returns `true` from the sync that exposes it onwards. A
+    /// receive loop can therefore drain the ring and stop once `recv` returns `None`
+    /// and this is `true`. Netmap itself gives the surviving endpoint no sign of the
+    /// close, which is why the sentinel is sent in-band.
+    ///
+    /// Zero-length packets are reserved for the sentinel on pipes. The sentinel is
+    /// best-effort: a peer that exits without dropping its `Netmap`, or whose TX ring is
+    /// full when it closes, sends none. Always `false` for rings that are not pipes.
+    pub fn peer_closed(&self) -> bool {
+        self.visible_slots().1
+    }
+
+    /// slots between `head` and `tail`, and whether the last of them is a pipe close sentinel
+    fn visible_slots(&self) -> (usize, bool) {
+        unsafe {
+            let ring = self.inner.ring;
+            let num_slots = (*ring).num_slots;
+            let tail = load_tail(ring) % num_slots;
+            let visible = ((tail + num_slots - (*ring).head % num_slots) % num_slots) as usize;
+            let last = ((tail + num_slots - 1) % num_slots) as usize;
+            let closed = self.pipe && visible > 0 && (*(*ring).slot.add(last)).len == 0;
+            (visible, closed)
+        }
+    }
+
+    /// the ring's `flags` field, e.g. `NR_FORWARD` on a ring in transparent mode
+    pub fn ring_flags(&self) -> u32 {
+        unsafe { ptr::read_volatile(ptr::addr_of!((*self.inner.ring).flags)) as u32 }
+    }
+
+    /// flags of the slot holding the next packet, or `None` if no packet is available
+    ///
+    /// This is for inspecting a packet before deciding what to do with it, e.g. checking
+    /// `NS_FORWARD` to see whether it is marked to be passed on to the other side in
+    /// transparent mode (see [`pass_through`](Self::pass_through)). Nothing is consumed.
+    pub fn next_slot_flags(&self) -> Option<u16> {
+        if self.available() == 0 {
+            return None;
+        }
         unsafe {
-            let ring = self.0.ring;
-            let avail = ((*ring).head - (*ring).tail) as usize;
-            let count = avail.min(batch.len());
+            let ring = self.inner.ring;
+            let head = (*ring).head % (*ring).num_slots;
+            Some((*(*ring).slot.add(head as usize)).flags)
+        }
+    }
+
+    /// receive single packet
+    ///
+    /// Like [`try_recv`](Self::try_recv), this only looks at packets already visible
+    /// in the ring and never syncs with the kernel.
+    ///
+    /// A jumbo frame spread over a chain of `NS_MOREFRAG` slots is gathered into one owned
+    /// frame. If only part of the chain has arrived, nothing is consumed and `None` is
+    /// returned until a later sync delivers the rest.
+    pub fn recv(&mut self) -> Option<Frame> {
+        unsafe {
+            let ring = self.inner.ring;
+            let num_slots = (*ring).num_slots;
+            loop {
+                // Userspace owns `cur..tail`; `tail` itself belongs to the kernel.
+                let cur = (*ring).cur % num_slots;
+                let (frame, frags) = read_packet(ring, cur, self.available())?;
+
+                let next = (cur + frags as u32) % num_slots;
+                self.inner.release(next);
+
+                if !self.accepts(frame.payload()) {
+                    self.inner.count_dropped();
+                    continue;
+                }
+                if let Some(trace) = &mut self.trace {
+                    trace.record(frame.payload());
+                }
+
+                return Some(frame);
+            }
+        }
+    }
+
+    /// receive a single packet into a buffer from `pool`
+    ///
+    /// Like [`recv`](Self::recv), but the packet is copied out of the ring into a
+    /// [`PooledFrame`], which, unlike a frame borrowing the slot, stays valid across
+    /// syncs and can be kept as long as needed. Its buffer returns to `pool` on drop, so a receive loop reuses a handful of
+    /// allocations rather than making one per packet. Never syncs.
+    pub fn recv_pooled(&mut self, pool: &FramePool) -> Option<PooledFrame> {
+        self.recv().map(|frame| frame.clone_into_pool(pool))
+    }
+
+    /// receive the next packet, waiting up to `timeout` for one to arrive
+    ///
+    /// Syncs the ring if it has nothing to read, then `poll(2)`s the descriptor until a
+    /// packet arrives, so it returns as soon as one does instead of on a fixed sleep
+    /// interval. Packets are filtered and traced as by [`recv`](Self::recv). Returns
+    /// `Ok(None)` once `timeout` passes without a packet.
+    ///
+    /// The descriptor is shared by every ring of the `Netmap`, so traffic on another ring
+    /// also ends a wait; the call then checks this ring again and keeps waiting.
+    ///
+    /// # Errors
+    /// Returns `Error::Io` if syncing or polling fails.
+    pub fn recv_timeout(&mut self, timeout: Duration) -> Result<Option<Frame<'_>>, Error> {
+        self.recv_until(Some(Instant::now() + timeout))
+    }
+
+    /// receive the next packet, waiting for one until `deadline` (`None` waits indefinitely)
+    ///
+    /// Returns `Ok(None)` once the deadline passes, or at once for a ring without a descriptor.
+    fn recv_until(&mut self, deadline: Option<Instant>) -> Result<Option<Frame<'_>>, Error> {
+        loop {
+            // Without a descriptor (only test rings lack one) there is nothing to sync or wait on.
+            if self.available() == 0 && self.fd.is_some() {
+                self.try_sync()?;
+            }
+            // Safety: the frame only outlives this iteration when it is returned, in which
+            // case nothing else touches `self`; the pointer just sidesteps the borrow
+            // checker's limits on returning a borrow conditionally from a loop.
+            let this: *mut Self = self;
+            if let Some(frame) = unsafe { (*this).recv() } {
+                return Ok(Some(frame));
+            }
+
+            let Some(fd) = self.fd else {
+                return Ok(None);
+            };
+            let Some(timeout_ms) = poll_timeout_ms(deadline) else {
+                return Ok(None);
+            };
+            let mut pfd = libc::pollfd {
+                fd,
+                events: libc::POLLIN,
+                revents: 0,
+            };
+            if unsafe { libc::poll(&mut pfd, 1, timeout_ms) } < 0 {
+                let err = io::Error::last_os_error();
+                if err.kind() != io::ErrorKind::Interrupted {
+                    return Err(Error::Io(err));
+                }
+            }
+        }
+    }
+
+    /// receive a single packet sent with [`TxRing::send_checked`], validating and stripping its CRC32 trailer
+    ///
+    /// Returns `Ok(None)` if no packet is available. Like [`recv`](Self::recv), this never syncs.
+    ///
+    /// # Errors
+    /// Returns `Error::ChecksumMismatch` if the packet is too short to carry a trailer or the
+    /// trailer does not match the payload. The packet is consumed either way.
+    #[cfg(feature = "checksum")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "checksum")))]
+    pub fn recv_checked(&mut self) -> Result<Option<Frame<'_>>, Error> {
+        let mut frame = match self.recv() {
+            Some(frame) => frame,
+            None => return Ok(None),
+        };
+        let payload_len = frame
+            .len()
+            .checked_sub(CHECKSUM_LEN)
+            .ok_or(Error::ChecksumMismatch)?;
+
+        let (payload, trailer) = frame.payload().split_at(payload_len);
+        let mut expected = [0u8; CHECKSUM_LEN];
+        expected.copy_from_slice(trailer);
+        if crc32fast::hash(payload) != u32::from_le_bytes(expected) {
+            return Err(Error::ChecksumMismatch);
+        }
+
+        frame.truncate(payload_len);
+        Ok(Some(frame))
+    }
+
+    /// receive a single packet already visible in the ring, without syncing
+    ///
+    /// This is a pure userspace check: it never issues an `rxsync`, so packets that
+    /// arrived since the last [`sync`](Ring::sync) stay invisible until the caller
+    /// syncs the ring again. Use it to drain a ring in a hot loop and sync on your
+    /// own schedule.
+    pub fn try_recv(&mut self) -> Option<Frame<'_>> {
+        self.recv()
+    }
+
+    /// receive a single packet by copying it into uninitialized memory
+    ///
+    /// Meant for buffer pools that hand out `MaybeUninit` storage, which would otherwise
+    /// have to be zeroed first. Like [`recv`](Self::recv), this never syncs. A packet
+    /// longer than `buf` is truncated to fit.
+    ///
+    /// Returns the number of bytes written; `buf[..n]` is initialized afterwards.
+    pub fn recv_into_uninit(&mut self, buf: &mut [MaybeUninit<u8>]) -> Option<usize> {
+        let frame = self.recv()?;
+        let len = frame.len().min(buf.len());
+        unsafe {
+            ptr::copy_nonoverlapping(frame.payload().as_ptr(), buf.as_mut_ptr() as *mut u8, len);
+        }
+        Some(len)
+    }
+
+    /// forward up to `max` received packets to `tx` without copying, dropping those `keep` rejects
+    ///
+    /// Kept packets are moved by swapping buffer indices between the RX and TX slots
+    /// (flagging both with `NS_BUF_CHANGED`); dropped packets are simply released. Neither
+    /// ring is synced, so call [`sync`](Ring::sync) on `tx` to transmit and on this ring to
+    /// pick up new packets. Forwarding stops early if `tx` runs out of free slots, leaving the
+    /// remaining packets in this ring.
+    ///
+    /// Returns `(forwarded, dropped)`.
+    pub fn forward_filtered(
+        &mut self,
+        tx: &mut TxRing<'_>,
+        max: usize,
+        keep: impl Fn(&[u8]) -> bool,
+    ) -> (usize, usize) {
+        self.forward_with(tx, max, |payload| keep(payload))
+    }
+
+    /// forward up to `max` received packets to `tx`, letting `transform` edit each in place
+    ///
+    /// Packets for which `transform` returns `false` are dropped. Otherwise this works
+    /// like [`forward_filtered`](Self::forward_filtered): buffers are swapped, not copied,
+    /// and neither ring is synced. Returns `(forwarded, dropped)`.
+    pub(crate) fn forward_with(
+        &mut self,
+        tx: &mut TxRing<'_>,
+        max: usize,
+        mut transform: impl FnMut(&mut [u8]) -> bool,
+    ) -> (usize, usize) {
+        let mut forwarded = 0;
+        let mut dropped = 0;
+
+        unsafe {
+            let rx_ring = self.inner.ring;
+            let tx_ring = tx.inner.ring;
+            let rx_slots = (*rx_ring).num_slots;
+            let tx_slots = (*tx_ring).num_slots;
+
+            let mut rx_pos = (*rx_ring).cur % rx_slots;
+            let rx_avail = self.inner.space();
+            let mut tx_pos = (*tx_ring).cur % tx_slots;
+            let mut tx_free = tx.inner.space();
+
+            for _ in 0..rx_avail.min(max) {
+                if tx_free == 0 {
+                    // Stop before `transform` sees the packet, so it stays in the ring unmodified.
+                    break;
+                }
+                let rx_slot = &mut *(*rx_ring).slot.add(rx_pos as usize);
+                let payload = slice::from_raw_parts_mut(rx_slot.buf as *mut u8, rx_slot.len as usize);
+                if let Some(trace) = &mut self.trace {
+                    trace.record(payload);
+                }
+
+                if transform(payload) {
+                    let tx_slot = &mut *(*tx_ring).slot.add(tx_pos as usize);
+                    mem::swap(&mut rx_slot.buf_idx, &mut tx_slot.buf_idx);
+                    mem::swap(&mut rx_slot.buf, &mut tx_slot.buf);
+                    tx_slot.len = rx_slot.len;
+                    rx_slot.flags |= ffi::NS_BUF_CHANGED as u16;
+                    tx_slot.flags |= ffi::NS_BUF_CHANGED as u16;
+
+                    tx_pos = (tx_pos + 1) % tx_slots;
+                    tx_free -= 1;
+                    forwarded += 1;
+                } else {
+                    dropped += 1;
+                    self.inner.count_dropped();
+                }
+                rx_pos = (rx_pos + 1) % rx_slots;
+            }
+
+            self.inner.release(rx_pos);
+            tx.publish(tx_pos);
+        }
+
+        (forwarded, dropped)
+    }
+
+    /// release the next received packet without consuming it, marking it `NS_FORWARD`
+    ///
+    /// On a ring opened in transparent mode (see
+    /// [`NetmapBuilder::transparent`](crate::NetmapBuilder::transparent)) the kernel passes
+    /// the packet on to the other side, e.g. from the NIC to the host stack, at the next
+    /// sync. Elsewhere the packet is simply released. The ring is not synced.
+    ///
+    /// Returns `false` if no packet is available.
+    pub fn pass_through(&mut self) -> bool {
+        if self.available() == 0 {
+            return false;
+        }
+        unsafe {
+            let ring = self.inner.ring;
+            let num_slots = (*ring).num_slots;
+            let head = (*ring).head % num_slots;
+            (*(*ring).slot.add(head as usize)).flags |= ffi::NS_FORWARD as u16;
+
+            let next = (head + 1) % num_slots;
+            self.inner.release(next);
+        }
+        true
+    }
+
+    /// poll for the next packet, for futures on runtimes other than Tokio
+    ///
+    /// If a packet is already visible, or becomes visible after syncing the ring, it is
+    /// returned as `Poll::Ready(Some(frame))` and its slots are released. Packets are
+    /// filtered, traced and gathered from `NS_MOREFRAG` chains as by [`recv`](Self::recv).
+    /// Otherwise `waker` is registered and `Poll::Pending` returned; a background thread
+    /// (started on first use, stopped when this handle drops) `poll(2)`s a netmap
+    /// descriptor and wakes the most recently registered waker once more slots arrive on
+    /// this ring, e.g. the rest of a partly received chain.
+    ///
+    /// The thread opens a descriptor bound to this ring alone (see
+    /// [`NetmapBuilder::ring_id`](crate::NetmapBuilder::ring_id)), so it neither syncs
+    /// nor wakes on rings that other threads consume. Where that is not possible (a pipe
+    /// with several rings, or rings registered with
+    /// [`exclusive`](crate::NetmapBuilder::exclusive)) it polls the `Netmap`'s descriptor,
+    /// which also syncs the other RX rings; while only they have packets it polls every
+    /// few milliseconds instead of spinning.
+    ///
+    /// Integration contract:
+    /// - Wakeups can be spurious. Just poll again.
+    /// - Only the waker from the latest call is woken, as with `Future::poll`.
+    /// - `Poll::Ready(None)` means no complete packet is visible and nothing can wake the
+    ///   task: the handle has no descriptor (it was not obtained from a `Netmap`), the
+    ///   watcher thread could not be started, or the descriptor failed.
+    pub fn poll_recv(&mut self, waker: &Waker) -> Poll<Option<Frame<'_>>> {
+        loop {
+            unsafe {
+                let ring = self.inner.ring;
+                let num_slots = (*ring).num_slots;
+                let cur = (*ring).cur % num_slots;
+                if let Some((frame, frags)) = read_packet(ring, cur, self.available()) {
+                    self.inner.release((cur + frags as u32) % num_slots);
+
+                    if !self.accepts(frame.payload()) {
+                        self.inner.count_dropped();
+                        continue;
+                    }
+                    if let Some(trace) = &mut self.trace {
+                        trace.record(frame.payload());
+                    }
+                    return Poll::Ready(Some(frame));
+                }
+            }
+
+            match self.poll_more_slots(waker) {
+                Poll::Ready(true) => {}
+                Poll::Ready(false) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+
+    /// `Ready(true)` once slots beyond the visible ones arrive, registering `waker` with the watcher until then
+    ///
+    /// `Ready(false)` means nothing will ever wake the task.
+    fn poll_more_slots(&mut self, waker: &Waker) -> Poll<bool> {
+        let Some(fd) = self.fd else {
+            return Poll::Ready(false);
+        };
+        let visible = self.available();
+        self.sync();
+        if self.available() > visible {
+            return Poll::Ready(true);
+        }
+        if self.watcher.is_none() {
+            // A descriptor bound to this ring keeps the watcher from syncing rings
+            // other threads consume; without one it watches the `Netmap`'s.
+            let bound = self.binder.as_ref().and_then(|binder| binder.bind(self.inner.index()).ok());
+            match FdWatcher::new(self.inner.ring, fd, bound) {
+                Ok(watcher) => self.watcher = Some(watcher),
+                Err(_) => return Poll::Ready(false),
+            }
+        }
+        let watcher = self.watcher.as_ref().expect("watcher started above");
+        if !watcher.is_alive() {
+            return Poll::Ready(false);
+        }
+        let tail = unsafe { load_tail(self.inner.ring) };
+        watcher.register(waker, tail);
+        // A packet may have landed between the check above and the registration.
+        if self.available() == visible {
+            return Poll::Pending;
+        }
+        Poll::Ready(true)
+    }
+
+    /// borrow up to `max` received packets as a batch that is released when the guard drops
+    ///
+    /// The guard derefs to the frames, which point straight into the ring buffers. The
+    /// slots stay owned by userspace while the guard is alive, so a sync cannot hand them
+    /// back to the kernel mid-processing; dropping the guard advances `head` and `cur` past
+    /// exactly the frames in the batch. The ring is not synced.
+    ///
+    /// Frames the destination MAC filter rejects are counted as dropped and left out of
+    /// the batch; their slots are released along with it. An `NS_MOREFRAG` chain is
+    /// gathered into one owned frame, and the batch stops before a chain whose tail has
+    /// not arrived yet.
+    pub fn recv_batch_guard(&mut self, max: usize) -> RxBatchGuard<'_, 'a> {
+        let avail = self.available() as u32;
+        let mut frames = Vec::with_capacity(avail.min(max as u32) as usize);
+        let mut consumed = 0;
+        unsafe {
+            let ring = self.inner.ring;
+            let num_slots = (*ring).num_slots;
+            let head = (*ring).head % num_slots;
+            while frames.len() < max {
+                let Some((frame, frags)) = read_packet(ring, head + consumed, (avail - consumed) as usize) else {
+                    break;
+                };
+                consumed += frags as u32;
+
+                if !self.accepts(frame.payload()) {
+                    self.inner.count_dropped();
+                    continue;
+                }
+                if let Some(trace) = &mut self.trace {
+                    trace.record(frame.payload());
+                }
+                frames.push(frame);
+            }
+        }
+
+        RxBatchGuard {
+            ring: self.inner.ring,
+            counters: self.inner.counters.clone(),
+            frames,
+            consumed,
+            _marker: PhantomData,
+        }
+    }
+
+    /// iterate over up to `max` received packets without copying or allocating
+    ///
+    /// The returned [`BatchRecv`] borrows the ring and yields frames pointing directly at
+    /// the mapped buffers. Their slots stay owned by userspace until the iterator is
+    /// dropped, which advances `head` and `cur` past the frames it yielded. Frames must
+    /// not be used after that: once the next [`sync`](Self::sync) hands the slots back,
+    /// the kernel may overwrite them. The borrow checker enforces this for the frames
+    /// themselves; copy out anything that must outlive the batch.
+    ///
+    /// This does not sync and works at the slot level, so that it stays an exact-size view
+    /// of the ring: each slot is one frame, the fragments of an `NS_MOREFRAG` chain come
+    /// out as separate frames, and the destination MAC filter is not applied. Use
+    /// [`recv_batch_guard`](Self::recv_batch_guard) where jumbo frames or a filter are in play.
+    pub fn recv_batch_borrowed(&mut self, max: usize) -> BatchRecv<'_, 'a> {
+        let count = self.available().min(max) as u32;
+        let start = unsafe { (*self.inner.ring).cur % (*self.inner.ring).num_slots };
+        BatchRecv {
+            ring: self,
+            start,
+            count,
+            taken: 0,
+        }
+    }
+
+    /// receive up to an adaptively sized batch of packets, copied out of the ring
+    ///
+    /// The batch size starts at 32. After several calls in a row that fill the whole
+    /// batch it doubles (up to 1024), trading latency for fewer calls under load; after
+    /// several in a row that return at most a quarter of it, it halves (down to 4). The
+    /// frames are owned copies, so the slots are released to the kernel right away.
+    ///
+    /// Like [`recv`](Self::recv) this only looks at packets already visible in the ring
+    /// and never syncs. Use [`auto_batch_size`](Self::auto_batch_size) to see the size
+    /// the next call will use.
+    pub fn recv_auto_batch(&mut self) -> Vec<Frame<'static>> {
+        let mut frames = Vec::with_capacity(self.auto_batch.size);
+        while frames.len() < self.auto_batch.size {
+            match self.recv() {
+                Some(frame) => frames.push(Frame::new_owned(frame.payload().to_vec())),
+                None => break,
+            }
+        }
+        self.auto_batch.record(frames.len());
+        frames
+    }
+
+    /// batch size the next [`recv_auto_batch`](Self::recv_auto_batch) call will use
+    pub fn auto_batch_size(&self) -> usize {
+        self.auto_batch.size
+    }
+
+    /// receive a batch of packets, returning the filled prefix of `batch`
+    ///
+    /// Slots are read in ring order, continuing from the end of the slot array
+    /// back to its start when the pending packets span the wrap boundary. Packets are
+    /// filtered, traced and gathered from `NS_MOREFRAG` chains as by [`recv`](Self::recv);
+    /// the batch stops before a chain whose tail has not arrived yet.
+    ///
+    /// At most `batch.len()` packets are received. The returned subslice holds exactly
+    /// the frames received by this call; entries of `batch` past it are left untouched.
+    ///
+    /// The frames borrow the ring's buffers, so `batch` stays tied to this borrow of the
+    /// ring: it cannot outlive it, and the ring cannot be synced or read again while the
+    /// frames are in use. Use a fresh `batch` per call.
+    pub fn recv_batch<'b>(&'b mut self, batch: &'b mut [Frame<'b>]) -> &'b mut [Frame<'b>] {
+        unsafe {
+            let ring = self.inner.ring;
+            let num_slots = (*ring).num_slots;
+            let start = (*ring).cur % num_slots;
+            let avail = self.available();
+
+            // Frames rejected by the MAC filter are consumed without taking a batch entry.
+            let mut consumed = 0;
+            let mut count = 0;
+            while count < batch.len() {
+                let Some((frame, frags)) = read_packet(ring, start + consumed as u32, avail - consumed) else {
+                    break;
+                };
+                consumed += frags;
+
+                if !self.accepts(frame.payload()) {
+                    self.inner.count_dropped();
+                    continue;
+                }
+                if let Some(trace) = &mut self.trace {
+                    trace.record(frame.payload());
+                }
+                batch[count] = frame;
+                count += 1;
+            }
+            self.inner.release((start + consumed as u32) % num_slots);
+
+            &mut batch[..count]
+        }
+    }
+}
+
+impl RxRingApi for RxRing<'_> {
+    fn recv(&mut self) -> Option<Frame<'_>> {
+        RxRing::recv(self)
+    }
+
+    fn sync(&mut self) {
+        RxRing::sync(self)
+    }
+}
+
+impl<'a> Deref for RxRing<'a> {
+    type Target = Ring<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::mock::{MockRing, MOCK_BUF_SIZE};
+    use super::*;
+
+    const SLOTS: u32 = 8;
+
+    fn tx_mock() -> MockRing {
+        MockRing::new(SLOTS, RingDirection::Tx)
+    }
+
+    fn rx_mock() -> MockRing {
+        MockRing::new(SLOTS, RingDirection::Rx)
+    }
+
+    #[test]
+    fn send_fills_slot_at_cur_and_advances() {
+        let mut mock = tx_mock();
+        mock.tx_ring().send(b"abc").unwrap();
+        mock.tx_ring().send(b"de").unwrap();
+
+        assert_eq!(mock.slot_data(0), b"abc");
+        assert_eq!(mock.slot_data(1), b"de");
+        assert_eq!(mock.pointers(), (2, 2, SLOTS - 1));
+    }
+
+    #[test]
+    fn send_at_returns_slot_holding_packet() {
+        let mut mock = tx_mock();
+        mock.set_pointers(SLOTS - 1, SLOTS - 1, SLOTS - 3);
+        let first = mock.tx_ring().send_at(b"first").unwrap();
+        let second = mock.tx_ring().send_at(b"second").unwrap();
+
+        assert_eq!((first, second), (SLOTS - 1, 0));
+        assert_eq!(mock.slot_data(first as usize), b"first");
+        assert_eq!(mock.slot_data(second as usize), b"second");
+
+        mock.set_pointers(1, 1, 1);
+        assert!(matches!(mock.tx_ring().send_at(b"full"), Err(Error::InsufficientSpace)));
+    }
+
+    #[test]
+    fn send_blocking_sends_without_waiting_when_ring_has_space() {
+        let mut mock = tx_mock();
+        mock.tx_ring().send_blocking(b"queued", Some(Duration::ZERO)).unwrap();
+        assert_eq!(mock.slot_data(0), b"queued");
+        assert_eq!(mock.pointers(), (1, 1, SLOTS - 1));
+
+        // A full mock ring has no descriptor to wait on, so it fails at once rather than blocking.
+        mock.set_pointers(1, 1, 1);
+        assert!(matches!(mock.tx_ring().send_blocking(b"full", None), Err(Error::InsufficientSpace)));
+    }
+
+    #[test]
+    fn counters_track_released_packets_and_drops() {
+        let counters = Arc::new(RingCounters::default());
+        let mut mock = tx_mock();
+        let mut tx = mock.tx_ring().with_counters(Arc::clone(&counters));
+        tx.send(b"abc").unwrap();
+        tx.send(&vec![7; MOCK_BUF_SIZE + 1]).unwrap();
+        let mut reservation = tx.reserve_batch(2).unwrap();
+        reservation.packet(0, 4).unwrap();
+        reservation.commit_lens(&[4]).unwrap();
+        assert_eq!(counters.packets.load(Ordering::Relaxed), 3, "a fragment chain is one packet");
+        assert_eq!(counters.bytes.load(Ordering::Relaxed), 3 + MOCK_BUF_SIZE as u64 + 1 + 4);
+
+        let counters = Arc::new(RingCounters::default());
+        let mut mock = rx_mock();
+        let mine = [0x02, 0, 0, 0, 0, 0x01];
+        mock.fill_slot(0, &[[0x02, 0, 0, 0, 0, 0x09], [0; 6]].concat());
+        mock.fill_slot(1, &[mine, [0; 6]].concat());
+        mock.set_pointers(0, 0, 2);
+        let mut rx = mock.rx_ring().with_counters(Arc::clone(&counters));
+        rx.set_dst_mac_filter(Some(mine));
+        assert!(rx.recv().is_some());
+        assert_eq!(counters.packets.load(Ordering::Relaxed), 2);
+        assert_eq!(counters.bytes.load(Ordering::Relaxed), 24);
+        assert_eq!(counters.dropped.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn send_splits_jumbo_payload_into_morefrag_chain() {
+        let mut mock = tx_mock();
+        let buf: Vec<u8> = (0..2 * MOCK_BUF_SIZE + 100).map(|i| i as u8).collect();
+        mock.tx_ring().send(&buf).unwrap();
+
+        let more = ffi::NS_MOREFRAG as u16;
+        assert_eq!(mock.slot_data(0), &buf[..MOCK_BUF_SIZE]);
+        assert_eq!(mock.slot_data(1), &buf[MOCK_BUF_SIZE..2 * MOCK_BUF_SIZE]);
+        assert_eq!(mock.slot_data(2), &buf[2 * MOCK_BUF_SIZE..]);
+        assert_eq!(mock.slot_flags(0) & more, more);
+        assert_eq!(mock.slot_flags(1) & more, more);
+        assert_eq!(mock.slot_flags(2) & more, 0);
+        assert_eq!(mock.pointers(), (3, 3, SLOTS - 1));
+    }
+
+    #[test]
+    fn send_jumbo_without_room_for_every_fragment_fails() {
+        let mut mock = tx_mock();
+        mock.set_pointers(0, 0, 2);
+        let buf = vec![0u8; 2 * MOCK_BUF_SIZE + 1];
+
+        assert!(matches!(mock.tx_ring().send(&buf), Err(Error::InsufficientSpace)));
+        assert_eq!(mock.pointers(), (0, 0, 2));
+    }
+
+    #[test]
+    fn recv_reassembles_morefrag_chain() {
+        let mut mock = rx_mock();
+        let first = vec![0xaa; MOCK_BUF_SIZE];
+        mock.fill_slot(0, &first);
+        mock.set_slot_flags(0, ffi::NS_MOREFRAG as u16);
+        mock.fill_slot(1, b"tail");
+        mock.fill_slot(2, b"next");
+        mock.set_pointers(0, 0, 3);
+
+        let mut ring = mock.rx_ring();
+        let frame = ring.recv().unwrap();
+        assert_eq!(frame.len(), MOCK_BUF_SIZE + 4);
+        assert_eq!(&frame.payload()[..MOCK_BUF_SIZE], &first[..]);
+        assert_eq!(&frame.payload()[MOCK_BUF_SIZE..], b"tail");
+        drop(frame);
+        assert_eq!(ring.recv().unwrap().payload(), b"next");
+        drop(ring);
+        assert_eq!(mock.pointers(), (3, 3, 3));
+    }
+
+    #[test]
+    fn recv_leaves_incomplete_chain_in_place() {
+        let mut mock = rx_mock();
+        mock.fill_slot(0, b"head");
+        mock.set_slot_flags(0, ffi::NS_MOREFRAG as u16);
+        mock.set_pointers(0, 0, 1);
+
+        assert!(mock.rx_ring().recv().is_none());
+        assert_eq!(mock.pointers(), (0, 0, 1));
+    }
+
+    #[test]
+    fn batch_and_poll_paths_gather_morefrag_chains() {
+        let first = vec![0xaa; MOCK_BUF_SIZE];
+        let jumbo = [&first[..], b"tail"].concat();
+        // A complete chain, a single-slot packet, then a chain still missing its tail.
+        let fill = |mock: &mut MockRing| {
+            mock.fill_slot(0, &first);
+            mock.set_slot_flags(0, ffi::NS_MOREFRAG as u16);
+            mock.fill_slot(1, b"tail");
+            mock.fill_slot(2, b"next");
+            mock.fill_slot(3, b"head");
+            mock.set_slot_flags(3, ffi::NS_MOREFRAG as u16);
+            mock.set_pointers(0, 0, 4);
+        };
+
+        let mut mock = rx_mock();
+        fill(&mut mock);
+        let mut rx = mock.rx_ring();
+        let mut batch = [Frame::new(&[]), Frame::new(&[]), Frame::new(&[])];
+        let payloads: Vec<Vec<u8>> = rx.recv_batch(&mut batch).iter().map(|f| f.payload().to_vec()).collect();
+        assert_eq!(payloads, [jumbo.clone(), b"next".to_vec()]);
+        drop(rx);
+        assert_eq!(mock.pointers(), (3, 3, 4), "the incomplete chain stays in the ring");
+
+        let mut mock = rx_mock();
+        fill(&mut mock);
+        let mut rx = mock.rx_ring();
+        {
+            let batch = rx.recv_batch_guard(8);
+            let payloads: Vec<&[u8]> = batch.iter().map(|f| f.payload()).collect();
+            assert_eq!(payloads, [&jumbo[..], b"next"]);
+        }
+        assert_eq!((rx.head(), rx.cur()), (3, 3));
+
+        let mut mock = rx_mock();
+        fill(&mut mock);
+        let mut rx = mock.rx_ring();
+        match rx.poll_recv(Waker::noop()) {
+            Poll::Ready(Some(frame)) => assert_eq!(frame.payload(), jumbo),
+            other => panic!("expected the gathered chain, got {:?}", other.map(|f| f.map(|f| f.len()))),
+        }
+        assert!(matches!(rx.poll_recv(Waker::noop()), Poll::Ready(Some(frame)) if frame.payload() == b"next"));
+        // Only part of a chain is left, and a mock ring has no descriptor to wait on.
+        assert!(matches!(rx.poll_recv(Waker::noop()), Poll::Ready(None)));
+        drop(rx);
+        assert_eq!(mock.pointers(), (3, 3, 4));
+    }
+
+    #[test]
+    fn send_batch_queues_what_fits() {
+        let mut mock = tx_mock();
+        mock.set_pointers(SLOTS - 2, SLOTS - 2, 1);
+        let packets: [&[u8]; 4] = [b"a", b"bb", b"ccc", b"dddd"];
+
+        assert_eq!(mock.tx_ring().send_batch(&packets).unwrap(), 3);
+        assert_eq!(mock.slot_data(SLOTS as usize - 2), b"a");
+        assert_eq!(mock.slot_data(SLOTS as usize - 1), b"bb");
+        assert_eq!(mock.slot_data(0), b"ccc");
+        assert_eq!(mock.pointers(), (1, 1, 1));
+        assert_eq!(mock.tx_ring().send_batch(&packets[3..]).unwrap(), 0);
+    }
+
+    #[test]
+    fn send_batch_rejects_oversized_packet_before_queuing() {
+        let mut mock = tx_mock();
+        let big = vec![0u8; MOCK_BUF_SIZE + 1];
+        let packets: [&[u8]; 2] = [b"ok", &big];
+
+        assert!(matches!(mock.tx_ring().send_batch(&packets), Err(Error::PacketTooLarge(n)) if n == big.len()));
+        assert_eq!(mock.pointers(), (0, 0, SLOTS - 1));
+    }
+
+    /// Handles standing in for a `Netmap`'s own, the mirror `mock` being ring 1.
+    fn mirror_peers(mock: &mut MockRing, counters: &Arc<RingCounters>) -> [UnsafeCell<TxRing<'static>>; 2] {
+        [
+            UnsafeCell::new(TxRing::new(ptr::null_mut(), 0)),
+            UnsafeCell::new(TxRing::new(mock.as_ptr(), 1).with_counters(Arc::clone(counters))),
+        ]
+    }
+
+    #[test]
+    fn mirrored_send_copies_packet_to_mirror_ring() {
+        let mut mock = tx_mock();
+        let mut mirror = tx_mock();
+        let counters = Arc::new(RingCounters::default());
+        let peers = mirror_peers(&mut mirror, &counters);
+        let mut ring = mock.tx_ring();
+        ring.set_peers(TxPeers::new(&peers));
+        ring.set_mirror(Some(1)).unwrap();
+        ring.send(b"primary").unwrap();
+        let packets: [&[u8]; 2] = [b"one", b"two"];
+        assert_eq!(ring.send_batch(&packets).unwrap(), 2);
+        ring.reserve_slot(6).unwrap().copy_from_slice(b"commit");
+        ring.commit_one();
+        ring.send(&vec![7; MOCK_BUF_SIZE + 1]).unwrap();
+        drop(ring);
+
+        for (i, data) in [&b"primary"[..], b"one", b"two", b"commit"].into_iter().enumerate() {
+            assert_eq!(mock.slot_data(i), data);
+            assert_eq!(mirror.slot_data(i), data);
+        }
+        assert_eq!(mirror.slot_data(4).len(), MOCK_BUF_SIZE);
+        assert_eq!(mirror.slot_data(5), [7]);
+        assert_eq!(mirror.pointers(), (6, 6, SLOTS - 1));
+        assert_eq!(counters.packets.load(Ordering::Relaxed), 5, "copies count on the mirror ring");
+    }
+
+    #[test]
+    fn full_or_reserved_mirror_ring_does_not_fail_the_send() {
+        let mut mock = tx_mock();
+        let mut mirror = tx_mock();
+        mirror.set_pointers(0, 0, 0);
+        let counters = Arc::new(RingCounters::default());
+        let peers = mirror_peers(&mut mirror, &counters);
+        let mut ring = mock.tx_ring();
+        ring.set_peers(TxPeers::new(&peers));
+        ring.set_mirror(Some(1)).unwrap();
+        ring.send(b"abc").unwrap();
+        assert_eq!(mirror.pointers(), (0, 0, 0));
+
+        // A slot the mirror ring's own handle has reserved must not be overwritten.
+        mirror.set_pointers(0, 0, SLOTS - 1);
+        unsafe { (*peers[1].get()).reserve_slot(3).unwrap().copy_from_slice(b"own") };
+        ring.send(b"def").unwrap();
+        drop(ring);
+
+        assert_eq!(mock.pointers(), (2, 2, SLOTS - 1));
+        assert_eq!(mirror.slot_data(0), b"own");
+        assert_eq!(mirror.pointers(), (0, 0, SLOTS - 1));
+        assert_eq!(counters.packets.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn tx_ring_is_send() {
+        fn assert_send<T: Send>() {}
+        assert_send::<TxRing<'static>>();
+    }
+
+    #[test]
+    fn set_mirror_needs_a_ring_from_a_netmap() {
+        let mut mock = tx_mock();
+        let mut ring = mock.tx_ring();
+        assert!(matches!(ring.set_mirror(Some(1)), Err(Error::InvalidRingIndex(1))));
+        assert!(matches!(ring.set_mirror(Some(0)), Err(Error::InvalidRingIndex(0))));
+        ring.set_mirror(None).unwrap();
+        assert_eq!(ring.mirror(), None);
+    }
+
+    #[test]
+    fn reserve_slot_publishes_only_on_commit() {
+        let mut mock = tx_mock();
+        let mut ring = mock.tx_ring();
+        ring.reserve_slot(3).unwrap().copy_from_slice(b"abc");
+        assert_eq!((ring.head(), ring.cur()), (0, 0));
+        ring.commit_one();
+        ring.commit_one(); // nothing reserved, so no second slot is published
+        drop(ring);
+
+        assert_eq!(mock.slot_data(0), b"abc");
+        assert_eq!(mock.pointers(), (1, 1, SLOTS - 1));
+    }
+
+    #[test]
+    fn reserve_slot_on_full_ring_fails() {
+        let mut mock = tx_mock();
+        mock.set_pointers(3, 3, 3);
+
+        assert!(matches!(mock.tx_ring().reserve_slot(1), Err(Error::InsufficientSpace)));
+        assert!(matches!(mock.tx_ring().reserve_slot(MOCK_BUF_SIZE + 1), Err(Error::PacketTooLarge(_))));
+    }
+
+    #[test]
+    fn send_rejects_oversized_packet() {
+        let mut mock = tx_mock();
+        // Needs SLOTS fragments, but at most SLOTS - 1 slots are ever free.
+        let buf = vec![0u8; SLOTS as usize * MOCK_BUF_SIZE];
+
+        assert!(matches!(mock.tx_ring().send(&buf), Err(Error::PacketTooLarge(n)) if n == buf.len()));
+        assert_eq!(mock.pointers(), (0, 0, SLOTS - 1));
+    }
+
+    #[test]
+    fn send_wraps_at_end_of_ring() {
+        let mut mock = tx_mock();
+        mock.set_pointers(SLOTS - 1, SLOTS - 1, SLOTS - 3);
+        mock.tx_ring().send(b"last").unwrap();
+
+        assert_eq!(mock.slot_data(SLOTS as usize - 1), b"last");
+        assert_eq!(mock.pointers(), (0, 0, SLOTS - 3));
+    }
+
+    #[test]
+    fn send_on_full_ring_fails() {
+        let mut mock = tx_mock();
+        mock.set_pointers(3, 3, 3);
+
+        assert!(matches!(mock.tx_ring().send(b"x"), Err(Error::InsufficientSpace)));
+        assert_eq!(mock.pointers(), (3, 3, 3));
+    }
+
+    #[test]
+    fn try_send_on_full_ring_would_block() {
+        let mut mock = tx_mock();
+        mock.set_pointers(3, 3, 3);
+
+        assert!(matches!(mock.tx_ring().try_send(b"x"), Err(Error::WouldBlock)));
+        assert_eq!(mock.pointers(), (3, 3, 3));
+    }
+
+    #[test]
+    fn send_to_host_fills_missing_udp_checksum() {
+        // Ethernet/IPv4/UDP, 10.0.0.1:1 -> 10.0.0.2:2, 2-byte payload, no UDP checksum.
+        let mut frame = vec![0u8; packet::ETH_HEADER_LEN];
+        frame[12..14].copy_from_slice(&[0x08, 0x00]);
+        frame.extend_from_slice(&[0x45, 0, 0, 30, 0, 0, 0, 0, 64, 17, 0, 0, 10, 0, 0, 1, 10, 0, 0, 2]);
+        frame.extend_from_slice(&[0, 1, 0, 2, 0, 10, 0, 0, b'h', b'i']);
+
+        let mut mock = tx_mock();
+        mock.tx_ring().send_to_host(&frame).unwrap();
+
+        let mut expected = frame.clone();
+        packet::fill_udp_checksum(&mut expected).unwrap();
+        assert_ne!(expected[40..42], [0, 0]);
+        assert_eq!(mock.slot_data(0), &expected[..]);
+    }
+
+    #[test]
+    fn send_host_timed_falls_back_to_plain_send() {
+        let mut frame = vec![0u8; MIN_FRAME_LEN];
+        frame[12..14].copy_from_slice(&[0x88, 0xb5]); // local experimental EtherType
+        let timestamp = SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1_000_000_000);
+
+        let mut mock = tx_mock();
+        mock.tx_ring().send_host_timed(&frame, timestamp).unwrap();
+
+        assert_eq!(mock.slot_data(0), &frame[..]);
+        assert_eq!(mock.pointers(), (1, 1, SLOTS - 1));
+    }
+
+    #[test]
+    fn send_to_host_rejects_incomplete_frame() {
+        let mut mock = tx_mock();
+
+        assert!(matches!(mock.tx_ring().send_to_host(&[0u8; 10]), Err(Error::MalformedPacket(_))));
+        assert_eq!(mock.pointers(), (0, 0, SLOTS - 1));
+    }
+
+    #[test]
+    fn send_to_host_rejects_frames_over_host_mtu() {
+        let mut mock = tx_mock();
+        let mtu = 100;
+        let mut tx = mock.tx_ring().with_host_mtu(Some(mtu));
+
+        // Well within the slot size, but the payload behind the Ethernet header exceeds the MTU.
+        let oversized = vec![0u8; packet::ETH_HEADER_LEN + mtu + 1];
+        assert!(matches!(tx.send_to_host(&oversized), Err(Error::ExceedsHostMtu(len)) if len == oversized.len()));
+        assert_eq!(mock.pointers(), (0, 0, SLOTS - 1));
+    }
+
+    #[test]
+    fn recv_on_empty_ring_returns_none() {
+        let mut mock = rx_mock();
+        mock.set_pointers(5, 5, 5);
+
+        assert!(mock.rx_ring().recv().is_none());
+        assert_eq!(mock.pointers(), (5, 5, 5));
+    }
+
+    #[test]
+    fn recv_reads_from_head_and_wraps() {
+        let mut mock = rx_mock();
+        mock.fill_slot(SLOTS as usize - 1, b"first");
+        mock.fill_slot(0, b"second");
+        mock.set_pointers(SLOTS - 1, SLOTS - 1, 1);
+
+        let mut rx = mock.rx_ring();
+        assert_eq!(rx.recv().unwrap().payload(), b"first");
+        assert_eq!(rx.recv().unwrap().payload(), b"second");
+        assert!(rx.recv().is_none());
+        drop(rx);
+        assert_eq!(mock.pointers(), (1, 1, 1));
+    }
+
+    #[test]
+    fn recv_batch_on_empty_ring_returns_zero() {
+        let mut mock = rx_mock();
+        mock.set_pointers(2, 2, 2);
+
+        {
+            let mut rx = mock.rx_ring();
+            let mut batch: Vec<Frame> = (0..4).map(|_| Frame::new(&[])).collect();
+            assert!(rx.recv_batch(&mut batch).is_empty());
+        }
+        assert_eq!(mock.pointers(), (2, 2, 2));
+    }
 
-            for i in 0..count {
-                let slot_idx = ((*ring).tail + i as u32) % (*ring).num_slots;
-                let slot = (*ring).slot.add(slot_idx as usize);
-                let buf = slice::from_raw_parts((*slot).buf as *const u8, (*slot).len as usize);
+    #[test]
+    fn recv_batch_returns_only_received_frames() {
+        let mut mock = rx_mock();
+        mock.fill_slot(0, b"fresh");
+        mock.set_pointers(0, 0, 1);
 
-                batch[i] = Frame::new(buf);
+        let mut rx = mock.rx_ring();
+        let mut batch: Vec<Frame> = (0..4).map(|_| Frame::new(b"stale")).collect();
+        let received = rx.recv_batch(&mut batch);
+        assert_eq!(received.len(), 1);
+        assert_eq!(received[0].payload(), b"fresh");
+    }
+
+    #[test]
+    fn forward_with_transforms_in_place_and_drops_rejected() {
+        let mut rx = rx_mock();
+        for (i, data) in [&b"\x01a"[..], b"\x02b", b"\x03c"].iter().enumerate() {
+            rx.fill_slot(i, data);
+        }
+        rx.set_pointers(0, 0, 3);
+        let mut tx = tx_mock();
+
+        let (forwarded, dropped) = rx.rx_ring().forward_with(&mut tx.tx_ring(), 8, |packet| {
+            packet[0] += 10;
+            packet[0] != 12
+        });
+        assert_eq!((forwarded, dropped), (2, 1));
+        assert_eq!(tx.slot_data(0), b"\x0ba");
+        assert_eq!(tx.slot_data(1), b"\x0dc");
+        assert_eq!(tx.pointers(), (2, 2, SLOTS - 1));
+        assert_eq!(rx.pointers(), (3, 3, 3));
+    }
+
+    #[test]
+    fn forward_with_stops_before_transforming_when_tx_is_full() {
+        let mut rx = rx_mock();
+        rx.fill_slot(0, b"x");
+        rx.set_pointers(0, 0, 1);
+        let mut tx = tx_mock();
+        tx.set_pointers(0, 0, 0);
+
+        let mut calls = 0;
+        let result = rx.rx_ring().forward_with(&mut tx.tx_ring(), 8, |_| {
+            calls += 1;
+            true
+        });
+        assert_eq!((result, calls), ((0, 0), 0));
+        assert_eq!(rx.pointers(), (0, 0, 1));
+        assert_eq!(rx.slot_data(0), b"x");
+    }
+
+    #[test]
+    fn recv_batch_reads_across_wrap() {
+        let mut mock = rx_mock();
+        let start = SLOTS - 2;
+        for (i, data) in [&b"a"[..], b"b", b"c"].iter().enumerate() {
+            mock.fill_slot(((start + i as u32) % SLOTS) as usize, data);
+        }
+        mock.set_pointers(start, start, 1);
+
+        {
+            let mut rx = mock.rx_ring();
+            let mut batch: Vec<Frame> = (0..4).map(|_| Frame::new(&[])).collect();
+            let received = rx.recv_batch(&mut batch);
+            let payloads: Vec<&[u8]> = received.iter().map(|f| f.payload()).collect();
+            assert_eq!(payloads, [&b"a"[..], b"b", b"c"]);
+        }
+        assert_eq!(mock.pointers(), (1, 1, 1));
+    }
+
+    #[test]
+    fn recv_batch_guard_releases_slots_on_drop() {
+        let mut mock = rx_mock();
+        let start = SLOTS - 1;
+        for (i, data) in [&b"a"[..], b"b", b"c"].iter().enumerate() {
+            mock.fill_slot(((start + i as u32) % SLOTS) as usize, data);
+        }
+        mock.set_pointers(start, start, 2);
+
+        let mut rx = mock.rx_ring();
+        let raw = unsafe { rx.as_raw_ring() };
+        {
+            let batch = rx.recv_batch_guard(2);
+            let payloads: Vec<&[u8]> = batch.iter().map(|f| f.payload()).collect();
+            assert_eq!(payloads, [&b"a"[..], b"b"]);
+            // Still owned by the batch: nothing released yet.
+            assert_eq!(unsafe { ((*raw).head, (*raw).cur) }, (start, start));
+        }
+        assert_eq!(unsafe { ((*raw).head, (*raw).cur) }, (1, 1));
+        assert_eq!(rx.available(), 1);
+
+        let batch = rx.recv_batch_guard(8);
+        assert_eq!(batch.len(), 1);
+        assert_eq!(batch[0].payload(), b"c");
+        drop(batch);
+        drop(rx);
+        assert_eq!(mock.pointers(), (2, 2, 2));
+    }
+
+    #[test]
+    fn recv_batch_borrowed_releases_only_yielded_frames() {
+        let mut mock = rx_mock();
+        let start = SLOTS - 1;
+        for (i, data) in [&b"a"[..], b"b", b"c"].iter().enumerate() {
+            mock.fill_slot(((start + i as u32) % SLOTS) as usize, data);
+        }
+        mock.set_pointers(start, start, 2);
+
+        let mut rx = mock.rx_ring();
+        let mut batch = rx.recv_batch_borrowed(8);
+        assert_eq!(batch.len(), 3);
+        let frames: Vec<Frame> = batch.by_ref().take(2).collect();
+        assert_eq!(frames[0].payload(), b"a");
+        assert_eq!(frames[1].payload(), b"b");
+        drop(batch);
+        assert_eq!((rx.head(), rx.cur()), (1, 1));
+
+        let rest: Vec<Vec<u8>> = rx.recv_batch_borrowed(8).map(|f| f.payload().to_vec()).collect();
+        assert_eq!(rest, [b"c".to_vec()]);
+        drop(rx);
+        assert_eq!(mock.pointers(), (2, 2, 2));
+    }
+
+    #[test]
+    fn poll_recv_returns_visible_packets_then_none_without_fd() {
+        let mut mock = rx_mock();
+        mock.fill_slot(SLOTS as usize - 1, b"ready");
+        mock.set_pointers(SLOTS - 1, SLOTS - 1, 0);
+
+        let mut rx = mock.rx_ring();
+        let waker = Waker::noop();
+        match rx.poll_recv(waker) {
+            Poll::Ready(Some(frame)) => assert_eq!(frame.payload(), b"ready"),
+            other => panic!("expected the visible packet, got {:?}", other.map(|f| f.map(|f| f.len()))),
+        }
+        // A mock ring has no descriptor, so an empty ring can never wake the task.
+        assert!(matches!(rx.poll_recv(waker), Poll::Ready(None)));
+        drop(rx);
+        assert_eq!(mock.pointers(), (0, 0, 0));
+    }
+
+    #[test]
+    fn dst_mac_filter_matches_unicast_and_optionally_group_addresses() {
+        const MINE: [u8; 6] = [0x02, 0, 0, 0, 0, 0x01];
+        let mut mock = rx_mock();
+        let mut rx = mock.rx_ring();
+        let frame_to = |dst: [u8; 6]| [&dst[..], &[0u8; 8]].concat();
+
+        assert!(rx.accepts(&frame_to([0x02, 0, 0, 0, 0, 0x02])), "no filter passes everything");
+
+        rx.set_dst_mac_filter(Some(MINE));
+        assert!(rx.accepts(&frame_to(MINE)));
+        assert!(!rx.accepts(&frame_to([0x02, 0, 0, 0, 0, 0x02])));
+        assert!(!rx.accepts(&frame_to([0xff; 6])));
+        assert!(!rx.accepts(&MINE[..5]), "a frame too short for a MAC never matches");
+
+        rx.set_pass_multicast(true);
+        assert!(rx.accepts(&frame_to([0xff; 6])));
+        assert!(rx.accepts(&frame_to([0x01, 0x00, 0x5e, 0, 0, 1])));
+        assert!(!rx.accepts(&frame_to([0x02, 0, 0, 0, 0, 0x02])));
+    }
+
+    #[test]
+    fn dst_mac_filter_applies_to_poll_recv_and_batch_guard() {
+        const MINE: [u8; 6] = [0x02, 0, 0, 0, 0, 0x01];
+        const OTHER: [u8; 6] = [0x02, 0, 0, 0, 0, 0x02];
+        let frame_to = |dst: [u8; 6], tag: u8| [&dst[..], &[tag; 8]].concat();
+        let counters = Arc::new(RingCounters::default());
+        let mut mock = rx_mock();
+        for (i, dst) in [OTHER, MINE, OTHER, OTHER, MINE, OTHER].into_iter().enumerate() {
+            mock.fill_slot(i, &frame_to(dst, i as u8));
+        }
+        mock.set_pointers(0, 0, 6);
+
+        let mut rx = mock.rx_ring().with_counters(Arc::clone(&counters));
+        rx.set_dst_mac_filter(Some(MINE));
+        match rx.poll_recv(Waker::noop()) {
+            Poll::Ready(Some(frame)) => assert_eq!(frame.payload(), frame_to(MINE, 1)),
+            other => panic!("expected the matching packet, got {:?}", other.map(|f| f.map(|f| f.len()))),
+        }
+        {
+            let batch = rx.recv_batch_guard(1);
+            assert_eq!(batch.len(), 1);
+            assert_eq!(batch[0].payload(), frame_to(MINE, 4));
+        }
+        // Only the rejected frame left over: the guard consumes it without yielding it.
+        assert!(rx.recv_batch_guard(8).is_empty());
+        assert_eq!(rx.available(), 0);
+        drop(rx);
+        assert_eq!(mock.pointers(), (6, 6, 6));
+        assert_eq!(counters.dropped.load(Ordering::Relaxed), 4);
+    }
+
+    #[test]
+    fn pass_through_marks_slot_forward_and_releases_it() {
+        let mut mock = rx_mock();
+        mock.fill_slot(SLOTS as usize - 1, b"host");
+        mock.fill_slot(0, b"mine");
+        mock.set_pointers(SLOTS - 1, SLOTS - 1, 1);
+
+        let mut rx = mock.rx_ring();
+        assert!(rx.pass_through());
+        assert_eq!(rx.available(), 1);
+        assert!(rx.pass_through());
+        assert!(!rx.pass_through());
+        drop(rx);
+
+        let forward = ffi::NS_FORWARD as u16;
+        assert_eq!(mock.slot_flags(SLOTS as usize - 1) & forward, forward);
+        assert_eq!(mock.slot_flags(0) & forward, forward);
+        assert_eq!(mock.pointers(), (1, 1, 1));
+    }
+
+    #[test]
+    fn recv_timeout_returns_queued_packet_without_waiting() {
+        let mut mock = rx_mock();
+        mock.fill_slot(0, b"ready");
+        mock.set_pointers(0, 0, 1);
+
+        let mut rx = mock.rx_ring();
+        let frame = rx.recv_timeout(Duration::from_secs(60)).unwrap();
+        assert_eq!(frame.map(|frame| frame.payload().to_vec()), Some(b"ready".to_vec()));
+        // A mock ring has no descriptor to wait on, so an empty one returns at once.
+        assert!(rx.recv_timeout(Duration::from_secs(60)).unwrap().is_none());
+    }
+
+    #[test]
+    fn recv_pooled_reuses_a_bounded_set_of_buffers() {
+        const HELD: usize = 4;
+        let pool = FramePool::new(HELD);
+        let mut mock = rx_mock();
+        let mut held = std::collections::VecDeque::new();
+        let mut buffers = std::collections::HashSet::new();
+
+        for i in 0..10_000u32 {
+            let slot = i % SLOTS;
+            mock.fill_slot(slot as usize, &i.to_be_bytes());
+            mock.set_pointers(slot, slot, (slot + 1) % SLOTS);
+            let frame = mock.rx_ring().recv_pooled(&pool).expect("queued packet not received");
+            assert_eq!(frame.payload(), i.to_be_bytes());
+            buffers.insert(frame.as_ptr() as usize);
+            held.push_back(frame);
+            if held.len() > HELD {
+                held.pop_front();
             }
-            (*ring).head = (*ring).tail + count as u32;
-            (*ring).tail = (*ring).head;
+        }
+
+        assert!(buffers.len() <= HELD + 1, "{} distinct buffers for {} frames in flight", buffers.len(), HELD + 1);
+        drop(held);
+        assert_eq!(pool.len(), HELD);
+    }
+
+    #[test]
+    fn blocking_wrappers_keep_packet_boundaries() {
+        use std::io::{Read, Write};
+
+        let mut mock = tx_mock();
+        let mut tx = BlockingTxRing::new(mock.tx_ring());
+        assert_eq!(tx.write(b"first").unwrap(), 5);
+        assert_eq!(tx.write(b"second").unwrap(), 6);
+        drop(tx);
+        assert_eq!(mock.slot_data(0), b"first");
+        assert_eq!(mock.slot_data(1), b"second");
+
+        let mut mock = rx_mock();
+        mock.fill_slot(0, b"truncated");
+        mock.fill_slot(1, b"whole");
+        mock.set_pointers(0, 0, 2);
+        let mut rx = BlockingRxRing::new(mock.rx_ring());
+        let mut buf = [0u8; 5];
+        assert_eq!(rx.read(&mut buf).unwrap(), 5);
+        assert_eq!(&buf, b"trunc");
+        let mut buf = [0u8; 16];
+        assert_eq!(rx.read(&mut buf).unwrap(), 5);
+        assert_eq!(&buf[..5], b"whole");
+        // A mock ring has no descriptor to wait on, so an empty one times out at once.
+        assert_eq!(rx.read(&mut buf).unwrap_err().kind(), io::ErrorKind::TimedOut);
+    }
+
+    #[test]
+    fn pipe_close_sentinel_is_reported_not_received() {
+        let mut mock = rx_mock();
+        mock.fill_slot(0, b"last words");
+        mock.fill_slot(1, b"");
+        mock.set_pointers(0, 0, 2);
+
+        let rx = mock.rx_ring();
+        assert_eq!(rx.available(), 2, "only pipe rings treat an empty packet as a sentinel");
+        assert!(!rx.peer_closed());
+
+        let mut rx = mock.rx_ring().with_pipe(true);
+        assert_eq!(rx.available(), 1);
+        assert!(rx.peer_closed(), "sentinel should be visible behind the pending packet");
+        assert_eq!(rx.recv().map(|frame| frame.payload().to_vec()), Some(b"last words".to_vec()));
+        assert!(rx.recv().is_none());
+        assert!(rx.peer_closed());
+        assert_eq!(mock.pointers().0, 1, "the sentinel must stay in the ring");
+    }
+
+    #[test]
+    fn ring_and_next_slot_flags_read_through() {
+        let mut mock = rx_mock();
+        mock.set_ring_flags(ffi::NR_FORWARD as u16);
+        mock.fill_slot(0, b"host");
+        mock.set_slot_flags(0, ffi::NS_FORWARD as u16);
+        mock.fill_slot(1, b"mine");
+
+        let mut rx = mock.rx_ring();
+        assert_eq!(rx.ring_flags(), ffi::NR_FORWARD as u32);
+        assert_eq!(rx.next_slot_flags(), None);
+        drop(rx);
+
+        mock.set_pointers(0, 0, 2);
+        rx = mock.rx_ring();
+        assert_eq!(rx.next_slot_flags(), Some(ffi::NS_FORWARD as u16));
+        assert!(rx.pass_through());
+        assert_eq!(rx.next_slot_flags(), Some(0));
+    }
+
+    #[test]
+    fn reserve_batch_commit_lens_publishes_lengths_and_wraps() {
+        let mut mock = tx_mock();
+        mock.set_pointers(SLOTS - 2, SLOTS - 2, SLOTS - 3);
+        let mut tx = mock.tx_ring();
+
+        let mut batch = tx.reserve_batch(4).unwrap();
+        for (i, data) in [&b"ab"[..], b"cde", b"f"].iter().enumerate() {
+            batch.buffer(i).unwrap()[..data.len()].copy_from_slice(data);
+        }
+        batch.commit_lens(&[2, 3, 1]).unwrap();
+        drop(tx);
+
+        assert_eq!(mock.pointers(), (1, 1, SLOTS - 3));
+        assert_eq!(mock.slot_data(SLOTS as usize - 2), b"ab");
+        assert_eq!(mock.slot_data(SLOTS as usize - 1), b"cde");
+        assert_eq!(mock.slot_data(0), b"f");
+    }
+
+    #[test]
+    fn batch_frames_build_packets_and_commit_on_drop() {
+        let mut mock = tx_mock();
+        mock.set_pointers(SLOTS - 1, SLOTS - 1, SLOTS - 3);
+        let mut tx = mock.tx_ring();
+
+        let mut batch = tx.batch(4).unwrap();
+        for (i, mut frame) in batch.frames().take(2).enumerate() {
+            assert!(frame.is_empty());
+            frame.extend_from_slice(b"hdr").unwrap();
+            frame.extend_from_slice(&vec![i as u8; i + 1]).unwrap();
+        }
+        let mut frame = batch.frames().next().unwrap();
+        frame.buffer_mut()[..2].copy_from_slice(b"xy");
+        frame.set_len(2).unwrap();
+        assert!(matches!(frame.set_len(MOCK_BUF_SIZE + 1), Err(Error::PacketTooLarge(_))));
+        assert!(matches!(frame.extend_from_slice(&[0; MOCK_BUF_SIZE]), Err(Error::PacketTooLarge(_))));
+        assert_eq!(frame.payload(), b"xy");
+        drop(batch);
+        drop(tx);
+
+        assert_eq!(mock.pointers(), (2, 2, SLOTS - 3));
+        assert_eq!(mock.slot_data(SLOTS as usize - 1), b"hdr\0");
+        assert_eq!(mock.slot_data(0), b"hdr\x01\x01");
+        assert_eq!(mock.slot_data(1), b"xy");
+    }
+
+    #[test]
+    fn reserve_batch_commit_lens_rejects_bad_lengths() {
+        let mut mock = tx_mock();
+        let mut tx = mock.tx_ring();
+
+        let batch = tx.reserve_batch(2).unwrap();
+        assert!(matches!(batch.commit_lens(&[1, 1, 1]), Err(Error::InvalidRingIndex(2))));
+        let batch = tx.reserve_batch(2).unwrap();
+        assert!(matches!(
+            batch.commit_lens(&[1, MOCK_BUF_SIZE + 1]),
+            Err(Error::PacketTooLarge(len)) if len == MOCK_BUF_SIZE + 1
+        ));
+        drop(tx);
+        assert_eq!(mock.pointers(), (0, 0, SLOTS - 1));
+    }
+
+    #[test]
+    fn reserve_batch_uses_every_free_slot() {
+        let mut mock = tx_mock();
+        let mut tx = mock.tx_ring();
+
+        let mut batch = tx.reserve_batch(SLOTS as usize - 1).unwrap();
+        for i in 0..SLOTS as usize - 1 {
+            batch.packet(i, 1).unwrap()[0] = i as u8;
+        }
+        batch.commit();
+        drop(tx);
+        assert_eq!(mock.pointers(), (SLOTS - 1, SLOTS - 1, SLOTS - 1));
+    }
+
+    #[test]
+    fn reserve_batch_on_full_ring_fails() {
+        let mut mock = tx_mock();
+        mock.set_pointers(4, 4, 4);
+
+        assert!(matches!(mock.tx_ring().reserve_batch(1), Err(Error::InsufficientSpace)));
+    }
+
+    #[test]
+    fn reserve_batch_commit_wraps() {
+        let mut mock = tx_mock();
+        mock.set_pointers(SLOTS - 2, SLOTS - 2, SLOTS - 4);
 
-            count
+        let mut tx = mock.tx_ring();
+        let mut batch = tx.reserve_batch(3).unwrap();
+        for (i, data) in [&b"x"[..], b"y", b"z"].iter().enumerate() {
+            batch.packet(i, data.len()).unwrap().copy_from_slice(data);
+        }
+        batch.commit();
+        drop(tx);
+
+        assert_eq!(mock.slot_data(SLOTS as usize - 2), b"x");
+        assert_eq!(mock.slot_data(SLOTS as usize - 1), b"y");
+        assert_eq!(mock.slot_data(0), b"z");
+        assert_eq!(mock.pointers(), (1, 1, SLOTS - 4));
+    }
+
+    #[test]
+    fn recv_auto_batch_grows_under_load_and_shrinks_when_idle() {
+        const NUM_SLOTS: u32 = 4096;
+        let mut mock = MockRing::new(NUM_SLOTS, RingDirection::Rx);
+        let mut ring = RxRing::new(mock.as_ptr(), 0);
+        let mut next = 0u32;
+        // Play the kernel: append `n` packets after whatever is still pending.
+        let mut deliver = |mock: &mut MockRing, n: u32| {
+            for _ in 0..n {
+                mock.fill_slot(next as usize, b"burst");
+                next = (next + 1) % NUM_SLOTS;
+            }
+            let (head, cur, _) = mock.pointers();
+            mock.set_pointers(head, cur, next);
+        };
+
+        // Bursts: the ring always holds more than a batch.
+        let initial = ring.auto_batch_size();
+        for _ in 0..3 * AUTO_BATCH_STREAK {
+            let size = ring.auto_batch_size();
+            deliver(&mut mock, size as u32);
+            assert_eq!(ring.recv_auto_batch().len(), size);
+        }
+        let loaded = ring.auto_batch_size();
+        assert!(loaded > initial, "batch did not grow under load: {} -> {}", initial, loaded);
+
+        // Trickle: one packet per call.
+        for _ in 0..AUTO_BATCH_STREAK {
+            deliver(&mut mock, 1);
+            assert_eq!(ring.recv_auto_batch().len(), 1);
+        }
+        assert!(ring.auto_batch_size() < loaded, "batch did not shrink when idle");
+    }
+
+    #[test]
+    fn index_is_the_ring_index_not_the_slot_count() {
+        let mut mocks: Vec<MockRing> = (0..4).map(|_| tx_mock()).collect();
+        for (i, mock) in mocks.iter_mut().enumerate() {
+            let ring = mock.as_ptr();
+            assert_eq!(Ring::new(ring, i).index(), i);
+            assert_eq!(TxRing::new(ring, i).index(), i);
+            assert_eq!(RxRing::new(ring, i).index(), i);
+            assert_eq!(Ring::new(ring, i).num_slots(), SLOTS as usize);
         }
     }
-}
\ No newline at end of file
+}
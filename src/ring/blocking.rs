@@ -0,0 +1,156 @@
+//! Blocking `std::io::Read`/`std::io::Write` adapters over netmap rings.
+//!
+//! These give synchronous code the same byte-stream view the Tokio wrappers give async
+//! code, while keeping packet boundaries: every `read` returns at most one frame and
+//! every `write` queues exactly one packet.
+
+use std::io::{self, Read, Write};
+use std::time::{Duration, Instant};
+
+use super::{RxRing, TxRing};
+use crate::error::Error;
+
+/// An RX ring that implements [`Read`], blocking in `poll(2)` until a packet arrives.
+///
+/// Each `read` copies the payload of a single packet into the buffer and returns its
+/// length; a packet longer than the buffer is truncated to fit and the rest of it is
+/// discarded, as with a datagram socket. Packets are never merged, so a `read` never
+/// returns bytes from two frames. A zero-length packet reads as `Ok(0)`, which
+/// `Read` adapters such as `read_to_end` take for end of stream.
+///
+/// ```no_run
+/// use std::io::Read;
+/// use netmap_rs::{BlockingRxRing, NetmapBuilder};
+///
+/// let nm = NetmapBuilder::new("netmap:pipe{blocking_doc}").build()?;
+/// let mut rx = BlockingRxRing::new(nm.rx_ring(0)?);
+/// let mut buf = [0u8; 2048];
+/// let len = rx.read(&mut buf)?;
+/// println!("received {} bytes", len);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub struct BlockingRxRing<'a> {
+    ring: RxRing<'a>,
+    read_timeout: Option<Duration>,
+}
+
+impl<'a> BlockingRxRing<'a> {
+    /// wrap `ring`, waiting indefinitely in `read` until [`set_read_timeout`](Self::set_read_timeout) says otherwise
+    pub fn new(ring: RxRing<'a>) -> Self {
+        Self { ring, read_timeout: None }
+    }
+
+    /// limit how long `read` waits for a packet; `None` (the default) waits indefinitely
+    ///
+    /// A `read` that times out fails with `io::ErrorKind::TimedOut`.
+    pub fn set_read_timeout(&mut self, timeout: Option<Duration>) {
+        self.read_timeout = timeout;
+    }
+
+    /// the timeout set with [`set_read_timeout`](Self::set_read_timeout)
+    pub fn read_timeout(&self) -> Option<Duration> {
+        self.read_timeout
+    }
+
+    /// borrow the wrapped ring, e.g. to set a filter or receive without blocking
+    pub fn get_mut(&mut self) -> &mut RxRing<'a> {
+        &mut self.ring
+    }
+
+    /// unwrap the ring
+    pub fn into_inner(self) -> RxRing<'a> {
+        self.ring
+    }
+}
+
+impl Read for BlockingRxRing<'_> {
+    /// Waits for the next packet and copies up to `buf.len()` bytes of it into `buf`.
+    ///
+    /// # Errors
+    /// Fails with `io::ErrorKind::TimedOut` when the read timeout passes first (or at
+    /// once for a ring without a descriptor to wait on), and with the OS error if
+    /// syncing or polling the ring fails.
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let deadline = self.read_timeout.map(|timeout| Instant::now() + timeout);
+        let Some(frame) = self.ring.recv_until(deadline)? else {
+            return Err(io::Error::new(io::ErrorKind::TimedOut, "no packet arrived before the read timeout"));
+        };
+        let len = frame.len().min(buf.len());
+        buf[..len].copy_from_slice(&frame.payload()[..len]);
+        Ok(len)
+    }
+}
+
+/// A TX ring that implements [`Write`], each `write` queuing one packet.
+///
+/// `write` copies the whole buffer into the ring as a single packet (spread over
+/// `NS_MOREFRAG` slots if it exceeds one buffer) and returns its full length; it never
+/// writes part of a buffer. When the ring is full it waits for space as
+/// [`TxRing::send_blocking`] does. Queued packets go out on `flush`, which `txsync`s the
+/// ring, so wrap the ring in a `BufWriter` only if you mean to coalesce packets.
+///
+/// ```no_run
+/// use std::io::Write;
+/// use netmap_rs::{BlockingTxRing, NetmapBuilder};
+///
+/// let nm = NetmapBuilder::new("netmap:pipe{blocking_doc}").build()?;
+/// let mut tx = BlockingTxRing::new(nm.tx_ring(0)?);
+/// tx.write_all(b"one packet")?;
+/// tx.flush()?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub struct BlockingTxRing<'a> {
+    ring: TxRing<'a>,
+    write_timeout: Option<Duration>,
+}
+
+impl<'a> BlockingTxRing<'a> {
+    /// wrap `ring`, waiting indefinitely for space in `write` until [`set_write_timeout`](Self::set_write_timeout) says otherwise
+    pub fn new(ring: TxRing<'a>) -> Self {
+        Self { ring, write_timeout: None }
+    }
+
+    /// limit how long `write` waits for ring space; `None` (the default) waits indefinitely
+    ///
+    /// A `write` that times out fails with `io::ErrorKind::TimedOut`.
+    pub fn set_write_timeout(&mut self, timeout: Option<Duration>) {
+        self.write_timeout = timeout;
+    }
+
+    /// the timeout set with [`set_write_timeout`](Self::set_write_timeout)
+    pub fn write_timeout(&self) -> Option<Duration> {
+        self.write_timeout
+    }
+
+    /// borrow the wrapped ring
+    pub fn get_mut(&mut self) -> &mut TxRing<'a> {
+        &mut self.ring
+    }
+
+    /// unwrap the ring
+    pub fn into_inner(self) -> TxRing<'a> {
+        self.ring
+    }
+}
+
+impl Write for BlockingTxRing<'_> {
+    /// Queues `buf` as one packet and returns its length.
+    ///
+    /// # Errors
+    /// Fails with `io::ErrorKind::TimedOut` if the ring stays full past the write
+    /// timeout, `io::ErrorKind::InvalidInput` if `buf` is too large to fit in the ring
+    /// at all, and with the OS error if syncing or polling the ring fails.
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self.ring.send_blocking(buf, self.write_timeout) {
+            Ok(()) => Ok(buf.len()),
+            Err(Error::Timeout) => Err(io::Error::new(io::ErrorKind::TimedOut, "no ring space before the write timeout")),
+            Err(e @ Error::PacketTooLarge(_)) => Err(io::Error::new(io::ErrorKind::InvalidInput, e.to_string())),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Hands queued packets to the kernel (`txsync`).
+    fn flush(&mut self) -> io::Result<()> {
+        self.ring.try_sync().map_err(io::Error::from)
+    }
+}
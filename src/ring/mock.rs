@@ -0,0 +1,110 @@
+//! Heap-backed stand-in for a kernel `netmap_ring`, so ring logic can be unit-tested
+//! without a netmap device.
+//!
+//! The tests play the kernel's part by hand: they place packets in slots and move
+//! `tail` with [`MockRing::set_pointers`], then check what the ring handles did.
+
+use std::mem;
+use std::slice;
+
+use super::{RingDirection, RxRing, TxRing};
+use crate::ffi;
+
+/// Buffer size used by [`MockRing::new`], matching netmap's default.
+pub(crate) const MOCK_BUF_SIZE: usize = 2048;
+
+/// A `netmap_ring` with its slots and buffers allocated on the heap.
+pub(crate) struct MockRing {
+    // Boxed so the pointer handed to `TxRing`/`RxRing` stays put when the mock moves.
+    ring: Box<ffi::netmap_ring>,
+    slots: Vec<ffi::netmap_slot>,
+    bufs: Vec<Box<[u8]>>,
+}
+
+impl MockRing {
+    /// Create a ring of `num_slots` slots laid out like a freshly opened ring in `direction`:
+    /// a TX ring has every slot but one free, an RX ring has nothing received.
+    pub(crate) fn new(num_slots: u32, direction: RingDirection) -> Self {
+        let mut bufs: Vec<Box<[u8]>> = (0..num_slots)
+            .map(|_| vec![0u8; MOCK_BUF_SIZE].into_boxed_slice())
+            .collect();
+        let mut slots: Vec<ffi::netmap_slot> = bufs
+            .iter_mut()
+            .enumerate()
+            .map(|(i, buf)| {
+                // SAFETY: netmap_slot is a plain C struct, all-zero is a valid value.
+                let mut slot: ffi::netmap_slot = unsafe { mem::zeroed() };
+                slot.buf_idx = i as u32;
+                slot.buf = buf.as_mut_ptr() as *mut _;
+                slot
+            })
+            .collect();
+
+        // SAFETY: as above, netmap_ring is a plain C struct.
+        let mut ring: Box<ffi::netmap_ring> = Box::new(unsafe { mem::zeroed() });
+        ring.num_slots = num_slots;
+        ring.nr_buf_size = MOCK_BUF_SIZE as u32;
+        ring.slot = slots.as_mut_ptr();
+        if direction == RingDirection::Tx {
+            ring.flags = ffi::NR_TX as u16;
+            ring.tail = num_slots - 1;
+        }
+
+        Self { ring, slots, bufs }
+    }
+
+    /// Set the ring pointers, as the kernel (`tail`) or a previous user (`head`, `cur`) would.
+    pub(crate) fn set_pointers(&mut self, head: u32, cur: u32, tail: u32) {
+        self.ring.head = head;
+        self.ring.cur = cur;
+        self.ring.tail = tail;
+    }
+
+    /// Set the ring's `flags` field, e.g. `NR_FORWARD`.
+    pub(crate) fn set_ring_flags(&mut self, flags: u16) {
+        self.ring.flags = flags;
+    }
+
+    /// Current `(head, cur, tail)`.
+    pub(crate) fn pointers(&self) -> (u32, u32, u32) {
+        (self.ring.head, self.ring.cur, self.ring.tail)
+    }
+
+    /// Place a received packet in slot `idx`, as the kernel would on RX.
+    pub(crate) fn fill_slot(&mut self, idx: usize, data: &[u8]) {
+        self.bufs[idx][..data.len()].copy_from_slice(data);
+        self.slots[idx].len = data.len() as u16;
+    }
+
+    /// The bytes slot `idx` currently holds, as the kernel would read them on TX.
+    pub(crate) fn slot_data(&self, idx: usize) -> &[u8] {
+        let slot = &self.slots[idx];
+        // SAFETY: `buf` points into one of `self.bufs`, which are MOCK_BUF_SIZE long.
+        unsafe { slice::from_raw_parts(slot.buf as *const u8, slot.len as usize) }
+    }
+
+    /// Set the flags on slot `idx`, e.g. `NS_MOREFRAG` for a fragment the kernel received.
+    pub(crate) fn set_slot_flags(&mut self, idx: usize, flags: u16) {
+        self.slots[idx].flags = flags;
+    }
+
+    /// The flags userspace left on slot `idx`.
+    pub(crate) fn slot_flags(&self, idx: usize) -> u16 {
+        self.slots[idx].flags
+    }
+
+    /// The mocked `netmap_ring`, for building handles by hand.
+    pub(crate) fn as_ptr(&mut self) -> *mut ffi::netmap_ring {
+        &mut *self.ring
+    }
+
+    /// A TX ring handle over this mock.
+    pub(crate) fn tx_ring(&mut self) -> TxRing<'_> {
+        TxRing::new(&mut *self.ring, 0)
+    }
+
+    /// An RX ring handle over this mock.
+    pub(crate) fn rx_ring(&mut self) -> RxRing<'_> {
+        RxRing::new(&mut *self.ring, 0)
+    }
+}
@@ -0,0 +1,135 @@
+//! Background readiness watcher behind [`RxRing::poll_recv`](super::RxRing::poll_recv).
+//!
+//! Runtimes other than Tokio have no reactor that understands the netmap descriptor, so
+//! each ring that is polled while empty gets a small thread that `poll(2)`s a descriptor
+//! on its behalf and wakes the registered task once slots arrive on its ring beyond
+//! those the task has already seen.
+//!
+//! The descriptor is bound to the watched ring alone where the interface allows it. The
+//! `Netmap`'s own descriptor covers every ring, so it also becomes readable for packets
+//! on the others; the watcher then checks its ring's `tail` before waking and pauses
+//! between polls while it has not moved, instead of spinning. The same check keeps a
+//! ring holding only part of an `NS_MOREFRAG` chain from waking the task until the rest
+//! arrives.
+
+use std::io;
+use std::os::unix::io::{AsRawFd, RawFd};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::Waker;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use super::load_tail;
+use crate::ffi;
+use crate::netmap::Netmap;
+
+/// How long the watcher blocks in `poll` before rechecking whether it should stop.
+const WATCH_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+struct Shared {
+    waker: Mutex<Option<Waker>>,
+    seen_tail: AtomicU32, // `tail` when the waker was registered; the task is woken once it moves
+    stop: AtomicBool,
+}
+
+/// The watched RX ring, read by the watcher thread to tell its packets from other rings'.
+struct WatchedRing(*const ffi::netmap_ring);
+
+// Safety: the ring outlives the watcher thread, which the `FdWatcher` joins when the
+// `RxRing` holding it drops. The thread only reads `tail`.
+unsafe impl Send for WatchedRing {}
+
+/// Wakes the last registered [`Waker`] when an RX ring has packets.
+pub(crate) struct FdWatcher {
+    shared: Arc<Shared>,
+    thread: Option<JoinHandle<()>>,
+    _bound: Option<Netmap>, // Descriptor bound to the ring alone, polled instead of the shared one
+}
+
+impl FdWatcher {
+    /// Start watching `ring` through `bound`, a descriptor bound to that ring alone, or
+    /// else through `fd`. Nothing is woken until a waker is registered.
+    pub(crate) fn new(ring: *const ffi::netmap_ring, fd: RawFd, bound: Option<Netmap>) -> io::Result<Self> {
+        let shared = Arc::new(Shared {
+            waker: Mutex::new(None),
+            seen_tail: AtomicU32::new(0),
+            stop: AtomicBool::new(false),
+        });
+        let thread = {
+            let shared = Arc::clone(&shared);
+            let ring = WatchedRing(ring);
+            let fd = bound.as_ref().map_or(fd, Netmap::as_raw_fd);
+            thread::Builder::new()
+                .name("netmap-rx-watch".to_string())
+                .spawn(move || watch(&ring, fd, &shared))?
+        };
+        Ok(Self {
+            shared,
+            thread: Some(thread),
+            _bound: bound,
+        })
+    }
+
+    /// Wake `waker` once the ring's `tail` moves past `tail`, replacing any earlier registration.
+    pub(crate) fn register(&self, waker: &Waker, tail: u32) {
+        let mut slot = self.shared.waker.lock().unwrap_or_else(|e| e.into_inner());
+        self.shared.seen_tail.store(tail, Ordering::Relaxed);
+        match slot.as_ref() {
+            Some(registered) if registered.will_wake(waker) => {}
+            _ => *slot = Some(waker.clone()),
+        }
+        drop(slot);
+        if let Some(thread) = &self.thread {
+            thread.thread().unpark();
+        }
+    }
+
+    /// Returns `false` once the watcher has given up on an unusable descriptor.
+    pub(crate) fn is_alive(&self) -> bool {
+        self.thread.as_ref().is_some_and(|thread| !thread.is_finished())
+    }
+}
+
+impl Drop for FdWatcher {
+    fn drop(&mut self) {
+        self.shared.stop.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            thread.thread().unpark();
+            let _ = thread.join();
+        }
+    }
+}
+
+fn watch(ring: &WatchedRing, fd: RawFd, shared: &Shared) {
+    let timeout_ms = WATCH_POLL_INTERVAL.as_millis() as libc::c_int;
+    while !shared.stop.load(Ordering::Relaxed) {
+        if shared.waker.lock().unwrap_or_else(|e| e.into_inner()).is_none() {
+            thread::park_timeout(WATCH_POLL_INTERVAL);
+            continue;
+        }
+        let mut pfd = libc::pollfd {
+            fd,
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        // Polling also syncs the RX rings, so the woken task finds the new packets.
+        if unsafe { libc::poll(&mut pfd, 1, timeout_ms) } > 0 {
+            // Stop on an unusable descriptor; the woken task then sees the watcher is gone.
+            let failed = pfd.revents & (libc::POLLERR | libc::POLLNVAL) != 0;
+            if !failed && unsafe { load_tail(ring.0) } == shared.seen_tail.load(Ordering::Relaxed) {
+                // Readable for another ring's packets, or for slots the task has already
+                // seen: poll again later rather than at once.
+                thread::sleep(WATCH_POLL_INTERVAL);
+                continue;
+            }
+            let waker = shared.waker.lock().unwrap_or_else(|e| e.into_inner()).take();
+            if let Some(waker) = waker {
+                waker.wake();
+            }
+            if failed {
+                return;
+            }
+        }
+    }
+}
@@ -14,19 +14,11 @@
 //! - [`AsyncNetmapTxRing`]: Implements `tokio::io::AsyncWrite` for a Netmap TX ring,
 //!   allowing asynchronous packet transmission.
 //!
-//! # Important Considerations for Correctness:
-//! The current implementations of `AsyncRead::poll_read` and `AsyncWrite::poll_flush`
-//! (and by extension `poll_write`) have **placeholders for crucial Netmap synchronization
-//! operations** (specifically, `ioctl` calls with `NIOCRXSYNC` and `NIOCTXSYNC`).
-//! Without the correct and fully implemented `ioctl` calls in these methods,
-//! the async wrappers **will not function correctly** with the Netmap kernel module
-//! (e.g., new packets may not become visible on RX rings, or TX packets may not
-//! actually be sent by the NIC).
-//!
-//! **These synchronization points MUST be correctly implemented using the appropriate
-//! FFI constants and `libc::ioctl` calls for these wrappers to be reliable.**
-//! The complexity lies in ensuring the `ioctl`s are called with the correct arguments,
-//! which typically involve a pointer to a `struct nmreq`.
+//! # Synchronization
+//! `AsyncRead::poll_read` syncs with `NIOCRXSYNC` before every read and consumes packets
+//! from the ring's `head`, like the synchronous [`RxRing::recv`](crate::RxRing::recv).
+//! `AsyncWrite::poll_flush` issues `NIOCTXSYNC`; `poll_write` itself does not sync, so
+//! a full TX ring only drains once the writer flushes (or the kernel polls the ring).
 //!
 //! # Example Usage (Conceptual)
 //! ```no_run
@@ -66,15 +58,19 @@
 
 use crate::error::Error as NetmapError;
 use crate::ffi;
+use crate::frame::Frame;
 use crate::netmap::Netmap;
+use crate::ring::{load_tail, publish_head};
 use std::io;
 use std::os::unix::io::AsRawFd;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
+use futures_core::Stream;
 use tokio::io::unix::AsyncFd;
-use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, ReadBuf};
 
+/// A [`Netmap`] registered with Tokio's reactor, handing out async ring wrappers.
 #[derive(Debug)]
 pub struct TokioNetmap {
     async_fd_netmap: Arc<AsyncFd<Netmap>>,
@@ -107,13 +103,13 @@ impl TokioNetmap {
     /// # Errors
     /// Returns `NetmapError::InvalidRingIndex` if the `ring_idx` is out of bounds.
     pub fn rx_ring(&self, ring_idx: usize) -> Result<AsyncNetmapRxRing, NetmapError> {
-        let netmap_instance = self.async_fd_netmap.get_ref();
-        if ring_idx >= netmap_instance.num_rx_rings() {
-            return Err(NetmapError::InvalidRingIndex(ring_idx));
-        }
-        // Safety: Netmap guarantees nifp and rings are valid if open succeeded.
         // The lifetime of ring_ptr is tied to Netmap within AsyncFd, managed by Arc.
-        let ring_ptr = unsafe { ffi::NETMAP_RXRING((*netmap_instance.desc).nifp, ring_idx as u32) };
+        // Index the rings the descriptor is bound to, as `Netmap::rx_ring` does.
+        let ring_ptr = self
+            .async_fd_netmap
+            .get_ref()
+            .rx_ring_ptr(ring_idx)
+            .ok_or(NetmapError::InvalidRingIndex(ring_idx))?;
 
         Ok(AsyncNetmapRxRing {
             shared_fd_netmap: Arc::clone(&self.async_fd_netmap),
@@ -131,12 +127,11 @@ impl TokioNetmap {
     /// # Errors
     /// Returns `NetmapError::InvalidRingIndex` if the `ring_idx` is out of bounds.
     pub fn tx_ring(&self, ring_idx: usize) -> Result<AsyncNetmapTxRing, NetmapError> {
-        let netmap_instance = self.async_fd_netmap.get_ref();
-        if ring_idx >= netmap_instance.num_tx_rings() {
-            return Err(NetmapError::InvalidRingIndex(ring_idx));
-        }
-        // Safety: See rx_ring.
-        let ring_ptr = unsafe { ffi::NETMAP_TXRING((*netmap_instance.desc).nifp, ring_idx as u32) };
+        let ring_ptr = self
+            .async_fd_netmap
+            .get_ref()
+            .tx_ring_ptr(ring_idx)
+            .ok_or(NetmapError::InvalidRingIndex(ring_idx))?;
 
         Ok(AsyncNetmapTxRing {
             shared_fd_netmap: Arc::clone(&self.async_fd_netmap),
@@ -151,9 +146,7 @@ impl TokioNetmap {
 /// manner when used within a Tokio runtime. It shares an `AsyncFd<Netmap>` with
 /// other ring wrappers from the same `TokioNetmap` instance.
 ///
-/// **Note:** The correct functioning of this `AsyncRead` implementation relies heavily
-/// on the proper, currently placeholder, implementation of `NIOCRXSYNC` ioctl calls
-/// within its `poll_read` method for synchronizing with the Netmap kernel module.
+/// Every read returns at most one packet, truncated to the buffer if it is longer.
 #[derive(Debug)]
 pub struct AsyncNetmapRxRing {
     shared_fd_netmap: Arc<AsyncFd<Netmap>>,
@@ -162,100 +155,166 @@ pub struct AsyncNetmapRxRing {
 unsafe impl Send for AsyncNetmapRxRing {}
 // unsafe impl Sync for AsyncNetmapRxRing {} // Sync is tricky with raw ptr mutation if methods were &self
 
+impl AsyncNetmapRxRing {
+    /// Receives the next packet together with its arrival time in nanoseconds.
+    ///
+    /// The timestamp is read from `CLOCK_MONOTONIC` as soon as the ring yields the
+    /// packet, so differences between timestamps measure inter-arrival times and they
+    /// can be compared with other monotonic clock readings for latency measurement.
+    ///
+    /// Netmap does not expose per-packet hardware timestamps (the ring's `ts` field is
+    /// only updated once per sync), so this is always a software timestamp.
+    pub async fn recv_packet_timed(&mut self) -> io::Result<(Vec<u8>, u64)> {
+        let max_payload = unsafe { (*self.ring_ptr).nr_buf_size } as usize;
+        let mut packet = vec![0u8; max_payload];
+        let len = self.read(&mut packet).await?;
+        let timestamp_ns = monotonic_now_ns();
+        packet.truncate(len);
+        Ok((packet, timestamp_ns))
+    }
+}
+
+/// Current `CLOCK_MONOTONIC` time in nanoseconds.
+fn monotonic_now_ns() -> u64 {
+    let mut ts = libc::timespec { tv_sec: 0, tv_nsec: 0 };
+    // CLOCK_MONOTONIC is always available on the platforms netmap supports.
+    unsafe { libc::clock_gettime(libc::CLOCK_MONOTONIC, &mut ts) };
+    ts.tv_sec as u64 * 1_000_000_000 + ts.tv_nsec as u64
+}
+
 impl AsyncRead for AsyncNetmapRxRing {
-    /// Attempts to read data from the Netmap RX ring into `buf`.
+    /// Attempts to read one packet from the Netmap RX ring into `buf`.
     ///
     /// This method integrates with Tokio's event loop. It will:
-    /// 1. Attempt to synchronize the ring with the kernel (currently a placeholder for `NIOCRXSYNC ioctl`).
-    /// 2. Check for available packets in the ring.
-    /// 3. If packets are available, copy one packet's data into `buf` and advance the ring.
-    /// 4. If no packets are available, it registers the current task for wakeup
-    ///    when the underlying Netmap file descriptor becomes readable and returns `Poll::Pending`.
+    /// 1. Synchronize the ring with the kernel (`NIOCRXSYNC`), exposing newly received
+    ///    packets and returning consumed slots.
+    /// 2. If a packet is available at `head`, copy up to `buf.remaining()` bytes of it into
+    ///    `buf` (discarding the rest) and release its slot by advancing `head` and `cur`.
+    ///    `tail` belongs to the kernel and is never written.
+    /// 3. If no packets are available, register the current task for wakeup when the
+    ///    underlying Netmap file descriptor becomes readable and return `Poll::Pending`.
     ///
-    /// **Critical Note:** The synchronization step (ioctl) is crucial and currently
-    /// simplified in this draft. It must be correctly implemented for this method
-    /// to function reliably.
+    /// Each call reads at most one packet. A zero-length packet, such as the close
+    /// sentinel of a dropped pipe endpoint, reads as 0 bytes, i.e. end of stream.
     fn poll_read(
         self: Pin<&mut Self>,
         cx: &mut Context<'_>,
         buf: &mut ReadBuf<'_>,
     ) -> Poll<io::Result<()>> {
-        let self_mut = self.get_mut();
+        if buf.remaining() == 0 {
+            return Poll::Ready(Ok(()));
+        }
+        self.get_mut().poll_packet(cx, |packet| {
+            let len_to_copy = packet.len().min(buf.remaining());
+            buf.put_slice(&packet[..len_to_copy]);
+        })
+    }
+}
+
+impl AsyncNetmapRxRing {
+    /// Turns the ring into a [`Stream`] yielding one owned frame per received packet.
+    ///
+    /// Unlike reading through `AsyncRead`, packet boundaries are kept and no buffer has to
+    /// be sized up front:
+    ///
+    /// ```no_run
+    /// # async fn run(rx: netmap_rs::tokio_async::AsyncNetmapRxRing) -> Result<(), netmap_rs::Error> {
+    /// use futures_util::StreamExt;
+    ///
+    /// let mut frames = rx.frames();
+    /// while let Some(frame) = frames.next().await {
+    ///     println!("received {} bytes", frame?.len());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn frames(self) -> FrameStream {
+        FrameStream { ring: self, done: false }
+    }
+
+    /// Waits for the packet at `head`, hands its payload to `consume` and releases its slot.
+    ///
+    /// Syncs with `NIOCRXSYNC` first and registers for readiness if the ring is empty.
+    fn poll_packet<T>(&mut self, cx: &mut Context<'_>, consume: impl FnOnce(&[u8]) -> T) -> Poll<io::Result<T>> {
         loop {
-            // 1. Synchronize the ring with the kernel. This is crucial for Netmap.
-            // We call NIOCRXSYNC on the main Netmap file descriptor.
-            // This should update the userspace view of all RX rings managed by this descriptor.
-            // The netmap(4) man page suggests `ioctl(fd, NIOCRXSYNC)` can be used.
-            // The third argument for _IOWR ioctls is a pointer to the type specified.
-            // For a general sync on the FD, often a NULL pointer is passed if the specific
-            // content of struct nmreq isn't needed for this particular operation on the main FD.
-            // However, to be safe and align with how Netmap often uses nmreq for context,
-            // passing a minimal (e.g. zeroed) nmreq might be more robust if the kernel driver
-            // dereferences the pointer. For NIOCRXSYNC/NIOCTXSYNC on the main port fd,
-            // the kernel uses the nifp from the fd directly.
-            // Let's try with 0 as the argument first, as per common simple ioctl usage for commands.
-            // If this fails (e.g. EFAULT), we'll need to pass a pointer to a dummy nmreq.
+            // NIOCRXSYNC on the descriptor syncs every RX ring it is bound to; the kernel
+            // takes the rings from the descriptor and ignores the argument.
             unsafe {
-                let fd = self_mut.shared_fd_netmap.get_ref().as_raw_fd();
-                let ret = libc::ioctl(fd, ffi::NIOCRXSYNC as libc::c_ulong, 0 as *mut ffi::nmreq);
+                let fd = self.shared_fd_netmap.get_ref().as_raw_fd();
+                let ret = libc::ioctl(fd, ffi::NIOCRXSYNC as libc::c_ulong, std::ptr::null_mut::<ffi::nmreq>());
                 if ret == -1 {
-                    // If ioctl fails, it's an OS error. Return it.
                     return Poll::Ready(Err(io::Error::last_os_error()));
                 }
             }
 
-            let ring = unsafe { &*self_mut.ring_ptr };
-            // Ring pointers (head, tail, cur) should now be updated by the kernel side
-            // due to NIOCRXSYNC. Our logic below uses these updated values.
-            let mut head = ring.head;
-            let mut tail = ring.tail;
-            let num_slots = ring.num_slots;
-
+            let ring = self.ring_ptr;
+            // Userspace owns [head, tail): packets are consumed from head, never from tail.
+            let (head, tail, num_slots) = unsafe { ((*ring).head, load_tail(ring), (*ring).num_slots) };
             if head == tail {
-                match self_mut.shared_fd_netmap.poll_read_ready_mut(cx) {
+                match self.shared_fd_netmap.poll_read_ready(cx) {
                     Poll::Ready(Ok(mut ready_guard)) => {
+                        // Readable again: sync and look at the ring once more. If that turns
+                        // up nothing, the cleared readiness makes the next poll register the waker.
                         ready_guard.clear_ready();
-                        // Re-check after poll indicated readiness
-                        // Placeholder for proper sync via ioctl
-                        // unsafe { let fd = self_mut.shared_fd_netmap.get_ref().as_raw_fd(); libc::ioctl(fd, ffi::NIOCRXSYNC as _, self_mut.ring_ptr); }
-                        let updated_ring = unsafe { &*self_mut.ring_ptr };
-                        head = updated_ring.head;
-                        if head == tail { return Poll::Pending; }
+                        continue;
                     }
                     Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
                     Poll::Pending => return Poll::Pending,
                 }
             }
-            // Process packet if head != tail
-            let current_slot_idx = tail % num_slots;
-            let slot = unsafe { &*ring.slot.add(current_slot_idx as usize) };
-            let packet_len = slot.len as usize;
 
-            if packet_len == 0 || buf.remaining() == 0 {
-                unsafe {
-                    let mutable_ring = &mut *self_mut.ring_ptr;
-                    let new_tail = (tail + 1) % num_slots;
-                    mutable_ring.cur = new_tail;
-                    mutable_ring.tail = new_tail;
-                }
-                return Poll::Ready(Ok(()));
-            }
+            let consumed = unsafe {
+                let slot = &*(*ring).slot.add((head % num_slots) as usize);
+                let consumed = consume(std::slice::from_raw_parts(slot.buf as *const u8, slot.len as usize));
+                publish_head(ring, (head + 1) % num_slots);
+                consumed
+            };
+            return Poll::Ready(Ok(consumed));
+        }
+    }
+}
 
-            let len_to_copy = std::cmp::min(packet_len, buf.remaining());
-            let packet_data = unsafe { std::slice::from_raw_parts(slot.buf as *const u8, len_to_copy) };
-            buf.put_slice(packet_data);
+/// A [`Stream`] of received packets, created by [`AsyncNetmapRxRing::frames`].
+///
+/// Yields one owned [`Frame`] per packet, or `Error::Io` if syncing or waiting on the
+/// descriptor fails. A zero-length packet, such as the close sentinel sent when a pipe
+/// endpoint is dropped, ends the stream, as it ends an `AsyncRead` with a 0-byte read.
+#[derive(Debug)]
+pub struct FrameStream {
+    ring: AsyncNetmapRxRing,
+    done: bool, // A zero-length packet ended the stream
+}
 
-            unsafe {
-                let mutable_ring = &mut *self_mut.ring_ptr;
-                let new_tail = (tail + 1) % num_slots;
-                mutable_ring.cur = new_tail;
-                mutable_ring.tail = new_tail;
+impl FrameStream {
+    /// Gives back the underlying ring.
+    pub fn into_inner(self) -> AsyncNetmapRxRing {
+        self.ring
+    }
+}
+
+impl Stream for FrameStream {
+    type Item = Result<Frame<'static>, NetmapError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let self_mut = self.get_mut();
+        if self_mut.done {
+            return Poll::Ready(None);
+        }
+        match self_mut.ring.poll_packet(cx, |packet| packet.to_vec()) {
+            Poll::Ready(Ok(packet)) if packet.is_empty() => {
+                self_mut.done = true;
+                Poll::Ready(None)
             }
-            return Poll::Ready(Ok(()));
+            Poll::Ready(Ok(packet)) => Poll::Ready(Some(Ok(Frame::new_owned(packet)))),
+            Poll::Ready(Err(e)) => Poll::Ready(Some(Err(NetmapError::Io(e)))),
+            Poll::Pending => Poll::Pending,
         }
     }
 }
 
+/// An asynchronous wrapper for a Netmap TX ring, implementing `tokio::io::AsyncWrite`.
+///
+/// Every write queues one packet; `flush` hands queued packets to the kernel.
 #[derive(Debug)]
 pub struct AsyncNetmapTxRing {
     shared_fd_netmap: Arc<AsyncFd<Netmap>>,
@@ -290,7 +349,7 @@ impl AsyncWrite for AsyncNetmapTxRing {
         loop {
             let ring = unsafe { &*self_mut.ring_ptr };
             let head = ring.head;
-            let tail = ring.tail;
+            let tail = unsafe { load_tail(self_mut.ring_ptr) };
             let num_slots = ring.num_slots;
             let max_payload = ring.nr_buf_size as usize;
 
@@ -299,7 +358,7 @@ impl AsyncWrite for AsyncNetmapTxRing {
             let is_full = (head + 1) % num_slots == tail;
 
             if is_full {
-                match self_mut.shared_fd_netmap.poll_write_ready_mut(cx) {
+                match self_mut.shared_fd_netmap.poll_write_ready(cx) {
                     Poll::Ready(Ok(mut ready_guard)) => {
                         ready_guard.clear_ready();
                         // FD is ready (space might be available). Loop to try writing again.
@@ -334,12 +393,7 @@ impl AsyncWrite for AsyncNetmapTxRing {
 
                 // Advance our head pointer
                 // Safety: ring_ptr is valid.
-                unsafe {
-                    let mutable_ring = &mut *self_mut.ring_ptr;
-                    let new_head = (head + 1) % num_slots;
-                    mutable_ring.head = new_head;
-                    mutable_ring.cur = new_head; // cur usually follows head in TX
-                }
+                unsafe { publish_head(self_mut.ring_ptr, (head + 1) % num_slots) };
                 return Poll::Ready(Ok(buf.len())); // Successfully wrote one packet
             }
         }
@@ -361,7 +415,7 @@ impl AsyncWrite for AsyncNetmapTxRing {
         unsafe {
             let self_mut = self.get_mut(); // Pin::get_mut is safe within poll_ methods if not moving self_mut
             let fd = self_mut.shared_fd_netmap.get_ref().as_raw_fd();
-            let ret = libc::ioctl(fd, ffi::NIOCTXSYNC as libc::c_ulong, 0 as *mut ffi::nmreq);
+            let ret = libc::ioctl(fd, ffi::NIOCTXSYNC as libc::c_ulong, std::ptr::null_mut::<ffi::nmreq>());
             if ret == -1 {
                 return Poll::Ready(Err(io::Error::last_os_error()));
             }
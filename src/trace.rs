@@ -0,0 +1,65 @@
+//! Bounded in-memory packet trace for post-mortem debugging.
+
+use std::collections::VecDeque;
+use std::time::Instant;
+
+use crate::frame::Frame;
+
+/// A bounded buffer holding copies of the most recently recorded packets
+/// together with the time they were recorded.
+///
+/// Once `capacity` packets are held, recording another one evicts the oldest.
+#[derive(Debug, Clone)]
+pub struct PacketTrace {
+    entries: VecDeque<(Instant, Vec<u8>)>,
+    capacity: usize,
+}
+
+impl PacketTrace {
+    /// Create an empty trace retaining at most `capacity` packets.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Record a copy of `payload`, timestamped with the current instant.
+    pub fn record(&mut self, payload: &[u8]) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back((Instant::now(), payload.to_vec()));
+    }
+
+    /// Maximum number of packets retained.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Number of packets currently retained.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check if no packets have been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Discard all recorded packets.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Copy out the retained packets, oldest first.
+    pub fn dump(&self) -> Vec<(Instant, Frame<'static>)> {
+        self.entries
+            .iter()
+            .map(|(ts, data)| (*ts, Frame::new_owned(data.clone())))
+            .collect()
+    }
+}
@@ -0,0 +1,235 @@
+#![cfg(feature = "sys")]
+
+//! Management helpers for VALE software switches.
+
+use std::fs::OpenOptions;
+use std::os::unix::io::AsRawFd;
+
+use crate::error::Error;
+use crate::ffi;
+
+/// Kernel-side polling mode for a VALE switch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ValePolling {
+    /// No kernel polling threads; rings are only serviced on `sync`/`poll` (the default).
+    #[default]
+    Disabled,
+    /// One kernel thread per CPU, each servicing a single ring (`NR_REG_ONE_NIC`).
+    PerRing {
+        /// First CPU to pin a polling thread to.
+        first_cpu: u16,
+        /// Number of CPUs (and therefore threads) to use.
+        num_cpus: u16,
+    },
+    /// Kernel threads share all rings of the switch port (`NR_REG_ALL_NIC`).
+    AllRings {
+        /// First CPU to pin a polling thread to.
+        first_cpu: u16,
+        /// Number of CPUs (and therefore threads) to use.
+        num_cpus: u16,
+    },
+}
+
+/// Tunables applied to a VALE switch by [`configure`].
+///
+/// Each field maps onto the `struct nmreq` passed to the `NIOCCONFIG` ioctl:
+///
+/// | setting        | `nmreq` member                                                                  |
+/// |----------------|---------------------------------------------------------------------------------|
+/// | switch name    | `nr_name`                                                                       |
+/// | `hash_buckets` | `nr_arg3`                                                                       |
+/// | `polling`      | `nr_arg2` (1 = on, 0 = off), `nr_flags` (mode), `nr_ringid` (first CPU), `nr_arg1` (CPU count) |
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ValeConfig {
+    /// Number of buckets in the switch's MAC learning table. `0` keeps the kernel default.
+    pub hash_buckets: u32,
+    /// Kernel polling mode for the switch.
+    pub polling: ValePolling,
+}
+
+impl ValeConfig {
+    fn to_nmreq(self, switch: &str) -> Result<ffi::nmreq, Error> {
+        if switch.len() >= ffi::IFNAMSIZ as usize {
+            return Err(Error::BindFail(format!(
+                "VALE switch name '{}' is too long.",
+                switch
+            )));
+        }
+
+        // Safety: nmreq is a plain C struct for which all-zeroes is a valid value.
+        let mut req: ffi::nmreq = unsafe { std::mem::zeroed() };
+        for (i, byte) in switch.bytes().enumerate() {
+            req.nr_name[i] = byte as i8;
+        }
+        req.nr_version = ffi::NETMAP_API as u16;
+        req.nr_arg3 = self.hash_buckets;
+
+        match self.polling {
+            ValePolling::Disabled => req.nr_arg2 = 0,
+            ValePolling::PerRing { first_cpu, num_cpus } => {
+                req.nr_arg2 = 1;
+                req.nr_flags = ffi::NR_REG_ONE_NIC;
+                req.nr_ringid = first_cpu;
+                req.nr_arg1 = num_cpus;
+            }
+            ValePolling::AllRings { first_cpu, num_cpus } => {
+                req.nr_arg2 = 1;
+                req.nr_flags = ffi::NR_REG_ALL_NIC;
+                req.nr_ringid = first_cpu;
+                req.nr_arg1 = num_cpus;
+            }
+        }
+
+        Ok(req)
+    }
+}
+
+/// Applies `config` to the VALE switch named `switch` (e.g. "vale0:").
+///
+/// This opens `/dev/netmap` and issues a `NIOCCONFIG` ioctl; see [`ValeConfig`]
+/// for how each setting is encoded. Usually requires root.
+///
+/// # Errors
+/// Returns `Error::BindFail` if the switch name does not fit in `nr_name`, and
+/// `Error::Io` if `/dev/netmap` cannot be opened or the kernel rejects the request.
+///
+/// # Example
+/// ```no_run
+/// use netmap_rs::vale::{self, ValeConfig, ValePolling};
+///
+/// vale::configure("vale0:", ValeConfig {
+///     hash_buckets: 1024,
+///     polling: ValePolling::AllRings { first_cpu: 2, num_cpus: 1 },
+/// })?;
+/// # Ok::<(), netmap_rs::Error>(())
+/// ```
+pub fn configure(switch: &str, config: ValeConfig) -> Result<(), Error> {
+    let mut req = config.to_nmreq(switch)?;
+    let dev = OpenOptions::new().read(true).write(true).open("/dev/netmap")?;
+
+    let ret = unsafe {
+        libc::ioctl(
+            dev.as_raw_fd(),
+            ffi::NIOCCONFIG as libc::c_ulong,
+            &mut req as *mut ffi::nmreq,
+        )
+    };
+    if ret == -1 {
+        return Err(Error::Io(std::io::Error::last_os_error()));
+    }
+    Ok(())
+}
+
+/// `NETMAP_REQ_VALE_POLLING_ENABLE` from <net/netmap.h>.
+const NETMAP_REQ_VALE_POLLING_ENABLE: u16 = 10;
+/// `NETMAP_REQ_VALE_POLLING_DISABLE` from <net/netmap.h>.
+const NETMAP_REQ_VALE_POLLING_DISABLE: u16 = 11;
+/// `NETMAP_POLLING_MODE_SINGLE_CPU` from <net/netmap.h>.
+const NETMAP_POLLING_MODE_SINGLE_CPU: u32 = 1;
+/// `NETMAP_POLLING_MODE_MULTI_CPU` from <net/netmap.h>.
+const NETMAP_POLLING_MODE_MULTI_CPU: u32 = 2;
+/// `NETMAP_REQ_IFNAMSIZ` from <net/netmap.h>.
+const NETMAP_REQ_IFNAMSIZ: usize = 64;
+/// `NIOCCTRL`, i.e. `_IOWR('i', 151, struct nmreq_header)`.
+const NIOCCTRL: libc::c_ulong = 0xc000_6997 | ((std::mem::size_of::<NmreqHeader>() as libc::c_ulong) << 16);
+
+/// `struct nmreq_header` from <net/netmap.h>, the header of every `NIOCCTRL` request.
+#[repr(C)]
+struct NmreqHeader {
+    nr_version: u16,
+    nr_reqtype: u16,
+    nr_reserved: u32,
+    nr_name: [u8; NETMAP_REQ_IFNAMSIZ],
+    nr_options: u64,
+    nr_body: u64,
+}
+
+/// `struct nmreq_vale_polling` from <net/netmap.h>, the body of both the enable and the
+/// disable request.
+#[repr(C)]
+#[derive(Default)]
+struct NmreqValePolling {
+    nr_mode: u32,
+    nr_first_cpu_id: u32,
+    nr_num_polling_cpus: u32,
+    pad1: u32,
+}
+
+/// Issues a `NIOCCTRL` request of type `reqtype` for `port`, with `body` as the request body.
+fn vale_ctrl(port: &str, reqtype: u16, body: &mut NmreqValePolling) -> Result<(), Error> {
+    if port.len() >= NETMAP_REQ_IFNAMSIZ {
+        return Err(Error::BindFail(format!("VALE port name '{}' is too long.", port)));
+    }
+
+    let mut hdr = NmreqHeader {
+        nr_version: ffi::NETMAP_API as u16,
+        nr_reqtype: reqtype,
+        nr_reserved: 0,
+        nr_name: [0; NETMAP_REQ_IFNAMSIZ],
+        nr_options: 0,
+        nr_body: body as *mut NmreqValePolling as u64,
+    };
+    hdr.nr_name[..port.len()].copy_from_slice(port.as_bytes());
+
+    let dev = OpenOptions::new().read(true).write(true).open("/dev/netmap")?;
+    let ret = unsafe { libc::ioctl(dev.as_raw_fd(), NIOCCTRL, &mut hdr as *mut NmreqHeader) };
+    if ret == -1 {
+        return Err(Error::Io(std::io::Error::last_os_error()));
+    }
+    Ok(())
+}
+
+/// Starts netmap's kernel polling threads for the VALE port `port` (e.g. "vale0:eth1").
+///
+/// Normally a switch only moves packets when some process syncs or polls one of its
+/// ports. With polling enabled, kernel threads pinned to the CPUs `polling` names service
+/// the port's rings continuously, so a NIC attached to a switch forwards with no
+/// userspace loop at all and with lower latency. The price is those CPUs spinning at
+/// 100% whether or not traffic flows. netmap only polls NICs attached to a switch
+/// (`vale-ctl -a vale0:eth1`); virtual ports are refused.
+///
+/// `ValePolling::AllRings` runs a single thread for every ring of the port, so
+/// `num_cpus` must be 1. `ValePolling::PerRing` runs one thread per ring, so `num_cpus`
+/// must equal the port's ring count. Passing `ValePolling::Disabled` is the same as
+/// calling [`disable_polling`].
+///
+/// Issues `NIOCCTRL` with `NETMAP_REQ_VALE_POLLING_ENABLE`, which needs root (or
+/// `CAP_NET_ADMIN`) to open `/dev/netmap`.
+///
+/// # Errors
+/// Returns `Error::BindFail` if the port name is too long, and `Error::Io` if
+/// `/dev/netmap` cannot be opened or the kernel rejects the request: for a virtual
+/// port, a CPU range it cannot use, or a port that is already being polled.
+///
+/// # Example
+/// ```no_run
+/// use netmap_rs::vale::{self, ValePolling};
+///
+/// vale::enable_polling("vale0:eth1", ValePolling::AllRings { first_cpu: 3, num_cpus: 1 })?;
+/// // ... eth1 and the switch's other ports now exchange traffic without a userspace loop ...
+/// vale::disable_polling("vale0:eth1")?;
+/// # Ok::<(), netmap_rs::Error>(())
+/// ```
+pub fn enable_polling(port: &str, polling: ValePolling) -> Result<(), Error> {
+    let (nr_mode, first_cpu, num_cpus) = match polling {
+        ValePolling::Disabled => return disable_polling(port),
+        ValePolling::PerRing { first_cpu, num_cpus } => (NETMAP_POLLING_MODE_MULTI_CPU, first_cpu, num_cpus),
+        ValePolling::AllRings { first_cpu, num_cpus } => (NETMAP_POLLING_MODE_SINGLE_CPU, first_cpu, num_cpus),
+    };
+    let mut body = NmreqValePolling {
+        nr_mode,
+        nr_first_cpu_id: first_cpu.into(),
+        nr_num_polling_cpus: num_cpus.into(),
+        pad1: 0,
+    };
+    vale_ctrl(port, NETMAP_REQ_VALE_POLLING_ENABLE, &mut body)
+}
+
+/// Stops the kernel polling threads [`enable_polling`] started for `port`.
+///
+/// # Errors
+/// As for [`enable_polling`]; the kernel also fails the request if `port` is not being polled.
+pub fn disable_polling(port: &str) -> Result<(), Error> {
+    // netmap sizes the disable request like the enable one and rejects it without a body.
+    vale_ctrl(port, NETMAP_REQ_VALE_POLLING_DISABLE, &mut NmreqValePolling::default())
+}
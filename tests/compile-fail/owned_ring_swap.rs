@@ -0,0 +1,14 @@
+use std::mem;
+
+use netmap_rs::NetmapBuilder;
+
+fn main() {
+    let first = NetmapBuilder::new("vale_test_a").build().unwrap().into_shared();
+    let second = NetmapBuilder::new("vale_test_b").build().unwrap().into_shared();
+
+    // Swapping the rings inside two owned handles would leave each handle releasing
+    // the busy flag of a ring it does not hold.
+    let a = first.tx_ring(0).unwrap();
+    let b = second.tx_ring(0).unwrap();
+    mem::swap(&mut *a, &mut *b);
+}
@@ -0,0 +1,13 @@
+use netmap_rs::{Frame, NetmapBuilder};
+
+fn main() {
+    let nm = NetmapBuilder::new("vale_test_a").build().unwrap();
+    let mut batch: Vec<Frame> = (0..4).map(|_| Frame::new(&[])).collect();
+
+    // Frames from recv_batch borrow ring slots, so they must not outlive the ring.
+    {
+        let mut rx = nm.rx_ring(0).unwrap();
+        rx.recv_batch(&mut batch);
+    }
+    println!("{:?}", batch[0].payload());
+}
@@ -0,0 +1,11 @@
+use netmap_rs::NetmapBuilder;
+
+fn main() {
+    let mut nm = NetmapBuilder::new("vale_test_a").build().unwrap();
+
+    // Two live mutable handles to the same TX ring must be rejected.
+    let mut first = nm.tx_ring_mut(0).unwrap();
+    let mut second = nm.tx_ring_mut(0).unwrap();
+    first.send(b"first").unwrap();
+    second.send(b"second").unwrap();
+}
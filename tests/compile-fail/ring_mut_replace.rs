@@ -0,0 +1,13 @@
+use std::mem;
+
+use netmap_rs::NetmapBuilder;
+
+fn main() {
+    let mut nm = NetmapBuilder::new("vale_test_a").build().unwrap();
+    let other = NetmapBuilder::new("vale_test_b").build().unwrap();
+
+    // The handles stored in `nm` must not be replaced by rings of another interface,
+    // which would dangle once `other` is dropped.
+    *nm.tx_ring_mut(0).unwrap() = other.tx_ring(0).unwrap();
+    mem::swap(&mut *nm.rx_ring_mut(0).unwrap(), &mut other.rx_ring(0).unwrap());
+}
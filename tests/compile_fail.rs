@@ -0,0 +1,8 @@
+#![cfg(all(unix, feature = "sys"))]
+
+// Compile-fail checks for borrow rules the public API is expected to enforce.
+#[test]
+fn test_ring_mut_borrows_are_exclusive() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/compile-fail/*.rs");
+}
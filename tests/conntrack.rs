@@ -0,0 +1,71 @@
+use netmap_rs::conntrack::{ConnTrack, FiveTuple, FlowState};
+use netmap_rs::packet::ETH_HEADER_LEN;
+use netmap_rs::Frame;
+use std::net::SocketAddrV4;
+use std::thread::sleep;
+use std::time::Duration;
+
+const IP_HEADER_LEN: usize = 20;
+const UDP_HEADER_LEN: usize = 8;
+
+// Builds an Ethernet/IPv4/UDP frame from `src` to `dst` with a 4-byte payload.
+fn udp_frame(src: SocketAddrV4, dst: SocketAddrV4) -> Vec<u8> {
+    let mut frame = vec![0u8; ETH_HEADER_LEN + IP_HEADER_LEN + UDP_HEADER_LEN + 4];
+    frame[12..14].copy_from_slice(&0x0800u16.to_be_bytes());
+
+    let ip = &mut frame[ETH_HEADER_LEN..];
+    ip[0] = 0x45;
+    let total_len = (IP_HEADER_LEN + UDP_HEADER_LEN + 4) as u16;
+    ip[2..4].copy_from_slice(&total_len.to_be_bytes());
+    ip[8] = 64;
+    ip[9] = 17;
+    ip[12..16].copy_from_slice(&src.ip().octets());
+    ip[16..20].copy_from_slice(&dst.ip().octets());
+
+    let udp = &mut ip[IP_HEADER_LEN..];
+    udp[0..2].copy_from_slice(&src.port().to_be_bytes());
+    udp[2..4].copy_from_slice(&dst.port().to_be_bytes());
+    udp[4..6].copy_from_slice(&((UDP_HEADER_LEN + 4) as u16).to_be_bytes());
+    frame
+}
+
+#[test]
+fn test_five_tuple_parses_udp_and_skips_other_frames() {
+    let client: SocketAddrV4 = "10.0.0.1:4000".parse().unwrap();
+    let server: SocketAddrV4 = "10.0.0.2:53".parse().unwrap();
+
+    let tuple = FiveTuple::from_frame(&udp_frame(client, server)).unwrap();
+    assert_eq!((tuple.src, tuple.dst, tuple.protocol), (client, server, 17));
+    assert_eq!(tuple.reversed().src, server);
+
+    assert!(FiveTuple::from_frame(&[0u8; 60]).is_none(), "non-IPv4 frames have no 5-tuple");
+}
+
+#[test]
+fn test_bidirectional_udp_is_one_flow_that_expires() {
+    let timeout = Duration::from_millis(50);
+    let client: SocketAddrV4 = "10.0.0.1:4000".parse().unwrap();
+    let server: SocketAddrV4 = "10.0.0.2:53".parse().unwrap();
+    let request = udp_frame(client, server);
+    let reply = udp_frame(server, client);
+
+    let mut conntrack = ConnTrack::new(timeout);
+    assert_eq!(conntrack.track(&Frame::new(&request)), FlowState::New);
+    assert_eq!(conntrack.track(&Frame::new(&request)), FlowState::New);
+    assert_eq!(conntrack.track(&Frame::new(&reply)), FlowState::Established);
+    assert_eq!(conntrack.track(&Frame::new(&request)), FlowState::Established);
+    assert_eq!(conntrack.active_flows(), 1);
+
+    sleep(timeout * 2);
+    assert_eq!(conntrack.active_flows(), 0);
+    // The reply direction now opens a fresh flow.
+    assert_eq!(conntrack.track(&Frame::new(&reply)), FlowState::New);
+    assert_eq!(conntrack.active_flows(), 1);
+}
+
+#[test]
+fn test_non_ip_frames_are_untracked() {
+    let mut conntrack = ConnTrack::new(Duration::from_secs(1));
+    assert_eq!(conntrack.track(&Frame::new(&[0u8; 60])), FlowState::Untracked);
+    assert_eq!(conntrack.active_flows(), 0);
+}
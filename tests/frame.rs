@@ -0,0 +1,146 @@
+use netmap_rs::{Error, Frame, Layers};
+
+#[test]
+fn test_write_to_copies_payload() {
+    let frame = Frame::new(b"hello netmap");
+    let mut out = Vec::new();
+
+    let written = frame.write_to(&mut out).unwrap();
+    assert_eq!(written, 12);
+    assert_eq!(out, b"hello netmap");
+}
+
+#[test]
+fn test_write_framed_to_prepends_length() {
+    let mut out = Vec::new();
+
+    let written = Frame::new(b"abc").write_framed_to(&mut out).unwrap();
+    assert_eq!(written, 5);
+    let written = Frame::new_owned(vec![0xff; 300]).write_framed_to(&mut out).unwrap();
+    assert_eq!(written, 302);
+
+    assert_eq!(&out[..5], &[0x00, 0x03, b'a', b'b', b'c']);
+    assert_eq!(&out[5..7], &300u16.to_be_bytes());
+    assert!(out[7..].iter().all(|&b| b == 0xff));
+    assert_eq!(out.len(), 307);
+}
+
+#[test]
+fn test_write_framed_to_rejects_oversized_frame() {
+    let frame = Frame::new_owned(vec![0; u16::MAX as usize + 1]);
+    let mut out = Vec::new();
+
+    let err = frame.write_framed_to(&mut out).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    assert!(out.is_empty());
+}
+
+/// Ethernet + 20-byte IPv4 header + 8-byte UDP header, no payload.
+fn udp_headers() -> Vec<u8> {
+    let mut frame = vec![0u8; 14 + 20 + 8];
+    frame[12..14].copy_from_slice(&[0x08, 0x00]);
+    frame[14] = 0x45;
+    frame[23] = 17;
+    frame
+}
+
+#[test]
+fn test_ensure_min_len_accepts_complete_udp_headers() {
+    let frame = Frame::new_owned(udp_headers());
+    frame.ensure_min_len(Layers::ETH | Layers::IPV4 | Layers::UDP).unwrap();
+    frame.ensure_min_len(Layers::UDP).unwrap();
+}
+
+#[test]
+fn test_ensure_min_len_rejects_frame_one_byte_short_of_udp() {
+    let mut data = udp_headers();
+    data.pop();
+    let frame = Frame::new_owned(data);
+
+    frame.ensure_min_len(Layers::ETH | Layers::IPV4).unwrap();
+    assert!(matches!(
+        frame.ensure_min_len(Layers::ETH | Layers::IPV4 | Layers::UDP),
+        Err(Error::MalformedPacket(_))
+    ));
+}
+
+#[test]
+fn test_ensure_min_len_honours_ipv4_options() {
+    // IHL of 6 words: a 24-byte IPv4 header, so the UDP header needs 4 more bytes.
+    let mut data = udp_headers();
+    data[14] = 0x46;
+    let frame = Frame::new_owned(data.clone());
+    assert!(frame.ensure_min_len(Layers::UDP).is_err());
+
+    data.extend_from_slice(&[0; 4]);
+    Frame::new_owned(data).ensure_min_len(Layers::UDP).unwrap();
+}
+
+/// Ethernet + IPv4 header with the given protocol around `l4`, with a consistent total length.
+fn ipv4_frame(protocol: u8, l4: &[u8]) -> Vec<u8> {
+    let mut frame = vec![0u8; 14 + 20];
+    frame[..6].copy_from_slice(&[0x02, 0, 0, 0, 0, 0x01]);
+    frame[6..12].copy_from_slice(&[0x02, 0, 0, 0, 0, 0x02]);
+    frame[12..14].copy_from_slice(&[0x08, 0x00]);
+    frame[14] = 0x45;
+    frame[16..18].copy_from_slice(&((20 + l4.len()) as u16).to_be_bytes());
+    frame[23] = protocol;
+    frame.extend_from_slice(l4);
+    frame
+}
+
+/// A small inner Ethernet/IPv4/UDP frame with a recognizable payload.
+fn inner_frame() -> Vec<u8> {
+    let mut udp = vec![0u8; 8];
+    udp[0..2].copy_from_slice(&1234u16.to_be_bytes());
+    udp[2..4].copy_from_slice(&5678u16.to_be_bytes());
+    udp[4..6].copy_from_slice(&(8 + 5u16).to_be_bytes());
+    udp.extend_from_slice(b"inner");
+    ipv4_frame(17, &udp)
+}
+
+#[test]
+fn test_decapsulate_vxlan() {
+    let inner = inner_frame();
+    let mut udp = vec![0u8; 8];
+    udp[0..2].copy_from_slice(&49152u16.to_be_bytes());
+    udp[2..4].copy_from_slice(&4789u16.to_be_bytes());
+    udp[4..6].copy_from_slice(&((8 + 8 + inner.len()) as u16).to_be_bytes());
+    udp.extend_from_slice(&[0x08, 0, 0, 0, 0, 0, 42, 0]); // VNI 42
+    udp.extend_from_slice(&inner);
+
+    let outer = Frame::new_owned(ipv4_frame(17, &udp));
+    let decapsulated = outer.decapsulate().expect("VXLAN frame was not decapsulated");
+    assert_eq!(decapsulated.payload(), &inner[..]);
+    assert!(decapsulated.decapsulate().is_none(), "inner frame is not tunneled");
+}
+
+#[test]
+fn test_decapsulate_gre_ethernet_and_ipv4() {
+    let inner = inner_frame();
+
+    // Transparent Ethernet Bridging with a key: the inner frame is carried whole.
+    let mut gre = vec![0x20, 0x00, 0x65, 0x58, 0, 0, 0, 7];
+    gre.extend_from_slice(&inner);
+    let decapsulated = Frame::new_owned(ipv4_frame(47, &gre)).decapsulate().expect("GRE/TEB not decapsulated");
+    assert_eq!(decapsulated.payload(), &inner[..]);
+
+    // A bare IPv4 payload gets the outer MAC addresses and an IPv4 EtherType.
+    let mut gre = vec![0x00, 0x00, 0x08, 0x00];
+    gre.extend_from_slice(&inner[14..]);
+    let outer = ipv4_frame(47, &gre);
+    let decapsulated = Frame::new_owned(outer.clone()).decapsulate().expect("GRE/IPv4 not decapsulated");
+    assert_eq!(&decapsulated[..12], &outer[..12]);
+    assert_eq!(&decapsulated[12..14], &[0x08, 0x00]);
+    assert_eq!(&decapsulated[14..], &inner[14..]);
+}
+
+#[test]
+fn test_decapsulate_ignores_plain_and_truncated_frames() {
+    assert!(Frame::new_owned(inner_frame()).decapsulate().is_none());
+    assert!(Frame::new(&[0u8; 60]).decapsulate().is_none());
+
+    // GRE claiming a checksum field it does not have room for.
+    let truncated = ipv4_frame(47, &[0x80, 0x00, 0x65, 0x58]);
+    assert!(Frame::new_owned(truncated).decapsulate().is_none());
+}
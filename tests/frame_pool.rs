@@ -0,0 +1,55 @@
+//! Allocation behaviour of `FramePool`, measured with a counting global allocator.
+//!
+//! Kept in its own test binary so the allocator only sees this file's test.
+
+use netmap_rs::{Frame, FramePool};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+#[test]
+fn test_pooled_frames_allocate_only_while_the_pool_warms_up() {
+    const FRAMES: usize = 10_000;
+    const HELD: usize = 8;
+
+    let packet = vec![0xab; 1500];
+    let pool = FramePool::new(HELD);
+    let mut held = VecDeque::with_capacity(HELD + 1);
+
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    for _ in 0..FRAMES {
+        held.push_back(Frame::new(&packet).clone_into_pool(&pool));
+        if held.len() > HELD {
+            held.pop_front();
+        }
+    }
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+
+    // One buffer per frame in flight; everything after that is reused. The slack covers
+    // the test harness allocating on other threads meanwhile.
+    assert!(allocations <= HELD + 1 + 16, "{} allocations for {} frames", allocations, FRAMES);
+    assert!(held.iter().all(|frame| frame.payload() == packet.as_slice()));
+}
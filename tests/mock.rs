@@ -1,5 +1,7 @@
-use netmap_rs::fallback::{create_fallback_channel, FallbackRxRing, FallbackTxRing};
-use netmap_rs::prelude::Error; // Only Error is explicitly used from prelude
+use netmap_rs::fallback::{
+    create_fallback_channel, create_fallback_channel_with_headroom, FallbackRxRing, FallbackTxRing,
+};
+use netmap_rs::prelude::{Error, Frame};
 use std::thread;
 use std::time::Duration;
 
@@ -67,3 +69,68 @@ fn test_threaded_fallback() {
     tx_handle.join().unwrap();
     rx_handle.join().unwrap();
 }
+
+#[test]
+fn test_fallback_send_at_offset() {
+    let (tx_ring, rx_ring) = create_fallback_channel_with_headroom(4, 16);
+
+    tx_ring.send_at_offset(8, b"payload").unwrap();
+    let (offset, frame) = rx_ring.recv_with_offset().unwrap();
+    assert_eq!(offset, 8);
+    assert_eq!(frame.len(), 8 + b"payload".len());
+    assert_eq!(&frame[..offset], &[0u8; 8], "headroom should be zeroed");
+    assert_eq!(&frame[offset..], b"payload");
+
+    // Plain recv strips the headroom.
+    tx_ring.send_at_offset(16, b"stripped").unwrap();
+    assert_eq!(rx_ring.recv().unwrap().payload(), b"stripped");
+
+    // Offsets beyond the configured headroom are rejected.
+    match tx_ring.send_at_offset(17, b"too far") {
+        Err(Error::FallbackUnsupported(_)) => { /* Expected */ }
+        other => panic!("Expected FallbackUnsupported, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_fallback_batch_roundtrip() {
+    let (mut tx_ring, rx_ring) = create_fallback_channel(8);
+
+    let mut batch = tx_ring.reserve_batch(3).unwrap();
+    for i in 0..3 {
+        batch.packet(i, 4).unwrap().copy_from_slice(&[i as u8; 4]);
+    }
+    assert!(matches!(batch.packet(3, 4), Err(Error::InvalidRingIndex(3))));
+    // Nothing is visible before commit.
+    assert!(rx_ring.recv().is_none());
+    batch.commit();
+
+    let mut frames: Vec<Frame<'static>> = (0..5).map(|_| Frame::new_owned(Vec::new())).collect();
+    let received = rx_ring.recv_batch(&mut frames);
+    assert_eq!(received.len(), 3);
+    for (i, frame) in received.iter().enumerate() {
+        assert_eq!(frame.payload(), &[i as u8; 4]);
+    }
+    assert!(rx_ring.recv_batch(&mut frames).is_empty());
+}
+
+#[test]
+fn test_fallback_reserve_batch_respects_max_size() {
+    let (mut tx_ring, rx_ring) = create_fallback_channel(4);
+    tx_ring.send(b"queued").unwrap();
+
+    match tx_ring.reserve_batch(4) {
+        Err(Error::InsufficientSpace) => { /* Expected */ }
+        Err(e) => panic!("Expected InsufficientSpace, got {:?}", e),
+        Ok(_) => panic!("Expected InsufficientSpace, but reservation succeeded"),
+    }
+    tx_ring.reserve_batch(3).unwrap().commit();
+
+    // recv_batch stops at the slice length, leaving the rest queued.
+    let mut frames = vec![Frame::new_owned(Vec::new()), Frame::new_owned(Vec::new())];
+    assert_eq!(rx_ring.recv_batch(&mut frames).len(), 2);
+    assert_eq!(frames[0].payload(), b"queued");
+    assert!(frames[1].is_empty());
+    assert_eq!(rx_ring.recv_batch(&mut frames).len(), 2);
+    assert!(rx_ring.recv().is_none());
+}
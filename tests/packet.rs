@@ -0,0 +1,160 @@
+use netmap_rs::packet::{fill_udp_checksum, flow_hash, segment_tcp, ETH_HEADER_LEN};
+use netmap_rs::Error;
+
+const IP_HEADER_LEN: usize = 20;
+const TCP_HEADER_LEN: usize = 20;
+const UDP_HEADER_LEN: usize = 8;
+
+// Builds an Ethernet/IPv4/TCP frame with FIN|PSH|ACK set and the given sequence number.
+fn tcp_frame(payload: &[u8], seq: u32) -> Vec<u8> {
+    let mut frame = vec![0u8; ETH_HEADER_LEN + IP_HEADER_LEN + TCP_HEADER_LEN];
+    frame[12..14].copy_from_slice(&0x0800u16.to_be_bytes());
+
+    let ip = &mut frame[ETH_HEADER_LEN..];
+    ip[0] = 0x45;
+    let total_len = (IP_HEADER_LEN + TCP_HEADER_LEN + payload.len()) as u16;
+    ip[2..4].copy_from_slice(&total_len.to_be_bytes());
+    ip[4..6].copy_from_slice(&100u16.to_be_bytes());
+    ip[8] = 64;
+    ip[9] = 6;
+    ip[12..16].copy_from_slice(&[10, 0, 0, 1]);
+    ip[16..20].copy_from_slice(&[10, 0, 0, 2]);
+
+    let tcp = &mut ip[IP_HEADER_LEN..];
+    tcp[0..2].copy_from_slice(&1234u16.to_be_bytes());
+    tcp[2..4].copy_from_slice(&80u16.to_be_bytes());
+    tcp[4..8].copy_from_slice(&seq.to_be_bytes());
+    tcp[12] = 5 << 4;
+    tcp[13] = 0x19; // FIN | PSH | ACK
+
+    frame.extend_from_slice(payload);
+    frame
+}
+
+// Builds an Ethernet/IPv4/UDP frame with a zero ("none") UDP checksum.
+fn udp_frame(payload: &[u8]) -> Vec<u8> {
+    let mut frame = tcp_frame(&[], 0);
+    frame.truncate(ETH_HEADER_LEN + IP_HEADER_LEN);
+    let ip = &mut frame[ETH_HEADER_LEN..];
+    ip[2..4].copy_from_slice(&((IP_HEADER_LEN + UDP_HEADER_LEN + payload.len()) as u16).to_be_bytes());
+    ip[9] = 17;
+
+    frame.extend_from_slice(&12345u16.to_be_bytes());
+    frame.extend_from_slice(&5000u16.to_be_bytes());
+    frame.extend_from_slice(&((UDP_HEADER_LEN + payload.len()) as u16).to_be_bytes());
+    frame.extend_from_slice(&[0, 0]);
+    frame.extend_from_slice(payload);
+    frame
+}
+
+// Ones'-complement sum used to verify header and pseudo-header checksums.
+fn ones_complement_sum(data: &[u8]) -> u16 {
+    let mut sum: u32 = data
+        .chunks(2)
+        .map(|c| u16::from_be_bytes([c[0], *c.get(1).unwrap_or(&0)]) as u32)
+        .sum();
+    while sum >> 16 != 0 {
+        sum = (sum & 0xffff) + (sum >> 16);
+    }
+    sum as u16
+}
+
+#[test]
+fn test_segment_tcp_splits_payload_in_order() {
+    let payload: Vec<u8> = (0..3500u32).map(|i| i as u8).collect();
+    let seq = 0xffff_fc00; // close to wrapping, to exercise sequence arithmetic
+    let mss = 1000;
+
+    let segments = segment_tcp(&tcp_frame(&payload, seq), mss).expect("segmentation failed");
+    assert_eq!(segments.len(), 4);
+
+    let headers_len = ETH_HEADER_LEN + IP_HEADER_LEN + TCP_HEADER_LEN;
+    let mut reassembled = Vec::new();
+    for (i, seg) in segments.iter().enumerate() {
+        let ip = &seg[ETH_HEADER_LEN..ETH_HEADER_LEN + IP_HEADER_LEN];
+        let tcp = &seg[ETH_HEADER_LEN + IP_HEADER_LEN..];
+        let chunk = &seg[headers_len..];
+        assert!(chunk.len() <= mss as usize, "segment {} exceeds the MSS", i);
+
+        assert_eq!(u16::from_be_bytes([ip[2], ip[3]]) as usize, seg.len() - ETH_HEADER_LEN);
+        assert_eq!(u16::from_be_bytes([ip[4], ip[5]]), 100 + i as u16);
+        assert_eq!(ones_complement_sum(ip), 0xffff, "bad IPv4 checksum on segment {}", i);
+
+        let expected_seq = seq.wrapping_add(reassembled.len() as u32);
+        assert_eq!(u32::from_be_bytes([tcp[4], tcp[5], tcp[6], tcp[7]]), expected_seq);
+        let is_last = i + 1 == segments.len();
+        assert_eq!(tcp[13] & 0x09 != 0, is_last, "FIN/PSH only belong on the last segment");
+
+        let mut pseudo = ip[12..20].to_vec();
+        pseudo.extend_from_slice(&[0, 6]);
+        pseudo.extend_from_slice(&(tcp.len() as u16).to_be_bytes());
+        pseudo.extend_from_slice(tcp);
+        assert_eq!(ones_complement_sum(&pseudo), 0xffff, "bad TCP checksum on segment {}", i);
+
+        reassembled.extend_from_slice(chunk);
+    }
+    assert_eq!(reassembled, payload, "segments do not add up to the original payload");
+}
+
+#[test]
+fn test_segment_tcp_small_frame_is_untouched() {
+    let frame = tcp_frame(b"fits in one segment", 1);
+    let segments = segment_tcp(&frame, 1460).expect("segmentation failed");
+    assert_eq!(segments, vec![frame]);
+}
+
+#[test]
+fn test_segment_tcp_rejects_non_tcp() {
+    let mut frame = tcp_frame(b"not tcp", 1);
+    frame[ETH_HEADER_LEN + 9] = 17; // UDP
+    assert!(matches!(segment_tcp(&frame, 1460), Err(Error::MalformedPacket(_))));
+}
+
+#[test]
+fn test_fill_udp_checksum_for_host_injection() {
+    // Odd payload length, to exercise the trailing-byte padding.
+    let mut frame = udp_frame(b"Hello from netmap-rs to host stack!");
+    fill_udp_checksum(&mut frame).expect("checksum failed");
+
+    let ip = &frame[ETH_HEADER_LEN..ETH_HEADER_LEN + IP_HEADER_LEN];
+    let udp = &frame[ETH_HEADER_LEN + IP_HEADER_LEN..];
+    assert_ne!(&udp[6..8], &[0, 0], "UDP checksum left unset");
+
+    let mut pseudo = ip[12..20].to_vec();
+    pseudo.extend_from_slice(&[0, 17]);
+    pseudo.extend_from_slice(&(udp.len() as u16).to_be_bytes());
+    pseudo.extend_from_slice(udp);
+    assert_eq!(ones_complement_sum(&pseudo), 0xffff, "bad UDP checksum");
+}
+
+#[test]
+fn test_fill_udp_checksum_rejects_non_udp() {
+    let mut frame = tcp_frame(b"not udp", 1);
+    assert!(matches!(fill_udp_checksum(&mut frame), Err(Error::MalformedPacket(_))));
+
+    let mut frame = udp_frame(b"bad length");
+    frame.truncate(frame.len() - 1);
+    assert!(matches!(fill_udp_checksum(&mut frame), Err(Error::MalformedPacket(_))));
+}
+
+#[test]
+fn test_flow_hash_is_symmetric_and_distinguishes_flows() {
+    let forward = udp_frame(b"x");
+    let mut reverse = forward.clone();
+    // Swap IPv4 addresses and UDP ports.
+    reverse[ETH_HEADER_LEN + 12..ETH_HEADER_LEN + 16].copy_from_slice(&forward[ETH_HEADER_LEN + 16..ETH_HEADER_LEN + 20]);
+    reverse[ETH_HEADER_LEN + 16..ETH_HEADER_LEN + 20].copy_from_slice(&forward[ETH_HEADER_LEN + 12..ETH_HEADER_LEN + 16]);
+    let udp = ETH_HEADER_LEN + IP_HEADER_LEN;
+    reverse[udp..udp + 2].copy_from_slice(&forward[udp + 2..udp + 4]);
+    reverse[udp + 2..udp + 4].copy_from_slice(&forward[udp..udp + 2]);
+    assert_eq!(flow_hash(&forward), flow_hash(&reverse));
+
+    let mut other_port = forward.clone();
+    other_port[udp..udp + 2].copy_from_slice(&4242u16.to_be_bytes());
+    assert_ne!(flow_hash(&forward), flow_hash(&other_port));
+
+    // Non-IP and truncated frames still hash without panicking.
+    flow_hash(&[0xff; 14]);
+    flow_hash(&forward[..20]);
+    flow_hash(&[]);
+}
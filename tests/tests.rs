@@ -91,7 +91,11 @@ mod updated_tests {
             Error::InvalidRingIndex(42),
             Error::PacketTooLarge(9000),
             Error::InsufficientSpace,
+            Error::BuffersExhausted,
             Error::UnsupportedPlatform("test platform".to_string()),
+            Error::MalformedPacket("test packet".to_string()),
+            Error::ChecksumMismatch,
+            Error::Timeout,
             Error::FallbackUnsupported("test feature".to_string()),
         ];
         
@@ -0,0 +1,80 @@
+#![cfg(all(unix, feature = "sys"))]
+
+// Counts heap allocations to check that the borrowed receive path never copies.
+// Like the integration tests, this needs the netmap module (it uses a pipe).
+use netmap_rs::prelude::*;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::time::{Duration, Instant};
+
+struct CountingAlloc;
+
+thread_local! {
+    // Per thread, so allocations made by other tests running in parallel are not counted.
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+fn allocations() -> usize {
+    ALLOCATIONS.with(Cell::get)
+}
+
+const BATCH: usize = 32;
+
+/// Sends `BATCH` packets and waits until all of them are visible on `rx_ring`.
+fn fill(tx_ring: &mut TxRing, rx_ring: &mut RxRing) {
+    for i in 0..BATCH {
+        tx_ring.send(&[i as u8; 64]).expect("Send failed");
+    }
+    tx_ring.sync();
+
+    let deadline = Instant::now() + Duration::from_secs(1);
+    while rx_ring.available() < BATCH {
+        assert!(Instant::now() < deadline, "Timeout: only {} of {} packets arrived", rx_ring.available(), BATCH);
+        rx_ring.sync();
+        std::thread::sleep(Duration::from_micros(50));
+    }
+}
+
+#[test]
+fn test_recv_batch_borrowed_does_not_allocate() {
+    const PIPE_NAME: &str = "netmap:pipe{zero_copy_alloc}";
+    let nm_tx = NetmapBuilder::new(PIPE_NAME).build().expect("Failed to open pipe TX endpoint");
+    let nm_rx = NetmapBuilder::new(PIPE_NAME).build().expect("Failed to open pipe RX endpoint");
+    let mut tx_ring = nm_tx.tx_ring(0).expect("Failed to get TX ring");
+    let mut rx_ring = nm_rx.rx_ring(0).expect("Failed to get RX ring");
+
+    fill(&mut tx_ring, &mut rx_ring);
+    let before = allocations();
+    let mut bytes = 0;
+    for frame in rx_ring.recv_batch_borrowed(BATCH) {
+        bytes += frame.len();
+    }
+    let borrowed_allocs = allocations() - before;
+    assert_eq!(bytes, BATCH * 64);
+
+    fill(&mut tx_ring, &mut rx_ring);
+    let before = allocations();
+    let mut owned = Vec::with_capacity(BATCH);
+    while let Some(frame) = rx_ring.recv() {
+        owned.push(frame.payload().to_vec());
+    }
+    let owned_allocs = allocations() - before;
+    assert_eq!(owned.len(), BATCH);
+
+    assert_eq!(borrowed_allocs, 0, "recv_batch_borrowed allocated");
+    assert!(owned_allocs >= BATCH, "owned path made only {} allocations", owned_allocs);
+}